@@ -0,0 +1,120 @@
+use farmbotsim_core::environment::station_module::station::{StationId, StationPosType};
+use farmbotsim_core::prelude::{AgentId, AgentState, EventKind, EventLog};
+use std::collections::BTreeMap;
+
+/// Event counts of a single agent.
+#[derive(Default)]
+struct AgentEventSummary {
+    assigned: u32,
+    completed: u32,
+    returned: u32,
+    state_changes: u32,
+    charging_sessions: u32,
+    discharged: bool,
+    collisions: u32,
+    near_misses: u32,
+}
+
+/// Event counts of a single station.
+#[derive(Default)]
+struct StationEventSummary {
+    slot_entries: u32,
+    queue_entries: u32,
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let jsonl_path = std::env::args()
+        .nth(1)
+        .unwrap_or("analyze/events.jsonl".to_string());
+    let event_log = EventLog::from_jsonl_file(&jsonl_path)?;
+
+    let mut n_created = 0;
+    let mut agents: BTreeMap<AgentId, AgentEventSummary> = BTreeMap::new();
+    let mut stations: BTreeMap<StationId, StationEventSummary> = BTreeMap::new();
+    for event in &event_log.events {
+        match &event.kind {
+            EventKind::TaskCreated { .. } => n_created += 1,
+            EventKind::TaskAssigned { agent_id, .. } => {
+                agents.entry(*agent_id).or_default().assigned += 1
+            }
+            EventKind::TaskCompleted { agent_id, .. } => {
+                agents.entry(*agent_id).or_default().completed += 1
+            }
+            EventKind::TaskReturned { agent_id, .. } => {
+                agents.entry(*agent_id).or_default().returned += 1
+            }
+            EventKind::StateChanged { agent_id, to, .. } => {
+                let summary = agents.entry(*agent_id).or_default();
+                summary.state_changes += 1;
+                if *to == AgentState::Charging {
+                    summary.charging_sessions += 1;
+                }
+            }
+            EventKind::StationEntered {
+                station_id,
+                pos_type,
+                ..
+            } => {
+                let summary = stations.entry(*station_id).or_default();
+                match pos_type {
+                    StationPosType::ChargingSlot => summary.slot_entries += 1,
+                    StationPosType::QueueSlot => summary.queue_entries += 1,
+                }
+            }
+            EventKind::StationLeft { .. } => {}
+            EventKind::BatteryDischarged { agent_id } => {
+                agents.entry(*agent_id).or_default().discharged = true
+            }
+            EventKind::Collision {
+                agent_id,
+                other_agent_id,
+            } => {
+                agents.entry(*agent_id).or_default().collisions += 1;
+                agents.entry(*other_agent_id).or_default().collisions += 1;
+            }
+            EventKind::NearMiss {
+                agent_id,
+                other_agent_id,
+            } => {
+                agents.entry(*agent_id).or_default().near_misses += 1;
+                agents.entry(*other_agent_id).or_default().near_misses += 1;
+            }
+        }
+    }
+
+    match event_log.events.last() {
+        Some(last) => println!(
+            "{} events over {} steps ({})",
+            event_log.events.len(),
+            last.step,
+            last.time
+        ),
+        None => println!("No events in {jsonl_path}"),
+    }
+    println!("Tasks created: {n_created}");
+
+    println!("\nAgents:");
+    println!(
+        "| id | assigned | completed | returned | state changes | charging | discharged | collisions | near misses |"
+    );
+    for (id, s) in &agents {
+        println!(
+            "| {id} | {} | {} | {} | {} | {} | {} | {} | {} |",
+            s.assigned,
+            s.completed,
+            s.returned,
+            s.state_changes,
+            s.charging_sessions,
+            s.discharged,
+            s.collisions,
+            s.near_misses
+        );
+    }
+
+    println!("\nStations:");
+    println!("| id | slot entries | queue entries |");
+    for (id, s) in &stations {
+        println!("| {id} | {} | {} |", s.slot_entries, s.queue_entries);
+    }
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use std::time::Instant;
+
+use farmbotsim_core::prelude::*;
+
+fn main() {
+    // Parameters
+    let field_config_path = DEFAULT_FIELD_CONFIG_PATH.to_string();
+    let n_pairs = 200;
+    let planner_kinds = vec![
+        PathPlannerKind::VisibilityGraph,
+        PathPlannerKind::Grid {
+            cell_size: Length::meters(0.1),
+            any_angle: false,
+        },
+        PathPlannerKind::Grid {
+            cell_size: Length::meters(0.1),
+            any_angle: true,
+        },
+        PathPlannerKind::Grid {
+            cell_size: Length::meters(0.05),
+            any_angle: true,
+        },
+    ];
+
+    let field_config: FieldConfig = load_json_or_panic(&field_config_path);
+    let points = field_config.get_graph_points();
+    let obstacles = field_config.get_obstacles();
+
+    // Deterministic pairs of crop and row positions spread over the field
+    let farm_entities = field_config.get_farm_entities();
+    let mut farm_entities: Vec<_> = farm_entities.values().collect();
+    farm_entities.sort_by_key(|farm_entity| farm_entity.get_id());
+    let targets: Vec<_> = farm_entities
+        .into_iter()
+        .map(|farm_entity| match farm_entity {
+            FarmEntity::Crop(crop) => crop.position,
+            FarmEntity::Row(row) => row.path[0],
+        })
+        .collect();
+    let step = (targets.len() / 2).max(1);
+    let pairs: Vec<_> = (0..n_pairs)
+        .filter(|_| targets.len() >= 2)
+        .map(|i| {
+            let a = (i * 7) % targets.len();
+            let b = (a + step + i) % targets.len();
+            (targets[a], targets[b])
+        })
+        .collect();
+
+    println!("Field: {field_config_path} | Path pairs: {}", pairs.len());
+    for kind in planner_kinds {
+        let start = Instant::now();
+        let mut path_planner = PathPlanner::new(kind, &points, obstacles.clone());
+        path_planner.set_no_go_zones(field_config.no_go_zones.clone());
+        let build_time = start.elapsed();
+
+        let mut planning_times = vec![];
+        let mut lengths = vec![];
+        for (a, b) in &pairs {
+            let start = Instant::now();
+            let path = path_planner.find_path(*a, *b);
+            planning_times.push(start.elapsed().as_secs_f32() * 1000.0);
+            if let Some(path) = path {
+                lengths.push(path.windows(2).map(|w| w[0].distance(w[1])).sum::<f32>());
+            }
+        }
+
+        let planning_time = summarize(planning_times.into_iter());
+        let found = lengths.len();
+        let length = if found > 0 {
+            summarize(lengths.into_iter())
+        } else {
+            StatSummary::default()
+        };
+        println!(
+            "{:<22} | build (ms): {:>9.3} | planning (ms): avg = {:>8.3}, max = {:>8.3} | found: {:>4} | length (m): avg = {:>7.3}",
+            match kind {
+                PathPlannerKind::Grid {
+                    cell_size,
+                    any_angle,
+                } => format!(
+                    "Grid {} ({cell_size})",
+                    if any_angle { "Theta*" } else { "A*" }
+                ),
+                _ => format!("{kind:?}"),
+            },
+            build_time.as_secs_f32() * 1000.0,
+            planning_time.avg,
+            planning_time.max,
+            found,
+            length.avg,
+        );
+    }
+}
@@ -6,6 +6,7 @@ fn main() {
     let agent_config_path = DEFAULT_AGENT_CONFIG_PATH.to_string();
     let n_episodes = 1;
     let termination_duration = Duration::days(7.0);
+    let fast_forward = false; // skip idle periods by jumping to the next event
     let number_agents = vec![1, 2, 3, 4, 5, 6];
     let charging_strategies = vec![
         ChargingStrategy::CriticalOnly(20.0),
@@ -42,14 +43,21 @@ fn main() {
         }
     }
 
-    let results = run_combinations(
+    let results = match run_combinations(
         scene_config_path.clone(),
         agent_config_path.clone(),
         n_episodes,
         termination_duration,
+        fast_forward,
         &number_agents,
         &combinations,
-    );
+    ) {
+        Ok(results) => results,
+        Err(e) => {
+            eprintln!("Failed to create simulation: {e}");
+            return;
+        }
+    };
 
     let experiment_output = ExperimentOutput {
         parameters: ExperimentParameters {
@@ -81,9 +89,10 @@ fn run_combinations(
     agent_config_path: String,
     n_episodes: u32,
     termination_duration: Duration,
+    fast_forward: bool,
     number_agents: &[u32],
     combinations: &Vec<Combination>,
-) -> Vec<AnalyzeEnvResult> {
+) -> Result<Vec<AnalyzeEnvResult>, FarmbotSimError> {
     let mut results = vec![];
 
     for n_agents in number_agents {
@@ -96,28 +105,25 @@ fn run_combinations(
             );
             let mut config_stats = vec![];
             let env_config = EnvConfig {
-                agent_config_path: agent_config_path.clone(),
+                fleet: vec![FleetGroup::new(agent_config_path.clone(), *n_agents)],
                 scene_config_path: scene_config_path.clone(),
+                fast_forward,
                 ..Default::default()
             };
-            let mut env = Env::from_config(env_config.clone());
-            env.n_agents = *n_agents;
-            env.task_manager.charging_strategy = c.charging_strategy.clone();
-            env.task_manager.choose_station_strategy = c.station_strategy.clone();
+            let mut simulation = Simulation::try_from_config(
+                env_config.clone(),
+                TerminationCondition::EnvDuration(termination_duration),
+            )?;
+            simulation.env.task_manager.charging_strategy = c.charging_strategy.clone();
+            simulation.env.task_manager.choose_station_strategy = c.station_strategy.clone();
             for i in 0..n_episodes {
                 println!("Episode {}/{n_episodes}", i + 1);
-                env.reset();
-                while env.duration < termination_duration {
-                    env.task_manager
-                        .assign_tasks(&mut env.agents, &mut env.stations);
-                    env.step();
-                }
-                let episode_stats = env.get_env_episode_stats();
+                let episode_stats = simulation.run_episode();
                 config_stats.push(episode_stats);
             }
-            let env_result = AnalyzeEnvResult::from_episodes(*n_agents, c.clone(), config_stats);
+            let env_result = AnalyzeEnvResult::from_episodes(c.clone(), config_stats);
             results.push(env_result);
         }
     }
-    results
+    Ok(results)
 }
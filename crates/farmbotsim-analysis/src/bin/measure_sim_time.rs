@@ -80,25 +80,23 @@ fn run_combinations(
             );
 
             let env_config = EnvConfig {
-                agent_config_path: agent_config_path.clone(),
+                fleet: vec![FleetGroup::new(agent_config_path.clone(), *n_agents)],
                 scene_config_path: scene_config_path.clone(),
                 ..Default::default()
             };
-            let mut env = Env::from_config(env_config.clone());
-            env.n_agents = *n_agents;
-            env.task_manager.charging_strategy = charging_strategy.clone();
-            env.task_manager.choose_station_strategy = station_strategy.clone();
+            let mut simulation = Simulation::from_config(
+                env_config.clone(),
+                TerminationCondition::EnvDuration(termination_duration),
+            );
+            simulation.env.task_manager.charging_strategy = charging_strategy.clone();
+            simulation.env.task_manager.choose_station_strategy = station_strategy.clone();
 
             for ep in 0..n_episodes {
                 println!("  Episode {}/{}", ep + 1, n_episodes);
-                env.reset();
+                simulation.start_episode();
 
                 let start = Instant::now();
-                while env.duration < termination_duration {
-                    env.task_manager
-                        .assign_tasks(&mut env.agents, &mut env.stations);
-                    env.step();
-                }
+                simulation.run_to_termination();
                 let elapsed = start.elapsed().as_secs_f32();
                 all_episode_times.push(elapsed);
                 println!("    ⏱ {elapsed:.3} seconds");
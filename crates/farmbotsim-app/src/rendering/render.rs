@@ -1,8 +1,10 @@
 use std::borrow::Borrow;
 
+use chrono::{NaiveDateTime, NaiveTime, TimeDelta, Timelike};
+
 use egui::epaint::CircleShape;
 use egui::{Align2, Color32, Grid, Pos2, RichText, Shape, Stroke, Ui, Vec2};
-use egui_plot::{Line, Plot, PlotPoint, PlotPoints, Text};
+use egui_plot::{Line, Plot, PlotPoint, PlotPoints, Text, VLine};
 
 use crate::rendering::camera::Camera;
 use farmbotsim_core::prelude::*;
@@ -66,12 +68,50 @@ pub fn render_obstacles(ui: &mut Ui, camera: &Camera, obstacles: &Vec<Obstacle>)
     }
 }
 
+/// Draws no-go zones as translucent areas, the fill assumes convex zones.
+pub fn render_no_go_zones(ui: &mut Ui, camera: &Camera, no_go_zones: &[NoGoZone]) {
+    let painter = ui.painter();
+    for zone in no_go_zones {
+        let points: Vec<Pos2> = zone
+            .points
+            .iter()
+            .map(|pos| camera.scene_to_screen_pos(*pos))
+            .collect();
+        painter.add(Shape::convex_polygon(
+            points.clone(),
+            Color32::from_rgba_unmultiplied(220, 60, 60, 40),
+            Stroke::NONE,
+        ));
+        painter.add(Shape::closed_line(
+            points,
+            Stroke::new(1.0, Color32::from_rgb(220, 60, 60)),
+        ));
+    }
+}
+
+/// Draws the outlines of obstacles inflated for path planning.
+pub fn render_inflated_obstacles(ui: &mut Ui, camera: &Camera, obstacles: &[Obstacle]) {
+    let painter = ui.painter();
+    for obs in obstacles {
+        let points: Vec<Pos2> = obs
+            .points
+            .iter()
+            .map(|pos| camera.scene_to_screen_pos(*pos))
+            .collect();
+        painter.add(Shape::closed_line(
+            points,
+            Stroke::new(1.0, Color32::from_rgb(200, 100, 0)),
+        ));
+    }
+}
+
 /// Draws agents with orientation indicators and their current task paths if any.
+/// Agents with odometry noise also get an outline at their estimated position.
 pub fn render_agents(ui: &mut Ui, camera: &Camera, agents: &Vec<Agent>) {
     let painter = ui.painter();
-    let radius = camera.scene_to_screen_val(0.15);
     let length = camera.scene_to_screen_val(0.5);
     for agent in agents {
+        let radius = camera.scene_to_screen_val(agent.footprint_radius.to_base_unit());
         let center = camera.scene_to_screen_pos(agent.pose.position);
         painter.add(CircleShape {
             center,
@@ -98,8 +138,17 @@ pub fn render_agents(ui: &mut Ui, camera: &Camera, agents: &Vec<Agent>) {
             color: Color32::MAGENTA,
         };
 
+        if agent.movement.noise().is_some() {
+            painter.add(CircleShape {
+                center: camera.scene_to_screen_pos(agent.estimated_pose.position),
+                radius,
+                fill: Color32::TRANSPARENT,
+                stroke: Stroke::new(camera.scene_to_screen_val(0.01), agent.color),
+            });
+        }
+
         if let Some(task) = &agent.current_task {
-            let mut path = vec![agent.pose.clone()];
+            let mut path = vec![agent.estimated_pose.clone()];
             if let Some(path_) = task.get_path() {
                 path.extend(path_);
             }
@@ -141,6 +190,19 @@ pub fn render_visibility_graph(ui: &mut Ui, camera: &Camera, visibility_graph: &
     }
 }
 
+/// Renders blocked cells of the grid planner.
+pub fn render_grid_planner(ui: &mut Ui, camera: &Camera, grid_planner: &GridPlanner) {
+    let color = Color32::from_rgba_unmultiplied(255, 100, 100, 60);
+    for cell in &grid_planner.blocked {
+        let corners = grid_planner.cell_corners(*cell);
+        let rect = egui::Rect::from_two_pos(
+            camera.scene_to_screen_pos(corners[0]),
+            camera.scene_to_screen_pos(corners[2]),
+        );
+        ui.painter().rect_filled(rect, 0.0, color);
+    }
+}
+
 /// Renders a station with slots and optionally visualizes its parameters.
 pub fn render_station(ui: &mut Ui, camera: &Camera, station: &Station, with_params: bool) {
     let painter = ui.painter();
@@ -413,6 +475,8 @@ pub fn ui_render_agents(ui: &mut Ui, agents: &Vec<Agent>, show_battery_plot: boo
         // ui.label("Battery  ");
         ui.label("Current task");
         ui.label("Work Schedule");
+        ui.label("Contacts");
+        ui.label("Health");
         ui.label("Battery");
         ui.end_row();
 
@@ -424,7 +488,11 @@ pub fn ui_render_agents(ui: &mut Ui, agents: &Vec<Agent>, show_battery_plot: boo
                 "{}°",
                 agent.pose.orientation.to_degrees().round() as i32
             ));
-            ui.label(format!("{:?}", agent.state));
+            if agent.yielding {
+                ui.label(format!("{:?} (yielding)", agent.state));
+            } else {
+                ui.label(format!("{:?}", agent.state));
+            }
             match &agent.current_task {
                 Some(task) => {
                     ui.label(format!("{:?}", task.get_intent()));
@@ -434,6 +502,8 @@ pub fn ui_render_agents(ui: &mut Ui, agents: &Vec<Agent>, show_battery_plot: boo
                 }
             }
             ui.label(agent.work_schedule.len().to_string());
+            ui.label(format!("{} / {}", agent.n_collisions, agent.n_near_misses));
+            ui.label(format!("{:.2}%", agent.battery.state_of_health()));
             if !show_battery_plot {
                 ui.label(format!("{:.2}%", agent.battery.get_soc()));
             } else {
@@ -549,8 +619,52 @@ pub fn ui_render_stations(ui: &mut Ui, stations: &Vec<Station>) {
     });
 }
 
-/// Displays the task manager's work, assigned, and completed tasks in collapsible grids.
-pub fn ui_render_task_manager(ui: &mut Ui, task_manager: &TaskManager) {
+/// Plots the irradiance over the current day for every solar-powered station,
+/// with the current time marked.
+pub fn ui_render_solar_irradiance(ui: &mut Ui, stations: &[Station], time: &NaiveDateTime) {
+    for station in stations {
+        let Some(budget) = &station.power_budget else {
+            continue;
+        };
+        let PowerSource::Solar(solar) = &budget.source else {
+            continue;
+        };
+        ui.horizontal(|ui| {
+            ui.label(RichText::new("⏺").color(station.color));
+            ui.label(format!(
+                "Station {} irradiance: {:.0} W/m², available power: {}",
+                station.id,
+                solar.irradiance(time),
+                budget.available_power(time)
+            ));
+        });
+        let midnight = time.date().and_time(NaiveTime::MIN);
+        let points: PlotPoints = (0..=24 * 4)
+            .map(|quarter| {
+                let t = midnight + TimeDelta::minutes(15 * quarter);
+                [quarter as f64 / 4.0, f64::from(solar.irradiance(&t))]
+            })
+            .collect();
+        let hours = f64::from(time.num_seconds_from_midnight()) / 3600.0;
+        Plot::new(format!("irradiance_plot_{}", station.id))
+            .allow_drag(false)
+            .allow_scroll(false)
+            .allow_zoom(false)
+            .height(100.0)
+            .default_x_bounds(0.0, 24.0)
+            .include_y(0.0)
+            .x_axis_label("hour")
+            .y_axis_label("W/m²")
+            .show(ui, |plot_ui| {
+                plot_ui.line(Line::new("irradiance", points).color(station.color));
+                plot_ui.vline(VLine::new("now", hours).color(Color32::LIGHT_RED));
+            });
+    }
+}
+
+/// Displays the task manager's work, assigned, and completed tasks in collapsible grids,
+/// together with the work tasks none of the agents is capable of.
+pub fn ui_render_task_manager(ui: &mut Ui, task_manager: &TaskManager, agents: &[Agent]) {
     fn make_grid_from<I>(ui: &mut Ui, label: String, iterator: I)
     where
         I: IntoIterator,
@@ -569,6 +683,7 @@ pub fn ui_render_task_manager(ui: &mut Ui, task_manager: &TaskManager) {
                 ui.label("Line id");
                 ui.label("Power");
                 ui.label("Info");
+                ui.label("Requires");
                 ui.end_row();
 
                 struct TaskInfo<'a> {
@@ -581,6 +696,7 @@ pub fn ui_render_task_manager(ui: &mut Ui, task_manager: &TaskManager) {
                     lid: u32,
                     power: Power,
                     info: &'a str,
+                    requires: &'a [String],
                 }
 
                 fn display_task_info(ui: &mut Ui, task_info: TaskInfo) {
@@ -593,6 +709,7 @@ pub fn ui_render_task_manager(ui: &mut Ui, task_manager: &TaskManager) {
                     ui.label(task_info.lid.to_string());
                     ui.label(format!("{}", task_info.power));
                     ui.label(task_info.info);
+                    ui.label(task_info.requires.join(", "));
                     ui.end_row();
                 }
 
@@ -607,6 +724,7 @@ pub fn ui_render_task_manager(ui: &mut Ui, task_manager: &TaskManager) {
                             duration,
                             power,
                             info,
+                            required_capabilities,
                             ..
                         } => {
                             let task_type = "Stationary";
@@ -628,6 +746,7 @@ pub fn ui_render_task_manager(ui: &mut Ui, task_manager: &TaskManager) {
                                     lid: *lid,
                                     power: *power,
                                     info,
+                                    requires: required_capabilities,
                                 },
                             );
                         }
@@ -639,6 +758,7 @@ pub fn ui_render_task_manager(ui: &mut Ui, task_manager: &TaskManager) {
                             velocity,
                             power,
                             info,
+                            required_capabilities,
                             ..
                         } => {
                             let task_type = "Moving";
@@ -661,6 +781,7 @@ pub fn ui_render_task_manager(ui: &mut Ui, task_manager: &TaskManager) {
                                     lid: *lid,
                                     power: *power,
                                     info,
+                                    requires: required_capabilities,
                                 },
                             );
                         }
@@ -672,7 +793,28 @@ pub fn ui_render_task_manager(ui: &mut Ui, task_manager: &TaskManager) {
     }
 
     ui.label("Task manager");
+    ui.label(format!(
+        "Path planner: {:?}",
+        task_manager.path_planner_kind()
+    ));
+    let path_cache = task_manager.path_cache();
+    ui.label(format!(
+        "Path cache: {} key points, {} paths, {} hits, {} misses",
+        path_cache.n_key_points(),
+        path_cache.n_paths(),
+        path_cache.hits,
+        path_cache.misses
+    ));
+    ui.label(format!(
+        "Planning fallbacks: {}",
+        task_manager.n_planning_fallbacks()
+    ));
     make_grid_from(ui, "Work List".to_string(), &task_manager.work_list);
+    make_grid_from(
+        ui,
+        "Blocked List".to_string(),
+        task_manager.blocked_tasks(agents),
+    );
     make_grid_from(
         ui,
         "Assigned List".to_string(),
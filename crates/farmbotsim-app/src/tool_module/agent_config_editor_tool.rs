@@ -2,17 +2,24 @@ use egui::Ui;
 
 use crate::{
     tool_module::{has_config_saving::HasConfigSaving, has_help::HasHelp, tool::Tool},
-    utilities::ui::{folder_select_combo, json_config_combo},
+    utilities::ui::{
+        capabilities_editor, error_label, folder_select_combo, json_config_combo,
+        value_with_unit_selector_ui,
+    },
 };
 use farmbotsim_core::prelude::*;
 
 /// A tool for editing agent configuration files.
 pub struct AgentConfigEditorTool {
     save_file_name: String,
+    load_error: Option<String>,
     pub current_agent_config_path: String,
     pub current_movement_path: String,
     pub current_battery_path: String,
     pub current_battery_soc: f32,
+    pub current_footprint_radius: Length,
+    pub current_capabilities: Vec<String>,
+    pub current_power_model: PowerModel,
     pub help_open: bool,
 }
 
@@ -23,10 +30,14 @@ impl Default for AgentConfigEditorTool {
 
         Self {
             save_file_name: String::new(),
+            load_error: None,
             current_agent_config_path: file_path.to_string(),
             current_movement_path: agent_config.movement,
             current_battery_path: agent_config.battery,
             current_battery_soc: agent_config.battery_soc,
+            current_footprint_radius: agent_config.footprint_radius,
+            current_capabilities: agent_config.capabilities,
+            current_power_model: agent_config.power_model,
             help_open: false,
         }
     }
@@ -51,6 +62,23 @@ impl Tool for AgentConfigEditorTool {
             ui.add(egui::DragValue::new(&mut self.current_battery_soc).range(0.0..=100.0));
         });
 
+        ui.horizontal(|ui| {
+            ui.label(r#"   "footprint_radius":"#);
+            ui.add(
+                egui::DragValue::new(&mut self.current_footprint_radius.value)
+                    .speed(0.01)
+                    .range(0.01..=2.0)
+                    .suffix(format!(" {}", self.current_footprint_radius.unit)),
+            );
+        });
+
+        ui.horizontal(|ui| {
+            ui.label(r#"   "capabilities":"#);
+            capabilities_editor(ui, "agent_capabilities", &mut self.current_capabilities);
+        });
+
+        self.ui_power_model(ui);
+
         ui.label(r#"}"#);
     }
 
@@ -79,12 +107,69 @@ impl AgentConfigEditorTool {
             && new_value != self.current_agent_config_path
         {
             self.current_agent_config_path = new_value;
-            let agent_config: AgentConfig =
-                load_json_or_panic(self.current_agent_config_path.clone());
-            self.current_movement_path = agent_config.movement;
-            self.current_battery_path = agent_config.battery;
-            self.current_battery_soc = agent_config.battery_soc;
+            match AgentConfig::try_from_json_file(&self.current_agent_config_path) {
+                Ok(agent_config) => {
+                    self.current_movement_path = agent_config.movement;
+                    self.current_battery_path = agent_config.battery;
+                    self.current_battery_soc = agent_config.battery_soc;
+                    self.current_footprint_radius = agent_config.footprint_radius;
+                    self.current_capabilities = agent_config.capabilities;
+                    self.current_power_model = agent_config.power_model;
+                    self.load_error = None;
+                }
+                Err(e) => self.load_error = Some(e.to_string()),
+            }
         }
+        error_label(ui, self.load_error.as_deref());
+    }
+
+    /// Renders the fields of the power model.
+    fn ui_power_model(&mut self, ui: &mut egui::Ui) {
+        let power_model = &mut self.current_power_model;
+        ui.label(r#"   "power_model": {"#);
+        value_with_unit_selector_ui(
+            ui,
+            "mass",
+            "mass",
+            &mut power_model.mass.value,
+            &mut power_model.mass.unit,
+            Some(0.0),
+            None,
+        );
+        ui.horizontal(|ui| {
+            ui.label(r#"        "rolling_resistance":"#);
+            ui.add(
+                egui::DragValue::new(&mut power_model.rolling_resistance)
+                    .speed(0.01)
+                    .range(0.0..=1.0),
+            );
+        });
+        ui.horizontal(|ui| {
+            ui.label(r#"        "drivetrain_efficiency":"#);
+            ui.add(
+                egui::DragValue::new(&mut power_model.drivetrain_efficiency)
+                    .speed(0.01)
+                    .range(0.01..=1.0),
+            );
+        });
+        ui.horizontal(|ui| {
+            ui.label(r#"        "turning_resistance":"#);
+            ui.add(
+                egui::DragValue::new(&mut power_model.turning_resistance)
+                    .speed(0.01)
+                    .range(0.0..=2.0),
+            );
+        });
+        value_with_unit_selector_ui(
+            ui,
+            "base_load",
+            "base_load",
+            &mut power_model.base_load.value,
+            &mut power_model.base_load.unit,
+            Some(0.0),
+            None,
+        );
+        ui.label(r#"   }"#);
     }
 
     /// Renders a dropdown to select a movement configuration file.
@@ -119,6 +204,9 @@ impl HasConfigSaving for AgentConfigEditorTool {
             self.current_movement_path.clone(),
             self.current_battery_path.clone(),
             self.current_battery_soc,
+            self.current_footprint_radius,
+            self.current_capabilities.clone(),
+            self.current_power_model,
         )
     }
     fn update_current_path(&mut self, path: String) {
@@ -140,5 +228,17 @@ impl HasHelp for AgentConfigEditorTool {
         ui.label("movement: path to movement config (see MovementConfigEditor)");
         ui.label("battery: select what is available");
         ui.label("battery_soc: initial percent of charge in [%]");
+        ui.label("footprint_radius: radius of the circle the agent occupies, used for collision detection");
+        ui.label("capabilities: tools the agent carries, it only gets tasks whose required capabilities it all has");
+        ui.separator();
+        ui.label("power_model: determines the power drawn from the battery");
+        ui.label("mass: mass of the agent including its load");
+        ui.label("rolling_resistance: rolling resistance coefficient of the wheels, about 0.02 on asphalt and 0.1-0.3 on soil");
+        ui.label("drivetrain_efficiency: fraction of the electric power turned into motion");
+        ui.label("turning_resistance: friction of wheels scrubbing sideways while turning, 0 for car-like agents");
+        ui.label(
+            "base_load: power of electronics and sensors, drawn whenever the agent is not charging",
+        );
+        ui.label("Driving power = (rolling + acceleration + turning losses) / drivetrain_efficiency + base_load");
     }
 }
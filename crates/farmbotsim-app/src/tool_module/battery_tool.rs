@@ -1,10 +1,14 @@
+use chrono::{Datelike, NaiveDate};
 use egui::Slider;
 use egui_plot::{HLine, Legend, Line, Plot, PlotPoints};
 use std::collections::HashMap;
 
 use crate::{
     tool_module::{has_help::HasHelp, tool::Tool},
-    utilities::files::get_folders_in_folder,
+    utilities::{
+        files::get_folders_in_folder,
+        ui::{error_label, value_with_unit_selector_ui},
+    },
 };
 use farmbotsim_core::prelude::*;
 
@@ -14,8 +18,9 @@ pub struct BatteryTool {
     selected: Option<String>,
     folder_names: Vec<String>,
     battery_map: HashMap<String, Battery>,
-    month: u32,
-    morph_data: Option<Vec<(u32, f32)>>,
+    date: NaiveDate,
+    morph_data: Option<Vec<(f32, f32)>>,
+    load_error: Option<String>,
     pub help_open: bool,
 }
 
@@ -26,8 +31,9 @@ impl Default for BatteryTool {
             selected: None,
             folder_names: folders,
             battery_map: HashMap::new(),
-            month: 1,
+            date: chrono::Local::now().date_naive(),
             morph_data: None,
+            load_error: None,
             help_open: false,
         }
     }
@@ -39,29 +45,23 @@ impl Tool for BatteryTool {
             None => {}
             Some(selected) => {
                 if let Some(battery) = self.battery_map.get(selected) {
-                    let jan_max: PlotPoints = battery
-                        .jan_max_data
-                        .iter()
-                        .map(|(x, y)| [f64::from(*x), f64::from(*y)])
-                        .collect::<Vec<_>>()
-                        .into();
-                    let line_jan_max = Line::new("January Max", jan_max);
-
-                    let jan_min: PlotPoints = battery
-                        .jan_min_data
-                        .iter()
-                        .map(|(x, y)| [f64::from(*x), f64::from(*y)])
-                        .collect::<Vec<_>>()
-                        .into();
-                    let line_jan_min = Line::new("January Min", jan_min);
-
-                    let jun_max: PlotPoints = battery
-                        .jun_max_data
+                    if let BatteryModel::CcCv(model) = &battery.model {
+                        Self::render_cc_cv_plot(ui, battery, model);
+                        return;
+                    }
+                    let curve_lines: Vec<Line> = battery
+                        .curves
                         .iter()
-                        .map(|(x, y)| [f64::from(*x), f64::from(*y)])
-                        .collect::<Vec<_>>()
-                        .into();
-                    let line_jun_max = Line::new("June Max", jun_max);
+                        .map(|curve| {
+                            let points: PlotPoints = curve
+                                .data
+                                .iter()
+                                .map(|(x, y)| [f64::from(*x), f64::from(*y)])
+                                .collect::<Vec<_>>()
+                                .into();
+                            Line::new(Self::curve_label(curve.day_of_year), points)
+                        })
+                        .collect();
 
                     let line_morph = match &self.morph_data {
                         Some(data) => {
@@ -70,9 +70,9 @@ impl Tool for BatteryTool {
                                 .map(|(x, y)| [f64::from(*x), f64::from(*y)])
                                 .collect::<Vec<_>>()
                                 .into();
-                            Line::new("Morph", morph)
+                            Line::new("Interpolated", morph)
                         }
-                        None => Line::new("Morph", vec![]), // fallback empty line
+                        None => Line::new("Interpolated", vec![]), // fallback empty line
                     };
 
                     Plot::new("battery_plot")
@@ -83,9 +83,9 @@ impl Tool for BatteryTool {
                         .show(ui, |plot_ui| {
                             let line = HLine::new("Current energy", battery.energy.value);
                             plot_ui.hline(line);
-                            plot_ui.line(line_jan_max);
-                            plot_ui.line(line_jan_min);
-                            plot_ui.line(line_jun_max);
+                            for line in curve_lines {
+                                plot_ui.line(line);
+                            }
                             plot_ui.line(line_morph);
                         });
                 }
@@ -101,14 +101,24 @@ impl Tool for BatteryTool {
         for folder in &self.folder_names {
             let whole_path = format!("{}{}", BATTERIES_PATH, folder.clone());
             if ui.button(whole_path.clone()).clicked() {
-                self.selected = Some(whole_path.clone());
-                self.battery_map
-                    .entry(whole_path.clone())
-                    .or_insert_with(|| {
-                        Battery::from_config(BatteryConfig::from_json_file(whole_path), 70.0)
-                    });
+                if self.battery_map.contains_key(&whole_path) {
+                    self.selected = Some(whole_path);
+                    self.load_error = None;
+                } else {
+                    match BatteryConfig::try_from_json_file(whole_path.clone())
+                        .and_then(|config| Battery::try_from_config(config, 70.0))
+                    {
+                        Ok(battery) => {
+                            self.battery_map.insert(whole_path.clone(), battery);
+                            self.selected = Some(whole_path);
+                            self.load_error = None;
+                        }
+                        Err(e) => self.load_error = Some(e.to_string()),
+                    }
+                }
             }
         }
+        error_label(ui, self.load_error.as_deref());
         if ui.button("Deselect").clicked() {
             self.selected = None;
         }
@@ -123,6 +133,22 @@ impl Tool for BatteryTool {
                 ui.label(format!("Voltage: {}", battery.voltage));
                 ui.label(format!("Capacity: {}", battery.capacity));
                 ui.label(format!("Energy: {}", battery.energy));
+                match &battery.health {
+                    Some(health) => ui.label(format!(
+                        "Ageing: {} cycles to {}% SoH, {}%/year calendar fade",
+                        health.model.cycle_life,
+                        health.model.end_of_life_soh,
+                        health.model.calendar_fade
+                    )),
+                    None => ui.label("Ageing: none"),
+                };
+                ui.label(format!(
+                    "Model: {}",
+                    match battery.model {
+                        BatteryModel::CurveMorph(_) => "CurveMorph",
+                        BatteryModel::CcCv(_) => "CcCv",
+                    }
+                ));
 
                 let response = ui.add(
                     Slider::new(&mut battery.soc, 0.0..=100.0)
@@ -132,28 +158,34 @@ impl Tool for BatteryTool {
                 if response.changed() {
                     battery.recalculate_energy();
                 }
-                let response = ui.add(
-                    Slider::new(&mut self.month, 1..=12)
-                        .text("Month")
-                        .step_by(1.0),
-                );
-                if response.changed() || response.enabled() {
-                    let mut data = vec![];
-                    let mut i = 26.0;
-                    battery.start_index.insert("jan".to_string(), 1);
-                    battery.start_index.insert("jun".to_string(), 1);
-                    while i <= battery.capacity.value {
-                        match battery.get_morph_x_y(i, self.month, 1) {
-                            Ok((time, energy)) => {
-                                data.push((time, energy));
-                            }
-                            Err(e) => {
-                                eprintln!("⚠️ Failed to get morph x/y for i = {i}: {e}");
+                match &mut battery.model {
+                    BatteryModel::CcCv(model) => Self::ui_cc_cv_params(ui, model),
+                    BatteryModel::CurveMorph(_) => {
+                        let response = ui
+                            .horizontal(|ui| {
+                                ui.label("Date:");
+                                ui.add(egui_extras::DatePickerButton::new(&mut self.date))
+                            })
+                            .inner;
+                        if response.changed() || response.enabled() {
+                            let day_of_year = leap_year_day(self.date.month(), self.date.day());
+                            let mut data = vec![];
+                            let mut i = 26.0;
+                            battery.reset_curve_search();
+                            while i <= battery.capacity.value {
+                                match battery.get_morph_x_y(i, day_of_year, 1.0) {
+                                    Ok((time, energy)) => {
+                                        data.push((time, energy));
+                                    }
+                                    Err(e) => {
+                                        eprintln!("⚠️ Failed to get morph x/y for i = {i}: {e}");
+                                    }
+                                }
+                                i += 5.0;
                             }
+                            self.morph_data = Some(data);
                         }
-                        i += 5.0;
                     }
-                    self.morph_data = Some(data);
                 }
             }
         }
@@ -164,6 +196,69 @@ impl Tool for BatteryTool {
     fn update(&mut self) {}
 }
 
+impl BatteryTool {
+    /// Returns the legend label of a measured curve, its date.
+    fn curve_label(day_of_year: u32) -> String {
+        NaiveDate::from_yo_opt(2000, day_of_year).map_or_else(
+            || format!("Day {day_of_year}"),
+            |date| date.format(CURVE_DATE_FORMAT).to_string(),
+        )
+    }
+
+    /// Plots the charging curve of a CC-CV battery from empty to full.
+    fn render_cc_cv_plot(ui: &mut egui::Ui, battery: &Battery, model: &CcCvModel) {
+        let curve: PlotPoints = model
+            .charge_curve(battery.capacity, battery.voltage, Duration::seconds(60.0))
+            .iter()
+            .map(|(x, y)| [f64::from(*x), f64::from(*y)])
+            .collect::<Vec<_>>()
+            .into();
+        let cv_energy = battery.capacity.to_watt_hour() * model.cv_threshold / 100.0;
+
+        Plot::new("battery_plot")
+            .legend(Legend::default())
+            .auto_bounds(true)
+            .x_axis_label("Time (s)")
+            .y_axis_label("Energy (Wh)")
+            .show(ui, |plot_ui| {
+                plot_ui.hline(HLine::new("Current energy", battery.energy.to_watt_hour()));
+                plot_ui.hline(HLine::new("CV threshold", cv_energy));
+                plot_ui.line(Line::new("CC-CV", curve));
+            });
+    }
+
+    /// Renders editable CC-CV parameters, changes are only previewed and not saved.
+    fn ui_cc_cv_params(ui: &mut egui::Ui, model: &mut CcCvModel) {
+        ui.label("CC-CV parameters (preview only):");
+        value_with_unit_selector_ui(
+            ui,
+            "charge_current",
+            "charge_current",
+            &mut model.charge_current.value,
+            &mut model.charge_current.unit,
+            Some(0.0),
+            None,
+        );
+        ui.add(
+            Slider::new(&mut model.cv_threshold, 0.0..=100.0)
+                .text("cv_threshold [%]")
+                .step_by(1.0),
+        );
+        value_with_unit_selector_ui(
+            ui,
+            "cutoff_current",
+            "cutoff_current",
+            &mut model.cutoff_current.value,
+            &mut model.cutoff_current.unit,
+            Some(0.0),
+            None,
+        );
+        if let Err(e) = model.validate() {
+            error_label(ui, Some(&e.to_string()));
+        }
+    }
+}
+
 impl HasHelp for BatteryTool {
     fn help_modal(&self) -> egui::Modal {
         egui::Modal::new(egui::Id::new("Battery Tool Help"))
@@ -173,6 +268,9 @@ impl HasHelp for BatteryTool {
         ui.label("This is a battery tool where you can see selected battery charging characteristics and parameters.");
         ui.separator();
 
-        ui.label("When battery is selected you can see morphed characteristics between jan and jun data.");
+        ui.label("Batteries have one of two charging models:");
+        ui.label("CurveMorph: measured curves for dates of the year, when the battery is selected you can see the curve interpolated between the two curves nearest to the chosen date.");
+        ui.label("CcCv: constant current up to cv_threshold [%], then the current tapers off until it drops to cutoff_current and the battery counts as full.");
+        ui.label("The CC-CV parameters can be changed to preview the curve, edit config.json of the battery to keep them.");
     }
 }
@@ -2,7 +2,9 @@ use egui::Ui;
 
 use crate::{
     tool_module::{has_config_saving::HasConfigSaving, has_help::HasHelp, tool::Tool},
-    utilities::ui::{json_config_combo, value_with_unit_selector_ui},
+    utilities::ui::{
+        capabilities_editor, error_label, json_config_combo, value_with_unit_selector_ui,
+    },
 };
 use farmbotsim_core::prelude::*;
 
@@ -10,6 +12,7 @@ use farmbotsim_core::prelude::*;
 pub struct FarmEntityPlanEditorTool {
     plan: FarmEntityPlan,
     save_file_name: String,
+    load_error: Option<String>,
     pub current_farm_entity_plan_path: String,
     pub help_open: bool,
 }
@@ -20,6 +23,7 @@ impl Default for FarmEntityPlanEditorTool {
         Self {
             plan,
             save_file_name: String::new(),
+            load_error: None,
             current_farm_entity_plan_path: DEFAULT_POINT_FARM_ENTITY_PLAN_PATH.to_string(),
             help_open: false,
         }
@@ -107,6 +111,7 @@ impl Tool for FarmEntityPlanEditorTool {
                         action_name,
                         duration,
                         power,
+                        required_capabilities,
                     } => {
                         ui.label(format!("        [{i}]"));
                         ui.label("{ \"Point\": {");
@@ -130,12 +135,19 @@ impl Tool for FarmEntityPlanEditorTool {
                             Some(0.0),
                             None,
                         );
+                        ui.label("required_capabilities:");
+                        capabilities_editor(
+                            ui,
+                            &format!("required_capabilities{i}"),
+                            required_capabilities,
+                        );
                         ui.label("} }");
                     }
                     FarmEntityAction::Line {
                         action_name,
                         velocity,
                         power,
+                        required_capabilities,
                     } => {
                         ui.label(format!("        [{i}]"));
                         ui.label("{ \"Line\": {");
@@ -159,6 +171,12 @@ impl Tool for FarmEntityPlanEditorTool {
                             Some(0.0),
                             None,
                         );
+                        ui.label("required_capabilities:");
+                        capabilities_editor(
+                            ui,
+                            &format!("required_capabilities{i}"),
+                            required_capabilities,
+                        );
                         ui.label("} }");
                     }
                     FarmEntityAction::Wait {
@@ -220,10 +238,15 @@ impl FarmEntityPlanEditorTool {
             && new_value != self.current_farm_entity_plan_path
         {
             self.current_farm_entity_plan_path = new_value;
-            let plan: FarmEntityPlan =
-                load_json_or_panic(self.current_farm_entity_plan_path.clone());
-            self.plan = plan;
+            match FarmEntityPlan::try_from_json_file(&self.current_farm_entity_plan_path) {
+                Ok(plan) => {
+                    self.plan = plan;
+                    self.load_error = None;
+                }
+                Err(e) => self.load_error = Some(e.to_string()),
+            }
         }
+        error_label(ui, self.load_error.as_deref());
     }
 
     // Updates cycle paramater in plan so it is always valid
@@ -273,11 +296,13 @@ impl HasHelp for FarmEntityPlanEditorTool {
                     action_name: String,
                     duration: Duration,
                     power: Power,
+                    required_capabilities: Vec<String>,
                     },
                     Line {
                         action_name: String,
                         velocity: LinearVelocity,
                         power: Power,
+                        required_capabilities: Vec<String>,
                         },
                         Wait {
                             action_name: String,
@@ -291,5 +316,6 @@ impl HasHelp for FarmEntityPlanEditorTool {
         ui.label("  point - stationary task");
         ui.label("  line - moving task");
         ui.label("  wait - internal task in task manager where task manager waits duration before adding next task");
+        ui.label("Tasks of point and line actions are only given to agents having all required_capabilities (see AgentConfigEditor).");
     }
 }
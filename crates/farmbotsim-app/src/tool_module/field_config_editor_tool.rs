@@ -1,17 +1,20 @@
-use egui::{Slider, Ui};
+use egui::{Pos2, Slider, Ui, Vec2};
 
 use crate::{
     rendering::{
         camera::Camera,
         render::{
             render_coordinate_system, render_drag_points, render_field_config, render_grid,
-            render_obstacles, render_visibility_graph,
+            render_no_go_zones, render_obstacles, render_visibility_graph,
         },
     },
     tool_module::{
         has_camera::HasCamera, has_config_saving::HasConfigSaving, has_help::HasHelp, tool::Tool,
     },
-    utilities::{files::get_json_files_in_folder, ui::json_config_combo},
+    utilities::{
+        files::get_json_files_in_folder,
+        ui::{error_label, json_config_combo, validation_issues_label},
+    },
 };
 
 use farmbotsim_core::prelude::*;
@@ -21,6 +24,8 @@ pub struct FieldConfigEditorTool {
     pub field_config: FieldConfig,
     pub camera: Camera,
     save_file_name: String,
+    load_error: Option<String>,
+    validation_issues: Option<Vec<ValidationIssue>>,
     pub current_field_config_path: String,
     pub help_open: bool,
 }
@@ -33,6 +38,8 @@ impl Default for FieldConfigEditorTool {
             field_config,
             camera: Camera::default(),
             save_file_name: String::new(),
+            load_error: None,
+            validation_issues: None,
             current_field_config_path: DEFAULT_FIELD_CONFIG_PATH.to_string(),
             help_open: false,
         }
@@ -46,6 +53,7 @@ impl Tool for FieldConfigEditorTool {
         self.camera.handle_events(ui);
         render_grid(ui, &self.camera);
         render_coordinate_system(ui, &self.camera);
+        render_no_go_zones(ui, &self.camera, &self.field_config.no_go_zones);
         render_obstacles(ui, &self.camera, &self.field_config.get_obstacles());
         render_visibility_graph(
             ui,
@@ -70,6 +78,12 @@ impl Tool for FieldConfigEditorTool {
         self.save_file_name = save_file_name;
         ui.separator();
 
+        if ui.button("Validate").clicked() {
+            self.validation_issues = Some(validate_field(&self.field_config));
+        }
+        validation_issues_label(ui, self.validation_issues.as_deref());
+        ui.separator();
+
         self.ui_mouse_position(ui);
         ui.separator();
 
@@ -148,8 +162,10 @@ impl Tool for FieldConfigEditorTool {
                                 for json_file in json_files {
                                     let whole_path =
                                         format!("{}{}", FARM_ENTITY_PLANS_PATH, json_file.clone());
-                                    let plan = FarmEntityPlan::from_json_file(&whole_path);
-                                    if plan.type_.to_lowercase() == "line" {
+                                    if let Ok(plan) =
+                                        FarmEntityPlan::try_from_json_file(&whole_path)
+                                        && plan.type_.to_lowercase() == "line"
+                                    {
                                         ui.selectable_value(
                                             &mut config.farm_entity_plan_path,
                                             whole_path.clone(),
@@ -225,8 +241,10 @@ impl Tool for FieldConfigEditorTool {
                                 for json_file in json_files {
                                     let whole_path =
                                         format!("{}{}", FARM_ENTITY_PLANS_PATH, json_file.clone());
-                                    let plan = FarmEntityPlan::from_json_file(&whole_path);
-                                    if plan.type_.to_lowercase() == "point" {
+                                    if let Ok(plan) =
+                                        FarmEntityPlan::try_from_json_file(&whole_path)
+                                        && plan.type_.to_lowercase() == "point"
+                                    {
                                         ui.selectable_value(
                                             &mut config.farm_entity_plan_path,
                                             whole_path.clone(),
@@ -260,13 +278,19 @@ impl Tool for FieldConfigEditorTool {
         if needs_recalc {
             self.field_config.recalc_id_color();
         }
+        ui.separator();
+
+        self.ui_obstacles(ui);
+        ui.separator();
+
+        self.ui_no_go_zones(ui);
 
         self.render_help(ui);
     }
 }
 
 impl FieldConfigEditorTool {
-    /// Handles dragging field configs
+    /// Handles dragging field configs and vertices of obstacles and no-go zones
     fn handle_dragging(&mut self, ui: &mut Ui) {
         let mut pts = vec![];
         for config_variant in &mut self.field_config.configs {
@@ -278,38 +302,116 @@ impl FieldConfigEditorTool {
             let pos = self.camera.scene_to_screen_pos(*left_top_pos);
             pts.push(pos);
         }
+        let vertices = self
+            .field_config
+            .obstacles
+            .iter()
+            .flat_map(|obstacle| obstacle.points.iter())
+            .chain(
+                self.field_config
+                    .no_go_zones
+                    .iter()
+                    .flat_map(|zone| zone.points.iter()),
+            );
+        pts.extend(vertices.map(|pos| self.camera.scene_to_screen_pos(*pos)));
 
         render_drag_points(ui, &self.camera, &pts);
 
-        let drag_point_size = self.camera.scene_to_screen_val(0.1);
         // Drag field
         for (i, config_variant) in &mut self.field_config.configs.iter_mut().enumerate() {
             let left_top_pos = match config_variant {
                 VariantFieldConfig::Line(config) => &mut config.left_top_pos,
                 VariantFieldConfig::Point(config) => &mut config.left_top_pos,
             };
+            drag_point(ui, &self.camera, format!("field_drag_{i}"), left_top_pos);
+        }
+        // Drag vertices
+        for (i, obstacle) in self.field_config.obstacles.iter_mut().enumerate() {
+            for (j, point) in obstacle.points.iter_mut().enumerate() {
+                drag_point(ui, &self.camera, format!("obstacle_drag_{i}_{j}"), point);
+            }
+        }
+        for (i, zone) in self.field_config.no_go_zones.iter_mut().enumerate() {
+            for (j, point) in zone.points.iter_mut().enumerate() {
+                drag_point(ui, &self.camera, format!("no_go_zone_drag_{i}_{j}"), point);
+            }
+        }
+    }
 
-            let screen_pos = self.camera.scene_to_screen_pos(*left_top_pos);
-            let rect = egui::Rect::from_center_size(screen_pos, egui::Vec2::splat(drag_point_size));
-            let response = ui.interact(
-                rect,
-                ui.make_persistent_id(format!("field_drag_{i}")),
-                egui::Sense::click_and_drag(),
-            );
+    /// Renders the list of obstacles outside the fields
+    fn ui_obstacles(&mut self, ui: &mut Ui) {
+        ui.label(egui::RichText::new("Obstacles:").size(16.0));
+        ui.horizontal_top(|ui| {
+            if ui.button("Add obstacle").clicked() {
+                self.field_config
+                    .obstacles
+                    .push(Obstacle::new(default_polygon()));
+            }
+            if ui.button("Remove all").clicked() {
+                self.field_config.obstacles.clear();
+            }
+        });
+
+        let mut to_remove: Option<usize> = None;
+        for (i, obstacle) in self.field_config.obstacles.iter_mut().enumerate() {
+            egui::CollapsingHeader::new(format!("Obstacle {i}"))
+                .default_open(false)
+                .show(ui, |ui| {
+                    ui_polygon_points(ui, &mut obstacle.points);
+                    if ui.button("Remove").clicked() {
+                        to_remove = Some(i);
+                    }
+                });
+        }
+        if let Some(index) = to_remove {
+            self.field_config.obstacles.remove(index);
+        }
+    }
 
-            if response.dragged_by(egui::PointerButton::Primary) {
-                let drag_delta = response.drag_delta();
-                let new_screen_pos = screen_pos + drag_delta;
-                let new_scene_pos = self.camera.screen_to_scene_pos(new_screen_pos);
-                *left_top_pos = new_scene_pos;
+    /// Renders the list of no-go zones
+    fn ui_no_go_zones(&mut self, ui: &mut Ui) {
+        ui.label(egui::RichText::new("No-go zones:").size(16.0));
+        ui.horizontal_top(|ui| {
+            if ui.button("Add zone").clicked() {
+                self.field_config
+                    .no_go_zones
+                    .push(NoGoZone::new(default_polygon(), 1.0));
+            }
+            if ui.button("Remove all").clicked() {
+                self.field_config.no_go_zones.clear();
             }
+        });
+
+        let mut to_remove: Option<usize> = None;
+        for (i, zone) in self.field_config.no_go_zones.iter_mut().enumerate() {
+            egui::CollapsingHeader::new(format!("No-go zone {i}"))
+                .default_open(false)
+                .show(ui, |ui| {
+                    ui.add(
+                        Slider::new(&mut zone.penalty, 0.0..=10.0)
+                            .text("Penalty")
+                            .step_by(0.1),
+                    );
+                    ui_polygon_points(ui, &mut zone.points);
+                    if ui.button("Remove").clicked() {
+                        to_remove = Some(i);
+                    }
+                });
+        }
+        if let Some(index) = to_remove {
+            self.field_config.no_go_zones.remove(index);
         }
     }
 
-    /// Changes field config to new value
+    /// Changes field config to new value, keeping the current one if it can't be loaded
     fn change_field_config(&mut self, new_field_config_path: String) {
-        let field_config: FieldConfig = load_json_or_panic(new_field_config_path);
-        self.field_config = field_config;
+        match load_json(new_field_config_path) {
+            Ok(field_config) => {
+                self.field_config = field_config;
+                self.load_error = None;
+            }
+            Err(e) => self.load_error = Some(e.to_string()),
+        }
     }
 
     /// Renders dropdown to select field configuration file
@@ -324,6 +426,52 @@ impl FieldConfigEditorTool {
             self.current_field_config_path = new_value;
             self.change_field_config(self.current_field_config_path.clone());
         }
+        error_label(ui, self.load_error.as_deref());
+    }
+}
+
+/// Returns a square of 0.5 m placed next to the origin, used for new obstacles and zones
+fn default_polygon() -> Vec<Pos2> {
+    vec![
+        Pos2::new(0.5, 0.5),
+        Pos2::new(1.0, 0.5),
+        Pos2::new(1.0, 1.0),
+        Pos2::new(0.5, 1.0),
+    ]
+}
+
+/// Renders buttons adding a vertex after the last one and removing the last vertex
+fn ui_polygon_points(ui: &mut Ui, points: &mut Vec<Pos2>) {
+    ui.label(format!("Points: {}", points.len()));
+    ui.horizontal_top(|ui| {
+        if ui.button("Add point").clicked()
+            && let (Some(&first), Some(&last)) = (points.first(), points.last())
+        {
+            points.push(first + (last - first) / 2.0);
+        }
+        if ui
+            .add_enabled(points.len() > 3, egui::Button::new("Remove point"))
+            .clicked()
+        {
+            points.pop();
+        }
+    });
+}
+
+/// Lets the user drag the scene position `pos` with the primary mouse button
+fn drag_point(ui: &mut Ui, camera: &Camera, id: String, pos: &mut Pos2) {
+    let drag_point_size = camera.scene_to_screen_val(0.1);
+    let screen_pos = camera.scene_to_screen_pos(*pos);
+    let rect = egui::Rect::from_center_size(screen_pos, Vec2::splat(drag_point_size));
+    let response = ui.interact(
+        rect,
+        ui.make_persistent_id(id),
+        egui::Sense::click_and_drag(),
+    );
+
+    if response.dragged_by(egui::PointerButton::Primary) {
+        let new_screen_pos = screen_pos + response.drag_delta();
+        *pos = camera.screen_to_scene_pos(new_screen_pos);
     }
 }
 
@@ -355,6 +503,12 @@ impl HasHelp for FieldConfigEditorTool {
         ui.label("In dropdown you can select field config and save new config");
         ui.separator();
 
+        ui.label("Validate:");
+        ui.label(
+            "Check fields for overlaps and farm entity plans incompatible with the field type.",
+        );
+        ui.separator();
+
         ui.label("Mouse position:");
         ui.label("See where mouse is on screen and in env/scene.");
         ui.separator();
@@ -362,5 +516,14 @@ impl HasHelp for FieldConfigEditorTool {
         ui.label("Fields:");
         ui.label("Add, remove, change variant field config (Supported Line, Point).");
         ui.label("Select farm entity plan for field (see FarmEntityPlanEditor).");
+        ui.separator();
+
+        ui.label("Obstacles:");
+        ui.label("Add polygons such as sheds, trees, ponds or fences that agents can't cross.");
+        ui.label("Drag the red points to move vertices, add or remove vertices in the list.");
+        ui.separator();
+
+        ui.label("No-go zones:");
+        ui.label("Add polygons that path planning avoids. The penalty is the extra cost per meter inside the zone relative to the distance, e.g. 1.0 makes crossing twice as expensive.");
     }
 }
@@ -1,6 +1,8 @@
 use chrono::{NaiveDate, NaiveTime, Timelike};
 
-use crate::utilities::ui::json_config_combo;
+use crate::utilities::ui::{
+    collision_avoidance_combo, error_label, fleet_editor, json_config_combo,
+};
 use farmbotsim_core::prelude::*;
 
 /// Trait for managing and interacting with an environment (`Env`) and its configuration (`EnvConfig`).
@@ -20,11 +22,29 @@ pub trait HasEnv {
     /// Get a mutable reference to the current environment configuration.
     fn get_mut_env_config(&mut self) -> &mut EnvConfig;
 
+    /// Set the error of the last environment rebuild.
+    fn set_env_error(&mut self, value: Option<String>);
+
+    /// Get the error of the last environment rebuild.
+    fn get_env_error(&self) -> Option<&str>;
+
     /// Rebuilds the environment from the current environment configuration.
+    /// Keeps the previous environment and stores the error if the configuration can't be loaded.
     fn rebuild_env(&mut self) {
         self.set_running(false);
         self.set_tick(0);
-        self.set_env(Env::from_config(self.get_env_config().clone()))
+        match Env::try_from_config(self.get_env_config().clone()) {
+            Ok(env) => {
+                self.set_env(env);
+                self.set_env_error(None);
+            }
+            Err(e) => self.set_env_error(Some(e.to_string())),
+        }
+    }
+
+    /// Render the error of the last environment rebuild, if any.
+    fn ui_render_env_error(&self, ui: &mut egui::Ui) {
+        error_label(ui, self.get_env_error());
     }
 
     /// Render a UI widget to select the scene configuration file.
@@ -39,14 +59,9 @@ pub trait HasEnv {
         }
     }
 
-    /// Render a UI widget to select the agent configuration file.
-    fn ui_agent_config_select(&mut self, ui: &mut egui::Ui) {
-        let mut new_value = self.get_env_config().agent_config_path.clone();
-
-        if json_config_combo(ui, " ", &mut new_value, AGENT_CONFIGS_PATH)
-            && new_value != self.get_env_config().agent_config_path
-        {
-            self.get_mut_env_config().agent_config_path = new_value;
+    /// Render UI controls to edit the agent groups of the fleet.
+    fn ui_fleet_select(&mut self, ui: &mut egui::Ui) {
+        if fleet_editor(ui, "fleet", &mut self.get_mut_env_config().fleet) {
             self.rebuild_env();
         }
     }
@@ -59,14 +74,16 @@ pub trait HasEnv {
             config.datetime_config.date, config.datetime_config.time
         ));
 
-        let mut date = NaiveDate::parse_from_str(&config.datetime_config.date, DATE_FORMAT)
-            .unwrap_or_else(|e| {
-                let msg = format!(
-                    "Failed to parse date '{}' with format '{}': {}",
-                    &config.datetime_config.date, DATE_FORMAT, e
-                );
-                log_error_and_panic(&msg)
-            });
+        let (mut date, time) = match (
+            NaiveDate::parse_from_str(&config.datetime_config.date, DATE_FORMAT),
+            NaiveTime::parse_from_str(&config.datetime_config.time, TIME_FORMAT),
+        ) {
+            (Ok(date), Ok(time)) => (date, time),
+            _ => {
+                ui.colored_label(egui::Color32::RED, "Invalid datetime");
+                return;
+            }
+        };
 
         let mut changed = false;
         if ui
@@ -79,15 +96,6 @@ pub trait HasEnv {
 
         ui.label("|");
 
-        let time = NaiveTime::parse_from_str(&config.datetime_config.time, TIME_FORMAT)
-            .unwrap_or_else(|e| {
-                let msg = format!(
-                    "Failed to parse time '{}' with format '{}': {}",
-                    &config.datetime_config.time, TIME_FORMAT, e
-                );
-                log_error_and_panic(&msg);
-            });
-
         let mut hours = time.hour();
         let mut minutes = time.minute();
         let mut seconds = time.second();
@@ -114,6 +122,44 @@ pub trait HasEnv {
         }
     }
 
+    /// Render UI controls to edit the simulation step.
+    fn ui_simulation_step_select(&mut self, ui: &mut egui::Ui) {
+        let mut seconds = self.get_env_config().simulation_step.to_base_unit();
+        if ui
+            .add(
+                egui::DragValue::new(&mut seconds)
+                    .speed(0.1)
+                    .range(0.01..=3600.0)
+                    .suffix(" s"),
+            )
+            .changed()
+        {
+            self.get_mut_env_config().simulation_step = Duration::seconds(seconds);
+            self.rebuild_env();
+        }
+    }
+
+    /// Render UI controls to edit the seed of the first episode.
+    fn ui_seed_select(&mut self, ui: &mut egui::Ui) {
+        if ui
+            .add(egui::DragValue::new(&mut self.get_mut_env_config().seed).speed(1))
+            .changed()
+        {
+            self.rebuild_env();
+        }
+    }
+
+    /// Render UI dropdown to select collision avoidance behaviour.
+    fn ui_collision_avoidance_select(&mut self, ui: &mut egui::Ui) {
+        if collision_avoidance_combo(
+            ui,
+            "Collision Avoidance",
+            &mut self.get_mut_env_config().collision_avoidance,
+        ) {
+            self.rebuild_env();
+        }
+    }
+
     /// Render UI dropdowns to select task manager config.
     fn ui_task_manager_config_select(&mut self, ui: &mut egui::Ui) {
         let mut new_config_path = self.get_env_config().task_manager_config_path.clone();
@@ -150,6 +196,13 @@ macro_rules! impl_has_env {
             fn get_mut_env_config(&mut self) -> &mut EnvConfig {
                 &mut self.env_config
             }
+
+            fn set_env_error(&mut self, value: Option<String>) {
+                self.env_error = value;
+            }
+            fn get_env_error(&self) -> Option<&str> {
+                self.env_error.as_deref()
+            }
         }
     };
 }
@@ -1,6 +1,6 @@
 use crate::{
     tool_module::{has_config_saving::HasConfigSaving, has_help::HasHelp, tool::Tool},
-    utilities::ui::{json_config_combo, value_with_unit_selector_ui},
+    utilities::ui::{error_label, json_config_combo, value_with_unit_selector_ui},
 };
 use farmbotsim_core::prelude::*;
 
@@ -8,6 +8,7 @@ use farmbotsim_core::prelude::*;
 pub struct MovementConfigEditorTool {
     movement: Movement,
     save_file_name: String,
+    load_error: Option<String>,
     pub current_movement_config_path: String,
     pub help_open: bool,
 }
@@ -18,6 +19,7 @@ impl Default for MovementConfigEditorTool {
         Self {
             movement,
             save_file_name: String::new(),
+            load_error: None,
             current_movement_config_path: DEFAULT_ROMBA_MOVEMENT_CONFIG_PATH.to_string(),
             help_open: false,
         }
@@ -54,9 +56,15 @@ impl MovementConfigEditorTool {
             && new_path != self.current_movement_config_path
         {
             self.current_movement_config_path = new_path;
-            let movement = load_json_or_panic(self.current_movement_config_path.clone());
-            self.movement = movement;
+            match Movement::try_from_json_file(self.current_movement_config_path.clone()) {
+                Ok(movement) => {
+                    self.movement = movement;
+                    self.load_error = None;
+                }
+                Err(e) => self.load_error = Some(e.to_string()),
+            }
         }
+        error_label(ui, self.load_error.as_deref());
     }
 
     /// Renders json like structure with editable values
@@ -69,6 +77,7 @@ impl MovementConfigEditorTool {
             egui::ComboBox::from_id_salt("Type")
                 .selected_text(match self.movement {
                     Movement::RombaMovement(_) => "RombaMovement",
+                    Movement::AckermannMovement(_) => "AckermannMovement",
                 })
                 .show_ui(ui, |ui| {
                     if ui
@@ -80,6 +89,15 @@ impl MovementConfigEditorTool {
                     {
                         self.movement = Movement::RombaMovement(RombaMovement::default())
                     }
+                    if ui
+                        .selectable_label(
+                            matches!(self.movement, Movement::AckermannMovement(_)),
+                            "AckermannMovement",
+                        )
+                        .clicked()
+                    {
+                        self.movement = Movement::AckermannMovement(AckermannMovement::default())
+                    }
                     // More variants ...
                 });
         });
@@ -133,6 +151,86 @@ impl MovementConfigEditorTool {
                     None,
                 );
 
+                // Max sub step
+                value_with_unit_selector_ui(
+                    ui,
+                    "max_sub_step",
+                    "max_sub_step",
+                    &mut params.max_sub_step.value,
+                    &mut params.max_sub_step.unit,
+                    Some(0.0),
+                    None,
+                );
+
+                noise_ui(ui, &mut params.noise);
+
+                ui.label("    }");
+            }
+            Movement::AckermannMovement(params) => {
+                ui.label("    params: {");
+
+                // Max velocity
+                value_with_unit_selector_ui(
+                    ui,
+                    "max_velocity",
+                    "max_velocity",
+                    &mut params.max_velocity.value,
+                    &mut params.max_velocity.unit,
+                    Some(0.0),
+                    None,
+                );
+
+                // Wheelbase
+                value_with_unit_selector_ui(
+                    ui,
+                    "wheelbase",
+                    "wheelbase",
+                    &mut params.wheelbase.value,
+                    &mut params.wheelbase.unit,
+                    Some(0.0),
+                    None,
+                );
+
+                // Max steering angle
+                value_with_unit_selector_ui(
+                    ui,
+                    "max_steering_angle",
+                    "max_steering_angle",
+                    &mut params.max_steering_angle.value,
+                    &mut params.max_steering_angle.unit,
+                    Some(0.0),
+                    None,
+                );
+
+                // Steering rate
+                value_with_unit_selector_ui(
+                    ui,
+                    "steering_rate",
+                    "steering_rate",
+                    &mut params.steering_rate.value,
+                    &mut params.steering_rate.unit,
+                    Some(0.0),
+                    None,
+                );
+
+                // Max sub step
+                value_with_unit_selector_ui(
+                    ui,
+                    "max_sub_step",
+                    "max_sub_step",
+                    &mut params.max_sub_step.value,
+                    &mut params.max_sub_step.unit,
+                    Some(0.0),
+                    None,
+                );
+
+                let turning_radius = 1.0 / params.max_curvature();
+                ui.label(format!(
+                    "        minimum turning radius: {turning_radius:.2} m"
+                ));
+
+                noise_ui(ui, &mut params.noise);
+
                 ui.label("    }");
             }
         }
@@ -140,6 +238,64 @@ impl MovementConfigEditorTool {
     }
 }
 
+/// Renders the optional odometry noise of a movement model.
+fn noise_ui(ui: &mut egui::Ui, noise: &mut Option<OdometryNoise>) {
+    let mut enabled = noise.is_some();
+    ui.horizontal(|ui| {
+        ui.label("        \"noise\":");
+        ui.checkbox(&mut enabled, "");
+    });
+    if enabled != noise.is_some() {
+        *noise = enabled.then(OdometryNoise::default);
+    }
+    if let Some(noise) = noise {
+        ui.horizontal(|ui| {
+            ui.label("        \"wheel_slip\":");
+            ui.add(
+                egui::DragValue::new(&mut noise.wheel_slip)
+                    .speed(0.001)
+                    .range(0.0..=1.0),
+            );
+        });
+        value_with_unit_selector_ui(
+            ui,
+            "heading_drift",
+            "heading_drift",
+            &mut noise.heading_drift.value,
+            &mut noise.heading_drift.unit,
+            Some(0.0),
+            None,
+        );
+        value_with_unit_selector_ui(
+            ui,
+            "gnss_noise",
+            "gnss_noise",
+            &mut noise.gnss_noise.value,
+            &mut noise.gnss_noise.unit,
+            Some(0.0),
+            None,
+        );
+        value_with_unit_selector_ui(
+            ui,
+            "gnss_interval",
+            "gnss_interval",
+            &mut noise.gnss_interval.value,
+            &mut noise.gnss_interval.unit,
+            Some(0.0),
+            None,
+        );
+        value_with_unit_selector_ui(
+            ui,
+            "heading_noise",
+            "heading_noise",
+            &mut noise.heading_noise.value,
+            &mut noise.heading_noise.unit,
+            Some(0.0),
+            None,
+        );
+    }
+}
+
 impl HasConfigSaving for MovementConfigEditorTool {
     fn base_path() -> &'static str {
         MOVEMENT_CONFIGS_PATH
@@ -161,14 +317,43 @@ impl HasHelp for MovementConfigEditorTool {
         ui.label("This is a Movement Config Editor where you can see, change, create, save movement configs.");
         ui.separator();
 
-        ui.label("There is 1 type of movement:");
+        ui.label("There are 2 types of movement:");
         ui.monospace(
             r#"pub struct RombaMovement {
     pub max_velocity: LinearVelocity,
     pub max_angular_velocity: AngularVelocity,
     pub wheel_distance: Length,
-    pub wheel_radius: Length
+    pub wheel_radius: Length,
+    pub max_sub_step: Duration,
+    pub noise: Option<OdometryNoise>
+}"#,
+        );
+        ui.monospace(
+            r#"pub struct AckermannMovement {
+    pub max_velocity: LinearVelocity,
+    pub wheelbase: Length,
+    pub max_steering_angle: Angle,
+    pub steering_rate: AngularVelocity,
+    pub max_sub_step: Duration,
+    pub noise: Option<OdometryNoise>
 }"#,
         );
+        ui.label("RombaMovement is a differential drive that turns in place.");
+        ui.label(
+            "AckermannMovement is car-like, it can't turn in place and ignores path orientations. Targets inside its turning circle are reached by backing up.",
+        );
+        ui.label("steering_rate: how fast the steering angle can change");
+        ui.label(
+            "max_sub_step is the longest time integrated at once, longer simulation steps are split into sub-steps."
+        );
+        ui.separator();
+        ui.label("noise: optional odometry noise, the agent then plans and steers with an estimated pose that drifts from its true pose.");
+        ui.label("wheel_slip: standard deviation of the relative error of driven distances and turns, e.g. 0.02 for 2 %");
+        ui.label(
+            "heading_drift: standard deviation of the heading error after one meter of travel",
+        );
+        ui.label("gnss_noise: standard deviation of a GNSS fix in each axis");
+        ui.label("gnss_interval: time between GNSS fixes while the agent drives, 0 disables them");
+        ui.label("heading_noise: standard deviation of a heading fix (dual-antenna GNSS or compass) taken with every GNSS fix");
     }
 }
@@ -1,7 +1,10 @@
+use egui::Pos2;
+
 use crate::rendering::{
     camera::Camera,
     render::{
-        render_agents, render_coordinate_system, render_grid, render_obstacles, render_spawn_area,
+        render_agents, render_coordinate_system, render_grid, render_grid_planner,
+        render_inflated_obstacles, render_no_go_zones, render_obstacles, render_spawn_area,
         render_stations, render_visibility_graph, ui_render_agents_path,
     },
 };
@@ -9,6 +12,7 @@ use crate::tool_module::{
     has_camera::HasCamera, has_env::HasEnv, has_env_controls::HasEnvControls, has_help::HasHelp,
     tool::Tool,
 };
+use crate::utilities::ui::path_planner_combo;
 use farmbotsim_core::prelude::*;
 
 /// A tool to view pathfinding of agents
@@ -19,12 +23,20 @@ pub struct PathTool {
     pub env: Env,
     pub camera: Camera,
     pub help_open: bool,
+    pub env_error: Option<String>,
+    pub path_planner_kind: PathPlannerKind,
+    pub path_smoothing: bool,
+    path_planner: PathPlanner,
+    path_planner_scene_path: String, // scene the planner was built for
+    planning_info: Option<String>,
 }
 
 impl Default for PathTool {
     fn default() -> Self {
         let env_config = EnvConfig::default();
         let env = Env::from_config(env_config.clone());
+        let path_planner_kind = env.task_manager.path_planner_kind();
+        let path_smoothing = env.task_manager.path_smoothing();
         Self {
             tick: 0,
             running: false,
@@ -32,6 +44,12 @@ impl Default for PathTool {
             env,
             camera: Camera::default(),
             help_open: false,
+            env_error: None,
+            path_planner_kind,
+            path_smoothing,
+            path_planner: PathPlanner::default(),
+            path_planner_scene_path: String::new(),
+            planning_info: None,
         }
     }
 }
@@ -43,7 +61,13 @@ impl Tool for PathTool {
         render_grid(ui, &self.camera);
         render_coordinate_system(ui, &self.camera);
         render_spawn_area(ui, &self.camera, &self.env.spawn_area);
-        render_visibility_graph(ui, &self.camera, &self.env.visibility_graph);
+        self.updated_path_planner();
+        match &self.path_planner {
+            PathPlanner::Grid(grid_planner) => render_grid_planner(ui, &self.camera, grid_planner),
+            _ => render_visibility_graph(ui, &self.camera, &self.env.visibility_graph),
+        }
+        render_no_go_zones(ui, &self.camera, &self.env.field_config.no_go_zones);
+        render_inflated_obstacles(ui, &self.camera, &self.env.planning_obstacles);
         render_obstacles(ui, &self.camera, &self.env.obstacles);
         render_stations(ui, &self.camera, &self.env.stations, false);
         render_agents(ui, &self.camera, &self.env.agents);
@@ -53,30 +77,25 @@ impl Tool for PathTool {
         ui.separator();
 
         ui.label(egui::RichText::new("Env config:").size(16.0));
-        // n_agents
-        ui.horizontal(|ui| {
-            ui.label("n_agents:");
-            if ui
-                .add(
-                    egui::DragValue::new(&mut self.env_config.n_agents)
-                        .speed(1)
-                        .range(1..=10),
-                )
-                .changed()
-            {
-                self.rebuild_env();
-            };
-        });
-        // agent_config_path
-        ui.horizontal(|ui| {
-            ui.label("agent_config_path:");
-            self.ui_agent_config_select(ui);
-        });
+        // fleet
+        ui.label("fleet:");
+        self.ui_fleet_select(ui);
         // scene_config
         ui.horizontal(|ui| {
             ui.label("scene_config: ");
             self.ui_scene_config_select(ui);
         });
+        self.ui_render_env_error(ui);
+        ui.separator();
+
+        ui.horizontal(|ui| {
+            ui.label("path_planner:");
+            path_planner_combo(ui, "path_tool_path_planner", &mut self.path_planner_kind);
+        });
+        ui.checkbox(&mut self.path_smoothing, "smooth paths");
+        if let Some(info) = &self.planning_info {
+            ui.label(info);
+        }
         ui.separator();
 
         self.ui_mouse_position(ui);
@@ -110,22 +129,66 @@ impl PathTool {
         if let Some(mouse_position) = mouse_position {
             if response.clicked_by(egui::PointerButton::Primary) {
                 let scene_pos = self.camera.screen_to_scene_pos(mouse_position);
-                for agent in &mut self.env.agents {
-                    let path = self
-                        .env
-                        .visibility_graph
-                        .find_path(agent.pose.position, scene_pos);
+                let starts: Vec<Pos2> = self.env.agents.iter().map(|a| a.pose.position).collect();
+                let path_planner = self.updated_path_planner();
+                let start_time = std::time::Instant::now();
+                let paths: Vec<Option<Vec<Pos2>>> = starts
+                    .into_iter()
+                    .map(|start| path_planner.find_path(start, scene_pos))
+                    .collect();
+                let planning_time = start_time.elapsed();
+
+                let mut n_paths = 0;
+                let mut total_length = 0.0;
+                for (agent, path) in self.env.agents.iter_mut().zip(paths) {
                     if let Some(path) = path {
-                        let path = path_to_poses(path);
-                        let task = Task::travel(path, agent.movement.max_velocity(), Intent::Idle);
+                        n_paths += 1;
+                        total_length += path.windows(2).map(|w| w[0].distance(w[1])).sum::<f32>();
+                        let velocity = agent.movement.max_velocity();
+                        let task = if self.path_smoothing {
+                            let path = smooth_path(
+                                &path,
+                                &self.env.planning_obstacles,
+                                velocity,
+                                agent.movement.max_angular_velocity(),
+                            );
+                            Task::smoothed_travel(path, velocity, Intent::Idle)
+                        } else {
+                            Task::travel(path_to_poses(path), velocity, Intent::Idle)
+                        };
                         agent.current_task = Some(task);
                     }
                 }
+                self.planning_info = Some(format!(
+                    "{n_paths} path(s) found, total length {total_length:.2} m, planning time {planning_time:.2?}"
+                ));
             }
         }
     }
 }
 
+impl PathTool {
+    /// Returns the selected path planner, rebuilt if the selection or the scene changed.
+    fn updated_path_planner(&mut self) -> &mut PathPlanner {
+        if self.path_planner.kind() != self.path_planner_kind
+            || self.path_planner_scene_path != self.env_config.scene_config_path
+        {
+            self.path_planner = PathPlanner::new(
+                self.path_planner_kind,
+                &self
+                    .env
+                    .field_config
+                    .get_inflated_graph_points(self.env.task_manager.planning_radius()),
+                self.env.planning_obstacles.clone(),
+            );
+            self.path_planner
+                .set_no_go_zones(self.env.field_config.no_go_zones.clone());
+            self.path_planner_scene_path = self.env_config.scene_config_path.clone();
+        }
+        &mut self.path_planner
+    }
+}
+
 impl HasHelp for PathTool {
     fn help_modal(&self) -> egui::Modal {
         egui::Modal::new(egui::Id::new("Path Tool Help"))
@@ -139,6 +202,15 @@ impl HasHelp for PathTool {
 
         ui.label("Env config:");
         ui.label("Configure env config.");
+        ui.label("fleet: groups of agents with their own number of agents, agent config and optionally their own start SoC.");
+        ui.label("If a selected config can't be loaded, the previous env is kept and the error is shown.");
+        ui.separator();
+
+        ui.label("Path planner:");
+        ui.label("Select the planner used for clicked paths, independent of the scene's planner. Grid planners show their blocked cells, A* moves between neighbouring cells and Theta* connects cells in line of sight.");
+        ui.label("Obstacles are inflated by the scene's planning radius before planning, the inflated outlines are drawn in orange.");
+        ui.label("With smooth paths the corners of clicked paths are replaced by arcs the agents drive without stopping.");
+        ui.label("After each click the number of found paths, their total length and the planning time are shown to compare planners.");
         ui.separator();
 
         ui.label("Mouse position:");
@@ -4,22 +4,10 @@ use std::io::Write;
 
 use crate::{
     tool_module::{has_help::HasHelp, tool::Tool},
-    utilities::ui::json_config_combo,
+    utilities::ui::{collision_avoidance_combo, error_label, fleet_editor, json_config_combo},
 };
 use farmbotsim_core::prelude::*;
 
-/// Defines conditions under which the simulation terminates.
-#[derive(Debug, Clone, PartialEq)]
-pub enum TerminationCondition {
-    /// Terminates when all tasks in the environment are completed.
-    /// Only valid if farm entity plans have no cycle.
-    AllTasksCompleted,
-    /// Terminates after a specified number of tasks are completed.
-    NumberCompletedTasks(u32),
-    /// Terminates after a specified duration in simulation time.
-    EnvDuration(Duration),
-}
-
 /// A tool for running and analyzing multiple environment configurations.
 pub struct PerformanceMatrixTool {
     current_pm_path: Option<String>,
@@ -27,15 +15,21 @@ pub struct PerformanceMatrixTool {
     pub running: bool,
     pub n_episodes: u32,
     pub scene_config_path: String,
+    /// Agent config of the first group of every env config.
     pub agent_config_path: String,
     pub datetime_config: DateTimeConfig,
     pub env_configs: Vec<EnvConfig>,
+    /// Total number of agents of the configs added by a fleet sweep.
+    pub sweep_n_agents: u32,
+    /// Agent config mixed with `agent_config_path` by a fleet sweep.
+    pub sweep_agent_config_path: String,
     pub env_episode_stats: Vec<Vec<EnvEpisodeStats>>,
     pub termination_condition: TerminationCondition,
-    pub env: Option<Env>,
+    pub simulation: Option<Simulation>,
     env_index: usize,
     env_episode: u32,
     save_file_name: String,
+    error: Option<String>,
     start_datetime: Option<chrono::DateTime<chrono::Local>>,
     start_time: Option<std::time::Instant>,
     pub help_open: bool,
@@ -57,12 +51,15 @@ impl Default for PerformanceMatrixTool {
             agent_config_path: DEFAULT_AGENT_CONFIG_PATH.to_string(),
             datetime_config: DateTimeConfig::from_string("01.01.2025 00:00:00".to_string()),
             env_configs: vec![],
+            sweep_n_agents: 4,
+            sweep_agent_config_path: DEFAULT_AGENT_CONFIG_PATH.to_string(),
             env_episode_stats: vec![],
             termination_condition,
-            env: None,
+            simulation: None,
             env_index: 0,
             env_episode: 0,
             save_file_name: String::new(),
+            error: None,
             start_datetime: None,
             start_time: None,
             help_open: false,
@@ -177,6 +174,7 @@ impl Tool for PerformanceMatrixTool {
         ui.horizontal(|ui| {
             if ui.button("Add").clicked() {
                 self.env_configs.push(EnvConfig {
+                    fleet: vec![FleetGroup::new(self.agent_config_path.clone(), 1)],
                     scene_config_path: self.scene_config_path.clone(),
                     ..Default::default()
                 });
@@ -184,6 +182,12 @@ impl Tool for PerformanceMatrixTool {
                 // self.env_n_completed_tasks.push(vec![]);
                 self.env_episode_stats.push(vec![]);
             }
+            if ui.button("Duplicate last").clicked()
+                && let Some(last) = self.env_configs.last()
+            {
+                self.env_configs.push(last.clone());
+                self.env_episode_stats.push(vec![]);
+            }
             if ui.button("Remove all").clicked() {
                 self.env_configs.clear();
                 // self.env_durations.clear();
@@ -191,18 +195,65 @@ impl Tool for PerformanceMatrixTool {
                 self.env_episode_stats.clear();
             }
         });
+        // fleet sweep
+        ui.horizontal(|ui| {
+            ui.label("Fleet sweep:");
+            ui.add(
+                egui::DragValue::new(&mut self.sweep_n_agents)
+                    .speed(1)
+                    .range(1..=10),
+            );
+            ui.label("agents mixed with");
+            json_config_combo(
+                ui,
+                "fleet_sweep_agent_config",
+                &mut self.sweep_agent_config_path,
+                AGENT_CONFIGS_PATH,
+            );
+            if ui.button("Add").clicked() {
+                self.add_fleet_sweep();
+            }
+        });
         let mut to_remove: Option<usize> = None;
         for (i, config) in self.env_configs.iter_mut().enumerate() {
             egui::CollapsingHeader::new(format!("Config {i}"))
                 .default_open(true)
                 .show(ui, |ui| {
-                    // n_agents
+                    // fleet
+                    ui.label("fleet:");
+                    fleet_editor(ui, &format!("fleet_{i}"), &mut config.fleet);
+                    // simulation_step
+                    ui.horizontal(|ui| {
+                        ui.label("simulation_step: ");
+                        let mut seconds = config.simulation_step.to_base_unit();
+                        if ui
+                            .add(
+                                egui::DragValue::new(&mut seconds)
+                                    .speed(0.1)
+                                    .range(0.01..=3600.0)
+                                    .suffix(" s"),
+                            )
+                            .changed()
+                        {
+                            config.simulation_step = Duration::seconds(seconds);
+                        }
+                    });
+                    // fast_forward
+                    ui.checkbox(&mut config.fast_forward, "fast_forward");
+                    // persist_battery_health
+                    ui.checkbox(&mut config.persist_battery_health, "persist_battery_health");
+                    // seed
+                    ui.horizontal(|ui| {
+                        ui.label("seed: ");
+                        ui.add(egui::DragValue::new(&mut config.seed).speed(1));
+                    });
+                    // collision avoidance
                     ui.horizontal(|ui| {
-                        ui.label("n_agents: ");
-                        ui.add(
-                            egui::DragValue::new(&mut config.n_agents)
-                                .speed(1)
-                                .range(1..=10),
+                        ui.label("collision_avoidance: ");
+                        collision_avoidance_combo(
+                            ui,
+                            &format!("collision_avoidance_{i}"),
+                            &mut config.collision_avoidance,
                         );
                     });
                     //taskmanager
@@ -242,6 +293,8 @@ impl Tool for PerformanceMatrixTool {
                 TerminationCondition::AllTasksCompleted => "AllTasksCompleted",
                 TerminationCondition::NumberCompletedTasks(_) => "NumberCompletedTasks",
                 TerminationCondition::EnvDuration(_) => "EnvDuration",
+                TerminationCondition::AllAgentsDischarged => "AllAgentsDischarged",
+                _ => "Custom",
             };
 
             egui::ComboBox::from_id_salt("Termination Condition")
@@ -276,18 +329,27 @@ impl Tool for PerformanceMatrixTool {
                             TerminationCondition::EnvDuration(Duration::days(1.0)); // default
                         selected_kind = "EnvDuration";
                     }
+                    if ui
+                        .selectable_label(
+                            selected_kind == "AllAgentsDischarged",
+                            "AllAgentsDischarged",
+                        )
+                        .clicked()
+                    {
+                        self.termination_condition = TerminationCondition::AllAgentsDischarged;
+                        selected_kind = "AllAgentsDischarged";
+                    }
                 });
             match &mut self.termination_condition {
                 TerminationCondition::NumberCompletedTasks(val) => {
                     let mut min_n_actions = 10000;
-                    let scene_config: SceneConfig =
-                        load_json_or_panic(self.scene_config_path.clone());
-                    let field_config: FieldConfig =
-                        load_json_or_panic(scene_config.field_config_path);
-                    if let Some(n_actions) = field_config.number_of_actions() {
-                        if n_actions < min_n_actions {
-                            min_n_actions = n_actions;
-                        }
+                    if let Some(n_actions) = load_json::<SceneConfig, _>(&self.scene_config_path)
+                        .and_then(|scene| load_json::<FieldConfig, _>(scene.field_config_path))
+                        .ok()
+                        .and_then(|field_config| field_config.number_of_actions())
+                        && n_actions < min_n_actions
+                    {
+                        min_n_actions = n_actions;
                     }
                     ui.horizontal(|ui| {
                         ui.label("Completed Tasks:");
@@ -316,6 +378,8 @@ impl Tool for PerformanceMatrixTool {
             self.running = true;
             self.start_datetime = Some(chrono::Local::now());
             self.start_time = Some(std::time::Instant::now());
+            self.simulation = None;
+            self.error = None;
             self.env_index = 0;
             self.env_episode = 0;
         }
@@ -343,6 +407,7 @@ impl Tool for PerformanceMatrixTool {
                 / (self.env_configs.len() as u32 * self.n_episodes) as f32;
             ui.add(egui::ProgressBar::new(progress).show_percentage());
         }
+        error_label(ui, self.error.as_deref());
 
         self.render_help(ui);
     }
@@ -355,36 +420,25 @@ impl Tool for PerformanceMatrixTool {
             return;
         }
 
-        if self.env.is_none() {
-            self.env = Some(Env::from_config(self.env_configs[self.env_index].clone()));
-        }
-        if let Some(env) = &mut self.env {
-            env.task_manager
-                .assign_tasks(&mut env.agents, &mut env.stations);
-            env.step();
-
-            // Check termination condition
-            let finished = match self.termination_condition {
-                TerminationCondition::AllTasksCompleted => {
-                    let scene_config: SceneConfig =
-                        load_json_or_panic(self.scene_config_path.clone());
-                    let field_config: FieldConfig =
-                        load_json_or_panic(scene_config.field_config_path);
-                    if let Some(n_actions) = field_config.number_of_actions() {
-                        env.task_manager.completed_tasks.len() as u32 >= n_actions
-                    } else {
-                        false
-                    }
+        if self.simulation.is_none() {
+            match self.new_simulation() {
+                Ok(mut simulation) => {
+                    simulation.start_episode();
+                    self.simulation = Some(simulation);
                 }
-                TerminationCondition::EnvDuration(duration) => env.duration >= duration,
-                TerminationCondition::NumberCompletedTasks(n_tasks) => {
-                    env.task_manager.completed_tasks.len() as u32 >= n_tasks
+                Err(e) => {
+                    self.error = Some(format!("Env config {}: {e}", self.env_index));
+                    self.running = false;
+                    return;
                 }
-            };
+            }
+        }
+        if let Some(simulation) = &mut self.simulation {
+            simulation.step();
 
             // If episode finished, compute stats and increment results
-            if finished {
-                let episode_stats = env.get_env_episode_stats();
+            if simulation.is_finished() {
+                let episode_stats = simulation.env.get_env_episode_stats();
                 self.increment_env_episode(episode_stats);
             }
         }
@@ -437,16 +491,49 @@ impl PerformanceMatrixTool {
         {
             self.agent_config_path = new_value;
             for env_config in self.env_configs.iter_mut() {
-                env_config.agent_config_path = self.agent_config_path.clone();
+                if let Some(group) = env_config.fleet.first_mut() {
+                    group.agent_config_path = self.agent_config_path.clone();
+                }
             }
         }
     }
 
+    /// Adds env configs for every composition of `sweep_n_agents` agents split between
+    /// `agent_config_path` and `sweep_agent_config_path`.
+    fn add_fleet_sweep(&mut self) {
+        for n_base in (0..=self.sweep_n_agents).rev() {
+            let n_sweep = self.sweep_n_agents - n_base;
+            let fleet = [
+                FleetGroup::new(self.agent_config_path.clone(), n_base),
+                FleetGroup::new(self.sweep_agent_config_path.clone(), n_sweep),
+            ]
+            .into_iter()
+            .filter(|group| group.n_agents > 0)
+            .collect();
+            self.env_configs.push(EnvConfig {
+                datetime_config: self.datetime_config.clone(),
+                scene_config_path: self.scene_config_path.clone(),
+                fleet,
+                ..Default::default()
+            });
+            self.env_episode_stats.push(vec![]);
+        }
+    }
+
     /// Checks if field configuration has any plan with cycle.
+    /// Configs that can't be loaded are treated as having none.
     fn has_cycle_plan(scene_path: String) -> bool {
-        let scene_config: SceneConfig = load_json_or_panic(scene_path);
-        let field_config: FieldConfig = load_json_or_panic(scene_config.field_config_path);
-        field_config.has_cycle_farm_entity_plan()
+        load_json::<SceneConfig, _>(scene_path)
+            .and_then(|scene_config| load_json::<FieldConfig, _>(scene_config.field_config_path))
+            .is_ok_and(|field_config| field_config.has_cycle_farm_entity_plan())
+    }
+
+    /// Creates a simulation for the current env config with the selected termination condition.
+    fn new_simulation(&self) -> Result<Simulation, FarmbotSimError> {
+        Simulation::try_from_config(
+            self.env_configs[self.env_index].clone(),
+            self.termination_condition.clone(),
+        )
     }
 
     /// Increment episode/env counters and store episode-level data
@@ -458,16 +545,18 @@ impl PerformanceMatrixTool {
         self.env_episode += 1;
 
         if self.env_episode < self.n_episodes {
-            // Same environment, next episode
-            self.env = Some(Env::from_config(self.env_configs[self.env_index].clone()));
+            // Same environment, next episode with the next seed
+            if let Some(simulation) = &mut self.simulation {
+                simulation.start_episode();
+            }
         } else {
             // Completed all episodes for current environment
             self.env_episode = 0;
             self.env_index += 1;
 
             if self.env_index < self.env_configs.len() {
-                // New environment, first episode
-                self.env = Some(Env::from_config(self.env_configs[self.env_index].clone()));
+                // New environment, first episode is created on the next update
+                self.simulation = None;
             } else {
                 // All environments completed → finalize
                 self.finalize_result();
@@ -541,7 +630,10 @@ impl HasHelp for PerformanceMatrixTool {
 
         ui.label("Env configs:");
         ui.label("Add or remove env configs.");
-        ui.label("Set number of agents and task manager config for each env config");
+        ui.label("Set fleet and task manager config for each env config");
+        ui.label("The fleet of an env config lists agent groups with their own number of agents, agent config and start SoC. The selected agent config is used by the first group of every env config.");
+        ui.label("With persist_battery_health, batteries with an ageing model keep their wear from one episode to the next.");
+        ui.label("Fleet sweep adds an env config for every split of the given number of agents between the selected agent config and a second one.");
         ui.separator();
 
         ui.label("Set condition when env stops");
@@ -549,6 +641,7 @@ impl HasHelp for PerformanceMatrixTool {
 
         ui.label("Saving");
         ui.label("Name file and start evaluation.");
+        ui.label("If an env config can't be loaded, evaluation stops and the error is shown.");
     }
 }
 
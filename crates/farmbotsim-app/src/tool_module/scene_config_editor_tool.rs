@@ -7,13 +7,17 @@ use crate::{
         camera::Camera,
         render::{
             render_coordinate_system, render_drag_points, render_field_config, render_grid,
-            render_obstacles, render_spawn_area, render_station, render_visibility_graph,
+            render_no_go_zones, render_obstacles, render_spawn_area, render_station,
+            render_visibility_graph,
         },
     },
     tool_module::{
         has_camera::HasCamera, has_config_saving::HasConfigSaving, has_help::HasHelp, tool::Tool,
     },
-    utilities::ui::json_config_combo,
+    utilities::ui::{
+        error_label, json_config_combo, path_planner_combo, power_budget_editor,
+        validation_issues_label,
+    },
 };
 
 /// A tool to edit, view, change scene configuration
@@ -22,6 +26,8 @@ pub struct SceneConfigEditorTool {
     field_config: FieldConfig,
     pub camera: Camera,
     save_file_name: String,
+    load_error: Option<String>,
+    validation_issues: Option<Vec<ValidationIssue>>,
     pub current_scene_config_path: String,
     pub help_open: bool,
 }
@@ -37,6 +43,8 @@ impl Default for SceneConfigEditorTool {
             scene_config,
             camera: Camera::default(),
             save_file_name: String::new(),
+            load_error: None,
+            validation_issues: None,
             current_scene_config_path: DEFAULT_SCENE_CONFIG_PATH.to_string(),
             help_open: false,
         }
@@ -55,6 +63,7 @@ impl Tool for SceneConfigEditorTool {
             &self.camera,
             &SpawnArea::from_config(self.scene_config.spawn_area_config.clone()),
         );
+        render_no_go_zones(ui, &self.camera, &self.field_config.no_go_zones);
         render_obstacles(ui, &self.camera, &self.field_config.get_obstacles());
         render_visibility_graph(
             ui,
@@ -88,11 +97,32 @@ impl Tool for SceneConfigEditorTool {
         self.save_file_name = save_file_name;
         ui.separator();
 
+        if ui.button("Validate").clicked() {
+            self.validation_issues = Some(validate_scene(
+                &self.scene_config,
+                &self.field_config,
+                &EnvConfig::default().fleet,
+            ));
+        }
+        validation_issues_label(ui, self.validation_issues.as_deref());
+        ui.separator();
+
         self.ui_mouse_position(ui);
         ui.separator();
 
         self.ui_field_config_select(ui);
 
+        ui.horizontal(|ui| {
+            ui.label("Path planner:");
+            path_planner_combo(
+                ui,
+                "scene_path_planner",
+                &mut self.scene_config.path_planner,
+            );
+        });
+        ui.checkbox(&mut self.scene_config.path_smoothing, "Path smoothing");
+        ui.separator();
+
         ui.label(egui::RichText::new("Spawn area:").size(16.0));
         ui.label(format!(
             "Left top pos {}",
@@ -216,6 +246,7 @@ impl Tool for SceneConfigEditorTool {
                             );
                         });
                 }
+                power_budget_editor(ui, &format!("station_{i}"), &mut station.power_budget);
 
                 if ui.button("Remove").clicked() {
                     to_remove = Some(i);
@@ -288,17 +319,33 @@ impl SceneConfigEditorTool {
         }
     }
 
-    /// Changes scene configuration.
+    /// Changes scene configuration, keeping the current one if it can't be loaded.
     fn change_scene_config(&mut self) {
-        let scene_config: SceneConfig = load_json_or_panic(self.current_scene_config_path.clone());
-        self.scene_config = scene_config;
-        self.field_config = load_json_or_panic(self.scene_config.field_config_path.clone());
+        let result =
+            load_json::<SceneConfig, _>(&self.current_scene_config_path).and_then(|scene_config| {
+                let field_config = load_json(&scene_config.field_config_path)?;
+                Ok((scene_config, field_config))
+            });
+        match result {
+            Ok((scene_config, field_config)) => {
+                self.scene_config = scene_config;
+                self.field_config = field_config;
+                self.load_error = None;
+            }
+            Err(e) => self.load_error = Some(e.to_string()),
+        }
     }
 
-    /// Changes field configuration.
+    /// Changes field configuration, keeping the current one if it can't be loaded.
     fn change_field_config(&mut self) {
-        self.field_config = load_json_or_panic(self.scene_config.field_config_path.clone());
-        self.field_config.recalc_id_color();
+        match load_json::<FieldConfig, _>(&self.scene_config.field_config_path) {
+            Ok(field_config) => {
+                self.field_config = field_config;
+                self.field_config.recalc_id_color();
+                self.load_error = None;
+            }
+            Err(e) => self.load_error = Some(e.to_string()),
+        }
     }
 
     /// Renders dropdown to select scene configuration file.
@@ -313,6 +360,7 @@ impl SceneConfigEditorTool {
             self.current_scene_config_path = new_value;
             self.change_scene_config();
         }
+        error_label(ui, self.load_error.as_deref());
     }
 
     /// Renders dropdown to select field configuration file.
@@ -358,6 +406,10 @@ impl HasHelp for SceneConfigEditorTool {
         ui.label("In dropdown you can select scene config and save new config");
         ui.separator();
 
+        ui.label("Validate:");
+        ui.label("Check the scene for overlapping fields, incompatible plans, spawn area intersecting obstacles and unreachable crops, rows or stations.");
+        ui.separator();
+
         ui.label("Mouse position:");
         ui.label("See where mouse is on screen and in env/scene.");
         ui.separator();
@@ -366,12 +418,21 @@ impl HasHelp for SceneConfigEditorTool {
         ui.label("Select field config (see FieldConfigEditor)");
         ui.separator();
 
+        ui.label("Path planner:");
+        ui.label("VisibilityGraph plans shortest paths and ignores other agents. Reservation reserves the space and time of each planned path, later paths wait at graph points until reserved segments are free. Time spent waiting is reported as conflict wait time.");
+        ui.label("Grid A* and Grid Theta* search an occupancy grid with the given cell size built from the obstacles, Theta* shortcuts cells in line of sight.");
+        ui.label("Obstacles are inflated by the footprint radius of the largest agent before planning, so that paths keep clear of them. Non-convex obstacles are inflated as their convex hull, split them into convex ones to keep their inner corners reachable. Validation uses the agents of the default env config.");
+        ui.label("Path smoothing replaces the corners of travel paths with arcs that avoid the obstacles, agents drive them without stopping and turn no faster than their max angular velocity. Corners without such an arc are turned in place.");
+        ui.separator();
+
         ui.label("SpawnArea Config:");
         ui.label("Set params for spawn area");
         ui.separator();
 
         ui.label("Station Configs:");
         ui.label("Set number and params for stations");
+        ui.label("A power budget limits the power all slots of a station draw together. EqualShare splits it evenly, LowestSocFirst serves the emptiest battery first, Fifo serves agents in the order they got their slot. An hourly profile scales the max power with a fraction (0-1) for each hour of the day, e.g. for a solar panel.");
+        ui.label("A Solar source computes the panel power from the site latitude, panel area and efficiency with a clear-sky model, or from an hourly irradiance CSV in configs/irradiance/, capped at the max power.");
         ui.separator();
     }
 }
@@ -1,17 +1,22 @@
-use farmbotsim_core::environment::env_module::{env::Env, env_config::EnvConfig};
+use farmbotsim_core::{
+    cfg::{EVENT_LOGS_PATH, SNAPSHOTS_PATH},
+    environment::env_module::{env::Env, env_config::EnvConfig, env_snapshot::EnvSnapshot},
+};
 
 use crate::{
     rendering::{
         camera::Camera,
         render::{
-            render_agents, render_coordinate_system, render_grid, render_obstacles,
-            render_spawn_area, render_stations, render_task_manager_on_field, ui_render_agents,
-            ui_render_datetime, ui_render_stations, ui_render_task_manager,
+            render_agents, render_coordinate_system, render_grid, render_no_go_zones,
+            render_obstacles, render_spawn_area, render_stations, render_task_manager_on_field,
+            ui_render_agents, ui_render_datetime, ui_render_solar_irradiance, ui_render_stations,
+            ui_render_task_manager,
         },
     },
     tool_module::{
         has_env::HasEnv, has_env_controls::HasEnvControls, has_help::HasHelp, tool::Tool,
     },
+    utilities::ui::json_config_combo,
 };
 
 /// A tool to set and view simulation in action.
@@ -22,7 +27,14 @@ pub struct SimulationTool {
     pub env: Env,
     pub camera: Camera,
     pub help_open: bool,
+    pub env_error: Option<String>,
     pub show_battery_plot: bool,
+    pub snapshot_file_name: String,
+    pub snapshot_path: String,
+    pub snapshot_include_timesteps: bool,
+    pub snapshot_message: Option<String>,
+    pub event_log_file_name: String,
+    pub event_log_message: Option<String>,
 }
 
 impl Default for SimulationTool {
@@ -36,7 +48,14 @@ impl Default for SimulationTool {
             env,
             camera: Camera::default(),
             help_open: false,
+            env_error: None,
             show_battery_plot: false,
+            snapshot_file_name: String::new(),
+            snapshot_path: String::new(),
+            snapshot_include_timesteps: false,
+            snapshot_message: None,
+            event_log_file_name: String::new(),
+            event_log_message: None,
         }
     }
 }
@@ -47,6 +66,7 @@ impl Tool for SimulationTool {
         render_grid(ui, &self.camera);
         render_coordinate_system(ui, &self.camera);
         render_spawn_area(ui, &self.camera, &self.env.spawn_area);
+        render_no_go_zones(ui, &self.camera, &self.env.field_config.no_go_zones);
         render_obstacles(ui, &self.camera, &self.env.obstacles);
         render_task_manager_on_field(ui, &self.camera, &self.env.task_manager);
         render_stations(ui, &self.camera, &self.env.stations, false);
@@ -57,29 +77,29 @@ impl Tool for SimulationTool {
         ui.separator();
 
         ui.label(egui::RichText::new("Env config:").size(16.0));
-        // n_agents
+        // fleet
+        ui.label("fleet:");
+        self.ui_fleet_select(ui);
+        // datetime
         ui.horizontal(|ui| {
-            ui.label("n_agents:");
-            if ui
-                .add(
-                    egui::DragValue::new(&mut self.env_config.n_agents)
-                        .speed(1)
-                        .range(1..=10),
-                )
-                .changed()
-            {
-                self.rebuild_env();
-            };
+            ui.label("datetime:");
+            self.ui_datetime_select(ui);
         });
-        // agent_config_path
+        // simulation step
         ui.horizontal(|ui| {
-            ui.label("agent_config_path:");
-            self.ui_agent_config_select(ui);
+            ui.label("simulation_step:");
+            self.ui_simulation_step_select(ui);
         });
-        // datetime
+        // seed
         ui.horizontal(|ui| {
-            ui.label("datetime:");
-            self.ui_datetime_select(ui);
+            ui.label("seed:");
+            self.ui_seed_select(ui);
+            ui.label(format!("| episode seed: {}", self.env.seed));
+        });
+        // collision avoidance
+        ui.horizontal(|ui| {
+            ui.label("collision_avoidance:");
+            self.ui_collision_avoidance_select(ui);
         });
         // scene_config
         ui.horizontal(|ui| {
@@ -91,32 +111,122 @@ impl Tool for SimulationTool {
             ui.label("task_manager_config_path:");
             self.ui_task_manager_config_select(ui);
         });
+        self.ui_render_env_error(ui);
 
         ui.separator();
 
         self.ui_render_controls(ui);
         ui.separator();
 
+        self.ui_render_snapshot(ui);
+        ui.separator();
+
+        self.ui_render_event_log(ui);
+        ui.separator();
+
         ui.checkbox(&mut self.show_battery_plot, "Battery plot");
         ui.label(egui::RichText::new("Env information:").size(16.0));
         ui_render_datetime(ui, &self.env.date_time_manager);
         ui_render_agents(ui, &self.env.agents, self.show_battery_plot);
         ui_render_stations(ui, &self.env.stations);
-        ui_render_task_manager(ui, &self.env.task_manager);
+        ui_render_solar_irradiance(
+            ui,
+            &self.env.stations,
+            &self.env.date_time_manager.current_time,
+        );
+        ui_render_task_manager(ui, &self.env.task_manager, &self.env.agents);
 
         self.render_help(ui);
     }
     fn update(&mut self) {
         if self.running {
             self.tick += 1;
-            self.env
-                .task_manager
-                .assign_tasks(&mut self.env.agents, &mut self.env.stations);
+            self.env.task_manager.assign_tasks(
+                &mut self.env.agents,
+                &mut self.env.stations,
+                self.env.duration,
+            );
             self.env.step();
         }
     }
 }
 
+impl SimulationTool {
+    /// Renders controls to save the current env state to a snapshot file and restore it later.
+    fn ui_render_snapshot(&mut self, ui: &mut egui::Ui) {
+        ui.label(egui::RichText::new("Snapshot:").size(16.0));
+        ui.horizontal(|ui| {
+            ui.label("File name:");
+            ui.add(egui::TextEdit::singleline(&mut self.snapshot_file_name).desired_width(100.0));
+            ui.checkbox(&mut self.snapshot_include_timesteps, "timesteps");
+            if ui.button("Save").clicked() && !self.snapshot_file_name.is_empty() {
+                let path = format!("{}{}.json", SNAPSHOTS_PATH, self.snapshot_file_name);
+                let result = std::fs::create_dir_all(SNAPSHOTS_PATH)
+                    .map_err(|e| e.to_string())
+                    .and_then(|_| {
+                        self.env
+                            .to_snapshot(self.snapshot_include_timesteps)
+                            .save_json_file(&path)
+                            .map_err(|e| e.to_string())
+                    });
+                self.snapshot_message = Some(match result {
+                    Ok(()) => format!("Saved {path}"),
+                    Err(e) => format!("Failed to save snapshot: {e}"),
+                });
+            }
+        });
+        // Folder is created on the first save
+        if std::path::Path::new(SNAPSHOTS_PATH).is_dir() {
+            self.ui_render_snapshot_load(ui);
+        }
+        if let Some(message) = &self.snapshot_message {
+            ui.label(message);
+        }
+    }
+
+    /// Renders controls to save the recorded env events to a JSONL file.
+    fn ui_render_event_log(&mut self, ui: &mut egui::Ui) {
+        ui.label(egui::RichText::new("Event log:").size(16.0));
+        ui.horizontal(|ui| {
+            ui.label(format!("Events: {}", self.env.event_log.events.len()));
+            ui.label("File name:");
+            ui.add(egui::TextEdit::singleline(&mut self.event_log_file_name).desired_width(100.0));
+            if ui.button("Save").clicked() && !self.event_log_file_name.is_empty() {
+                let path = format!("{}{}.jsonl", EVENT_LOGS_PATH, self.event_log_file_name);
+                let result = std::fs::create_dir_all(EVENT_LOGS_PATH)
+                    .and_then(|_| self.env.event_log.save_jsonl_file(&path));
+                self.event_log_message = Some(match result {
+                    Ok(()) => format!("Saved {path}"),
+                    Err(e) => format!("Failed to save event log: {e}"),
+                });
+            }
+        });
+        if let Some(message) = &self.event_log_message {
+            ui.label(message);
+        }
+    }
+
+    /// Renders controls to restore the env from a saved snapshot file.
+    fn ui_render_snapshot_load(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            json_config_combo(ui, "snapshot", &mut self.snapshot_path, SNAPSHOTS_PATH);
+            if ui.button("Load").clicked() && !self.snapshot_path.is_empty() {
+                match EnvSnapshot::from_json_file(&self.snapshot_path) {
+                    Ok(snapshot) => {
+                        self.env.restore_snapshot(snapshot);
+                        self.running = false;
+                        self.tick = self.env.step_count;
+                        self.snapshot_message = Some(format!("Loaded {}", self.snapshot_path));
+                    }
+                    Err(e) => {
+                        self.snapshot_message = Some(format!("Failed to load snapshot: {e}"));
+                    }
+                }
+            }
+        });
+    }
+}
+
 impl HasHelp for SimulationTool {
     fn help_modal(&self) -> egui::Modal {
         egui::Modal::new(egui::Id::new("Simulation Tool Help"))
@@ -128,16 +238,33 @@ impl HasHelp for SimulationTool {
 
         ui.label("Env config:");
         ui.label("Configure env config.");
+        ui.label("fleet: groups of agents with their own number of agents, agent config and optionally their own start SoC.");
+        ui.label("If a selected config can't be loaded, the previous env is kept and the error is shown.");
+        ui.label("collision_avoidance: None only counts collisions and near misses, YieldByPriority makes agents stop for close agents with higher priority (heading to station > working > rest).");
         ui.separator();
 
         ui.label("Env controls:");
         ui.label("Then you have start/pause/resume/reset controls for env as well as current env step count.");
         ui.separator();
 
+        ui.label("Snapshot:");
+        ui.label("Save the current env state to a snapshot file and load it later to continue from that state.");
+        ui.label("Recorded agent timesteps are only saved if checked, they make snapshots large.");
+        ui.separator();
+
+        ui.label("Event log:");
+        ui.label("Save events (tasks, state changes, station slots/queue, discharges, collisions) recorded since the last reset as JSONL.");
+        ui.label(
+            "The file can be summarized with the analyze_events binary of the analysis crate.",
+        );
+        ui.separator();
+
         ui.label("Env information:");
         ui.label("Date time to keep track of time progression.");
         ui.label("Agents are represented with table with their information.");
+        ui.label("Contacts column shows collisions / near misses of each agent.");
         ui.label("Stations are represented in table with information.");
+        ui.label("Solar-powered stations plot the irradiance over the current day, the red line marks the current time.");
         ui.label("Task manager with available, assigned, completed tasks");
     }
 }
@@ -1,6 +1,6 @@
 use crate::{
     tool_module::{has_config_saving::HasConfigSaving, has_help::HasHelp, tool::Tool},
-    utilities::ui::json_config_combo,
+    utilities::ui::{error_label, json_config_combo},
 };
 use farmbotsim_core::prelude::*;
 
@@ -8,6 +8,7 @@ use farmbotsim_core::prelude::*;
 pub struct TaskManagerConfigEditorTool {
     config: TaskManagerConfig,
     save_file_name: String,
+    load_error: Option<String>,
     pub current_config_path: String,
     pub help_open: bool,
 }
@@ -18,6 +19,7 @@ impl Default for TaskManagerConfigEditorTool {
         Self {
             config,
             save_file_name: String::new(),
+            load_error: None,
             current_config_path: DEFAULT_TASK_MANAGER_CONFIG_PATH.to_string(),
             help_open: false,
         }
@@ -54,9 +56,15 @@ impl TaskManagerConfigEditorTool {
             && new_path != self.current_config_path
         {
             self.current_config_path = new_path;
-            let config = load_json_or_panic(self.current_config_path.clone());
-            self.config = config;
+            match load_json(&self.current_config_path) {
+                Ok(config) => {
+                    self.config = config;
+                    self.load_error = None;
+                }
+                Err(e) => self.load_error = Some(e.to_string()),
+            }
         }
+        error_label(ui, self.load_error.as_deref());
     }
 
     /// Renders json-like structure with editable values
@@ -4,8 +4,8 @@ use crate::{
     rendering::{
         camera::Camera,
         render::{
-            render_agents, render_coordinate_system, render_grid, render_obstacles,
-            render_spawn_area, render_stations, render_task_manager_on_field,
+            render_agents, render_coordinate_system, render_grid, render_no_go_zones,
+            render_obstacles, render_spawn_area, render_stations, render_task_manager_on_field,
             render_visibility_graph, ui_render_agents, ui_render_datetime, ui_render_stations,
             ui_render_task_manager,
         },
@@ -25,6 +25,7 @@ pub struct TaskTool {
     pub env: Env,
     pub camera: Camera,
     pub help_open: bool,
+    pub env_error: Option<String>,
 }
 
 impl Default for TaskTool {
@@ -38,6 +39,7 @@ impl Default for TaskTool {
             env,
             camera: Camera::default(),
             help_open: false,
+            env_error: None,
         }
     }
 }
@@ -49,6 +51,7 @@ impl Tool for TaskTool {
         render_coordinate_system(ui, &self.camera);
         render_spawn_area(ui, &self.camera, &self.env.spawn_area);
         render_visibility_graph(ui, &self.camera, &self.env.visibility_graph);
+        render_no_go_zones(ui, &self.camera, &self.env.field_config.no_go_zones);
         render_obstacles(ui, &self.camera, &self.env.obstacles);
         render_task_manager_on_field(ui, &self.camera, &self.env.task_manager);
         render_stations(ui, &self.camera, &self.env.stations, false);
@@ -59,25 +62,9 @@ impl Tool for TaskTool {
         ui.separator();
 
         ui.label(egui::RichText::new("Env config:").size(16.0));
-        // n_agents
-        ui.horizontal(|ui| {
-            ui.label("n_agents:");
-            if ui
-                .add(
-                    egui::DragValue::new(&mut self.env_config.n_agents)
-                        .speed(1)
-                        .range(1..=10),
-                )
-                .changed()
-            {
-                self.rebuild_env();
-            };
-        });
-        // agent_config_path
-        ui.horizontal(|ui| {
-            ui.label("agent_config_path:");
-            self.ui_agent_config_select(ui);
-        });
+        // fleet
+        ui.label("fleet:");
+        self.ui_fleet_select(ui);
         // datetime
         ui.horizontal(|ui| {
             ui.label("datetime:");
@@ -93,6 +80,7 @@ impl Tool for TaskTool {
             ui.label("task_manager_config_path:");
             self.ui_task_manager_config_select(ui);
         });
+        self.ui_render_env_error(ui);
         ui.separator();
 
         self.ui_mouse_position(ui);
@@ -247,7 +235,7 @@ impl Tool for TaskTool {
         ui_render_datetime(ui, &self.env.date_time_manager);
         ui_render_agents(ui, &self.env.agents, true);
         ui_render_stations(ui, &self.env.stations);
-        ui_render_task_manager(ui, &self.env.task_manager);
+        ui_render_task_manager(ui, &self.env.task_manager, &self.env.agents);
         ui.separator();
 
         self.render_help(ui);
@@ -274,6 +262,8 @@ impl HasHelp for TaskTool {
 
         ui.label("Env config:");
         ui.label("Configure env config.");
+        ui.label("fleet: groups of agents with their own number of agents, agent config and optionally their own start SoC.");
+        ui.label("If a selected config can't be loaded, the previous env is kept and the error is shown.");
         ui.separator();
 
         ui.label("Env controls:");
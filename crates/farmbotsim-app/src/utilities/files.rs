@@ -9,7 +9,12 @@ use std::{
 
 /// Get json files that are in folder with path.
 pub fn get_json_files_in_folder(path: &str) -> Vec<String> {
-    let mut json_files = Vec::new();
+    get_files_in_folder(path, "json")
+}
+
+/// Get files with the extension that are in folder with path.
+pub fn get_files_in_folder(path: &str, extension: &str) -> Vec<String> {
+    let mut files = Vec::new();
 
     let entries = fs::read_dir(path).unwrap_or_else(|e| {
         let msg = format!("Failed to read directory {path}: {e}");
@@ -23,14 +28,14 @@ pub fn get_json_files_in_folder(path: &str) -> Vec<String> {
         });
 
         let path = entry.path();
-        if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("json") {
+        if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some(extension) {
             if let Some(file_name) = path.file_name().and_then(|s| s.to_str()) {
-                json_files.push(file_name.to_string());
+                files.push(file_name.to_string());
             }
         }
     }
 
-    json_files
+    files
 }
 
 /// Get folders that are in folder with path.
@@ -1,4 +1,11 @@
-use crate::utilities::files::{get_folders_in_folder, get_json_files_in_folder};
+use farmbotsim_core::prelude::{
+    AGENT_CONFIGS_PATH, CollisionAvoidance, DEFAULT_GRID_CELL_SIZE, FleetGroup, IRRADIANCE_PATH,
+    PathPlannerKind, PowerBudget, PowerSharePolicy, PowerSource, SolarModel, ValidationIssue,
+};
+
+use crate::utilities::files::{
+    get_files_in_folder, get_folders_in_folder, get_json_files_in_folder,
+};
 
 /// Renders dropdown of all json config files in folder path.
 pub fn json_config_combo(
@@ -28,6 +35,111 @@ pub fn json_config_combo(
     changed
 }
 
+/// Renders error message in red if there is one.
+pub fn error_label(ui: &mut egui::Ui, error: Option<&str>) {
+    if let Some(error) = error {
+        ui.colored_label(egui::Color32::RED, error);
+    }
+}
+
+/// Renders validation result, either a success message or all issues in red.
+pub fn validation_issues_label(ui: &mut egui::Ui, issues: Option<&[ValidationIssue]>) {
+    match issues {
+        Some([]) => {
+            ui.colored_label(egui::Color32::GREEN, "No issues found");
+        }
+        Some(issues) => {
+            egui::ScrollArea::vertical()
+                .id_salt("validation_issues")
+                .max_height(150.0)
+                .show(ui, |ui| {
+                    for issue in issues {
+                        ui.colored_label(egui::Color32::RED, issue.to_string());
+                    }
+                });
+        }
+        None => {}
+    }
+}
+
+/// Renders dropdown to select collision avoidance behaviour.
+pub fn collision_avoidance_combo(
+    ui: &mut egui::Ui,
+    id_salt: &str,
+    current_value: &mut CollisionAvoidance,
+) -> bool {
+    let previous_value = *current_value;
+
+    egui::ComboBox::from_id_salt(id_salt)
+        .selected_text(format!("{current_value:?}"))
+        .show_ui(ui, |ui| {
+            for option in [
+                CollisionAvoidance::None,
+                CollisionAvoidance::YieldByPriority,
+            ] {
+                ui.selectable_value(current_value, option, format!("{option:?}"));
+            }
+        });
+
+    *current_value != previous_value
+}
+
+/// Renders dropdown to select a path planner kind and the grid cell size for grid planners,
+/// returns true if the value changed.
+pub fn path_planner_combo(
+    ui: &mut egui::Ui,
+    id_salt: &str,
+    current_value: &mut PathPlannerKind,
+) -> bool {
+    fn label(kind: &PathPlannerKind) -> &'static str {
+        match kind {
+            PathPlannerKind::VisibilityGraph => "VisibilityGraph",
+            PathPlannerKind::Reservation => "Reservation",
+            PathPlannerKind::Grid {
+                any_angle: false, ..
+            } => "Grid A*",
+            PathPlannerKind::Grid {
+                any_angle: true, ..
+            } => "Grid Theta*",
+        }
+    }
+    let previous_value = *current_value;
+    let grid_cell_size = match *current_value {
+        PathPlannerKind::Grid { cell_size, .. } => cell_size,
+        _ => DEFAULT_GRID_CELL_SIZE,
+    };
+
+    egui::ComboBox::from_id_salt(id_salt)
+        .selected_text(label(current_value))
+        .show_ui(ui, |ui| {
+            for option in [
+                PathPlannerKind::VisibilityGraph,
+                PathPlannerKind::Reservation,
+                PathPlannerKind::Grid {
+                    cell_size: grid_cell_size,
+                    any_angle: false,
+                },
+                PathPlannerKind::Grid {
+                    cell_size: grid_cell_size,
+                    any_angle: true,
+                },
+            ] {
+                ui.selectable_value(current_value, option, label(&option));
+            }
+        });
+    if let PathPlannerKind::Grid { cell_size, .. } = current_value {
+        ui.add(
+            egui::DragValue::new(&mut cell_size.value)
+                .speed(0.01)
+                .range(0.01..=1.0)
+                .prefix("cell size: ")
+                .suffix(format!(" {}", cell_size.unit)),
+        );
+    }
+
+    *current_value != previous_value
+}
+
 /// Renders dropdown of all folders in folder path.
 pub fn folder_select_combo(
     ui: &mut egui::Ui,
@@ -89,3 +201,185 @@ pub fn value_with_unit_selector_ui<T: ToString + PartialEq + Copy + enum_iterato
             });
     });
 }
+
+/// Renders editable agent groups of a fleet, returns true if the fleet changed.
+/// The last group can't be removed. Spawn areas of groups can only be set in the JSON config.
+pub fn fleet_editor(ui: &mut egui::Ui, id_salt: &str, fleet: &mut Vec<FleetGroup>) -> bool {
+    let mut changed = false;
+    let mut to_remove: Option<usize> = None;
+    let removable = fleet.len() > 1;
+    for (i, group) in fleet.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            ui.label(format!("Group {}:", i + 1));
+            changed |= ui
+                .add(egui::DragValue::new(&mut group.n_agents).range(1..=10))
+                .changed();
+            changed |= json_config_combo(
+                ui,
+                &format!("{id_salt}_agent_config_{i}"),
+                &mut group.agent_config_path,
+                AGENT_CONFIGS_PATH,
+            );
+            if removable && ui.button("Remove").clicked() {
+                to_remove = Some(i);
+            }
+        });
+        ui.horizontal(|ui| {
+            let mut custom_soc = group.start_soc.is_some();
+            if ui.checkbox(&mut custom_soc, "start SoC").changed() {
+                group.start_soc = custom_soc.then_some(100.0);
+                changed = true;
+            }
+            if let Some(soc) = &mut group.start_soc {
+                changed |= ui
+                    .add(egui::Slider::new(soc, 0.0..=100.0).suffix(" %"))
+                    .changed();
+            }
+            if group.spawn_area.is_some() {
+                ui.label("| own spawn area");
+            }
+        });
+    }
+    if let Some(index) = to_remove {
+        fleet.remove(index);
+        changed = true;
+    }
+    if ui.button("Add group").clicked() {
+        fleet.push(FleetGroup::default());
+        changed = true;
+    }
+    changed
+}
+
+/// Renders an optional station power budget with its share policy and power source.
+pub fn power_budget_editor(
+    ui: &mut egui::Ui,
+    id_salt: &str,
+    power_budget: &mut Option<PowerBudget>,
+) {
+    let mut limited = power_budget.is_some();
+    if ui.checkbox(&mut limited, "power_budget").changed() {
+        *power_budget = limited.then(PowerBudget::default);
+    }
+    let Some(budget) = power_budget else {
+        return;
+    };
+    value_with_unit_selector_ui(
+        ui,
+        &format!("{id_salt}_max_power"),
+        "max_power",
+        &mut budget.max_power.value,
+        &mut budget.max_power.unit,
+        Some(0.0),
+        None,
+    );
+    ui.horizontal(|ui| {
+        ui.label("policy:");
+        egui::ComboBox::from_id_salt(format!("{id_salt}_policy"))
+            .selected_text(budget.policy.as_str())
+            .show_ui(ui, |ui| {
+                for policy in [
+                    PowerSharePolicy::EqualShare,
+                    PowerSharePolicy::LowestSocFirst,
+                    PowerSharePolicy::Fifo,
+                ] {
+                    ui.selectable_value(&mut budget.policy, policy, policy.as_str());
+                }
+            });
+    });
+    let source_name = |source: &PowerSource| match source {
+        PowerSource::Grid => "Grid",
+        PowerSource::HourlyProfile(_) => "HourlyProfile",
+        PowerSource::Solar(_) => "Solar",
+    };
+    ui.horizontal(|ui| {
+        ui.label("source:");
+        egui::ComboBox::from_id_salt(format!("{id_salt}_source"))
+            .selected_text(source_name(&budget.source))
+            .show_ui(ui, |ui| {
+                for source in [
+                    PowerSource::Grid,
+                    PowerSource::HourlyProfile(vec![1.0; 24]),
+                    PowerSource::Solar(SolarModel::default()),
+                ] {
+                    let selected = source_name(&budget.source) == source_name(&source);
+                    if ui
+                        .selectable_label(selected, source_name(&source))
+                        .clicked()
+                        && !selected
+                    {
+                        budget.source = source;
+                    }
+                }
+            });
+    });
+    match &mut budget.source {
+        PowerSource::Grid => {}
+        PowerSource::HourlyProfile(profile) => {
+            egui::Grid::new(format!("{id_salt}_profile")).show(ui, |ui| {
+                for (hour, value) in profile.iter_mut().enumerate() {
+                    ui.label(format!("{hour:02}h"));
+                    ui.add(egui::DragValue::new(value).range(0.0..=1.0).speed(0.01));
+                    if hour % 6 == 5 {
+                        ui.end_row();
+                    }
+                }
+            });
+        }
+        PowerSource::Solar(solar) => {
+            ui.add(egui::Slider::new(&mut solar.latitude, -90.0..=90.0).text("latitude [°]"));
+            ui.add(egui::Slider::new(&mut solar.panel_area, 0.1..=20.0).text("panel_area [m²]"));
+            ui.add(egui::Slider::new(&mut solar.efficiency, 0.01..=1.0).text("efficiency"));
+            ui.horizontal(|ui| {
+                let mut from_file = solar.irradiance_file.is_some();
+                if ui.checkbox(&mut from_file, "irradiance_file").changed() {
+                    solar.irradiance_file = from_file.then(String::new);
+                    solar.irradiance_series.clear();
+                }
+                if let Some(file) = &mut solar.irradiance_file {
+                    egui::ComboBox::from_id_salt(format!("{id_salt}_irradiance_file"))
+                        .selected_text(format!("{file:?}"))
+                        .show_ui(ui, |ui| {
+                            for name in get_files_in_folder(IRRADIANCE_PATH, "csv") {
+                                let path = format!("{IRRADIANCE_PATH}{name}");
+                                ui.selectable_value(file, path, name);
+                            }
+                        });
+                }
+            });
+        }
+    }
+}
+
+/// Renders editable capability names in a row, returns true if the capabilities changed.
+pub fn capabilities_editor(
+    ui: &mut egui::Ui,
+    id_salt: &str,
+    capabilities: &mut Vec<String>,
+) -> bool {
+    let mut changed = false;
+    let mut to_remove: Option<usize> = None;
+    ui.horizontal(|ui| {
+        for (i, capability) in capabilities.iter_mut().enumerate() {
+            changed |= ui
+                .add(
+                    egui::TextEdit::singleline(capability)
+                        .id_salt(format!("{id_salt}_{i}"))
+                        .desired_width(70.0),
+                )
+                .changed();
+            if ui.small_button("x").clicked() {
+                to_remove = Some(i);
+            }
+        }
+        if let Some(index) = to_remove {
+            capabilities.remove(index);
+            changed = true;
+        }
+        if ui.small_button("+").clicked() {
+            capabilities.push(String::new());
+            changed = true;
+        }
+    });
+    changed
+}
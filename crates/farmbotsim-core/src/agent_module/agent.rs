@@ -1,23 +1,28 @@
 use egui::{Color32, Pos2, Vec2};
+use rand::SeedableRng;
+use rand_chacha::ChaCha12Rng;
 use serde::{Deserialize, Serialize};
 
 use crate::{
     agent_module::{
-        agent_config::AgentConfig, agent_state::AgentState, work_schedule::WorkSchedule,
+        agent_config::AgentConfig, agent_state::AgentState, power_model::PowerModel,
+        work_schedule::WorkSchedule,
     },
-    battery_module::{battery::Battery, battery_config::BatteryConfig},
-    cfg::{TOLERANCE_ANGLE, TOLERANCE_DISTANCE},
+    battery_module::{battery::Battery, battery_config::BatteryConfig, is_battery::IsBattery},
+    cfg::TOLERANCE_DISTANCE,
     environment::datetime::DateTimeManager,
+    error::FarmbotSimError,
+    event_module::event::EventKind,
     movement_module::{
         is_movement::IsMovement,
-        movement::{Movement, MovementInputs},
+        movement::{Movement, MovementState},
         pose::Pose,
     },
     statistics::AgentTimestep,
-    task_module::task::Task,
+    task_module::task::{Intent, Task},
     units::{
-        angle::Angle, angular_velocity::AngularVelocity, duration::Duration,
-        linear_velocity::LinearVelocity,
+        angle::Angle, angular_velocity::AngularVelocity, duration::Duration, energy::Energy,
+        length::Length, linear_velocity::LinearVelocity, power::Power,
     },
     utilities::pos2::ExtendedPos2,
 };
@@ -37,28 +42,46 @@ impl std::fmt::Display for AgentId {
 }
 
 /// Represents a mobile agent in the simulation with movement, battery, and task execution capabilities.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Agent {
     pub id: AgentId,
-    pub pose: Pose,
+    pub pose: Pose,           // true pose
+    pub estimated_pose: Pose, // pose the agent believes it has, equals `pose` without odometry noise
     pub movement: Movement,
+    pub movement_state: MovementState, // runtime state of `movement`, e.g. the steering angle
     pub velocity_lin: LinearVelocity,
     pub velocity_ang: AngularVelocity,
+    pub acceleration_lin: f32, // m/s², change of `velocity_lin` during the last step
     pub color: Color32,
     pub spawn_position: Pos2,
+    pub footprint_radius: Length,
+    pub capabilities: Vec<String>,
+    pub yielding: bool, // set by env when the agent has to stop and wait for another agent
+    pub blocked_duration: Duration, // how long an agent standing still has been in the agent's way
+    pub n_collisions: u32,
+    pub n_near_misses: u32,
 
     pub work_schedule: WorkSchedule,
     pub current_task: Option<Task>,
     pub completed_task_ids: Vec<u32>, // for storing so task manager can know
+    pub pending_events: Vec<EventKind>, // for storing so env can collect
 
     pub state: AgentState,
     pub battery: Battery,
+    pub power_model: PowerModel,
+    pub charging_power_limit: Option<Power>, // set by env from the station power budget, unlimited if None
 
     pub timesteps: Vec<AgentTimestep>,
+
+    pub noise_rng: ChaCha12Rng,   // drives odometry noise, seeded by env
+    pub since_gnss_fix: Duration, // travel time since the last GNSS fix
+    pub position_variance: f32,   // variance of the estimated position in m²
+    pub heading_variance: f32,    // variance of the estimated heading in rad²
 }
 
 impl Agent {
     /// Constructs an [`Agent`] from an [`AgentConfig`], setting its initial state, pose, and battery.
+    /// Panics if the movement or battery files can't be loaded.
     pub fn from_config(
         config: AgentConfig,
         id: u32,
@@ -66,31 +89,69 @@ impl Agent {
         direction: Vec2,
         color: Color32,
     ) -> Self {
-        Self {
+        Self::try_from_config(config, id, position, direction, color)
+            .unwrap_or_else(|e| e.log_and_panic())
+    }
+
+    /// Constructs an [`Agent`] from an [`AgentConfig`], setting its initial state, pose, and battery.
+    /// Returns an error if the movement or battery files can't be loaded.
+    pub fn try_from_config(
+        config: AgentConfig,
+        id: u32,
+        position: Pos2,
+        direction: Vec2,
+        color: Color32,
+    ) -> Result<Self, FarmbotSimError> {
+        Ok(Self {
             id: AgentId(id),
             pose: Pose::new(position, Angle::radians(direction.angle())),
-            movement: Movement::from_json_file(config.movement),
+            estimated_pose: Pose::new(position, Angle::radians(direction.angle())),
+            movement: Movement::try_from_json_file(config.movement)?,
+            movement_state: MovementState::default(),
             velocity_lin: LinearVelocity::ZERO,
             velocity_ang: AngularVelocity::ZERO,
+            acceleration_lin: 0.0,
             color,
             spawn_position: position,
+            footprint_radius: config.footprint_radius,
+            capabilities: config.capabilities,
+            yielding: false,
+            blocked_duration: Duration::ZERO,
+            n_collisions: 0,
+            n_near_misses: 0,
 
             work_schedule: WorkSchedule::default(),
             current_task: None,
             completed_task_ids: vec![],
+            pending_events: vec![],
 
             state: AgentState::Wait,
-            battery: Battery::from_config(
-                BatteryConfig::from_json_file(config.battery),
+            battery: Battery::try_from_config(
+                BatteryConfig::try_from_json_file(config.battery)?,
                 config.battery_soc,
-            ),
+            )?,
+            power_model: config.power_model,
+            charging_power_limit: None,
 
             timesteps: vec![],
-        }
+
+            noise_rng: ChaCha12Rng::seed_from_u64(id as u64),
+            since_gnss_fix: Duration::ZERO,
+            position_variance: 0.0,
+            heading_variance: 0.0,
+        })
+    }
+
+    /// Returns true if the agent has every capability the task requires.
+    pub fn is_capable_of(&self, task: &Task) -> bool {
+        task.get_required_capabilities()
+            .iter()
+            .all(|capability| self.capabilities.contains(capability))
     }
 
     /// Updates the agent's state, task, movement, and battery based on simulation time.
     pub fn update(&mut self, simulation_step: Duration, date_time_manager: &DateTimeManager) {
+        self.battery.age(simulation_step);
         if self.state == AgentState::Discharged {
             self.update_timesteps(simulation_step);
             return;
@@ -98,20 +159,210 @@ impl Agent {
         self.update_state(simulation_step, date_time_manager);
 
         self.update_task_and_path(simulation_step);
-        let inputs = self.get_inputs();
-        self._move(simulation_step, inputs);
+        if self.yielding {
+            self.velocity_lin = LinearVelocity::ZERO;
+            self.velocity_ang = AngularVelocity::ZERO;
+            self.acceleration_lin = 0.0;
+        } else {
+            self._move(simulation_step);
+        }
 
         self.update_timesteps(simulation_step);
     }
 
+    /// Returns how many steps the agent stays idle, i.e. neither moves nor changes its task or
+    /// state apart from the battery, capped at `max_steps`. Returns 0 if the agent is not idle.
+    /// `work_available` tells whether the task manager would hand out work to this agent if it were free.
+    pub fn idle_steps(
+        &self,
+        simulation_step: Duration,
+        max_steps: u32,
+        work_available: bool,
+    ) -> u32 {
+        let schedule_empty = self.work_schedule.is_empty();
+        match (&self.state, &self.current_task) {
+            (AgentState::Discharged, None) => max_steps,
+            (
+                AgentState::Charging,
+                Some(Task::WaitInfinite {
+                    intent: Intent::Charge,
+                }),
+            ) if schedule_empty => max_steps,
+            (
+                AgentState::Wait,
+                Some(Task::WaitInfinite {
+                    intent: Intent::Charge | Intent::Queue,
+                }),
+            ) if schedule_empty => max_steps,
+            (AgentState::Wait, None)
+                if schedule_empty
+                    && !work_available
+                    && Length::meters(
+                        self.estimated_pose.position.distance(self.spawn_position),
+                    ) <= TOLERANCE_DISTANCE =>
+            {
+                max_steps
+            }
+            (AgentState::Wait, Some(Task::WaitDuration { duration, intent }))
+                if !(schedule_empty && *intent == Intent::Idle) =>
+            {
+                Self::countdown_steps(*duration, simulation_step, max_steps)
+            }
+            (AgentState::Work, Some(Task::Stationary { duration, .. })) => {
+                let inputs = self
+                    .movement
+                    .calculate_inputs_for_target(&self.estimated_pose, self.get_target_pose());
+                if !inputs.is_zero() {
+                    return 0;
+                }
+                Self::countdown_steps(*duration, simulation_step, max_steps)
+            }
+            _ => 0,
+        }
+    }
+
+    /// Returns the SoC thresholds at which the charging strategy would send this agent charging
+    /// and that have not been crossed yet. Agents that already charge or head to a station have none.
+    pub fn pending_soc_thresholds(&self, soc_thresholds: &[f32]) -> Vec<f32> {
+        let heading_to_station = self.work_schedule.has_charging()
+            || self
+                .current_task
+                .as_ref()
+                .is_some_and(|task| matches!(task.get_intent(), Intent::Charge | Intent::Queue));
+        if heading_to_station || matches!(self.state, AgentState::Charging | AgentState::Discharged)
+        {
+            return vec![];
+        }
+        let soc = self.battery.get_soc();
+        soc_thresholds
+            .iter()
+            .copied()
+            .filter(|threshold| soc >= *threshold)
+            .collect()
+    }
+
+    /// Returns true if the agent follows a path, i.e. travels or works along a row.
+    pub fn is_moving(&self) -> bool {
+        matches!(
+            &self.current_task,
+            Some(Task::Travel { path, .. } | Task::Moving { path, .. }) if !path.is_empty()
+        )
+    }
+
+    /// Returns true if the next target of the agent lies roughly in the direction of `position`.
+    pub fn is_heading_towards(&self, position: Pos2) -> bool {
+        let heading = self.get_target_pose().position - self.estimated_pose.position;
+        let to_position = position - self.pose.position;
+        heading.length() > TOLERANCE_DISTANCE.to_base_unit()
+            && heading.normalized().dot(to_position.normalized()) > 0.5
+    }
+
+    /// Returns true if the straight way to the next target passes closer than `clearance`
+    /// to `position`.
+    pub fn is_heading_through(&self, position: Pos2, clearance: Length) -> bool {
+        let start = self.pose.position;
+        let way = self.get_target_pose().position - self.estimated_pose.position;
+        let t = if way.length_sq() > 0.0 {
+            ((position - start).dot(way) / way.length_sq()).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        way.length() > TOLERANCE_DISTANCE.to_base_unit()
+            && (start + way * t).distance(position) < clearance.to_base_unit()
+    }
+
+    /// Returns true if the agent has to give way to `other`. Agents heading to a station go first,
+    /// then working agents, the rest come last. Ties are broken by the lower ID.
+    pub fn has_lower_priority_than(&self, other: &Agent) -> bool {
+        (self.traffic_priority(), std::cmp::Reverse(self.id))
+            < (other.traffic_priority(), std::cmp::Reverse(other.id))
+    }
+
+    /// Returns the traffic priority class of the agent, higher goes first.
+    fn traffic_priority(&self) -> u32 {
+        match self.current_task.as_ref().map(|task| task.get_intent()) {
+            Some(Intent::Charge | Intent::Queue) => 2,
+            Some(Intent::Work) => 1,
+            _ => 0,
+        }
+    }
+
+    /// Returns true if the battery would be depleted during the next step.
+    pub fn is_depleted_by_step(&self, simulation_step: Duration) -> bool {
+        match self.state {
+            AgentState::Charging | AgentState::Discharged => false,
+            _ => {
+                let power = self.state.power_consumption(self);
+                self.battery.energy_after_discharge(power, simulation_step) <= Energy::ZERO
+            }
+        }
+    }
+
+    /// Returns true if the battery reached the release SoC while charging,
+    /// or dropped below any of the given SoC thresholds.
+    pub fn has_battery_event(&self, pending_soc_thresholds: &[f32]) -> bool {
+        let soc = self.battery.get_soc();
+        match self.state {
+            AgentState::Charging => soc >= 100.0,
+            _ => pending_soc_thresholds
+                .iter()
+                .any(|threshold| soc < *threshold),
+        }
+    }
+
+    /// Advances an idle agent by a single step without recording a timestep.
+    /// Must only be used while `idle_steps` is positive.
+    pub fn skip_step(&mut self, simulation_step: Duration, day_of_year: u32) {
+        self.battery.age(simulation_step);
+        match self.state {
+            AgentState::Discharged => {}
+            AgentState::Charging => {
+                self.battery
+                    .charge_limited(simulation_step, day_of_year, self.charging_power_limit)
+            }
+            _ => {
+                let power = self.state.power_consumption(self);
+                self.battery.discharge(power, simulation_step);
+            }
+        }
+        if let Some(Task::Stationary { duration, .. } | Task::WaitDuration { duration, .. }) =
+            &mut self.current_task
+            && *duration > Duration::ZERO
+        {
+            *duration = *duration - simulation_step;
+        }
+    }
+
+    /// Records skipped steps as a single timestep.
+    pub fn record_skipped_steps(&mut self, duration: Duration) {
+        self.yielding = false;
+        self.blocked_duration = Duration::ZERO;
+        self.velocity_lin = LinearVelocity::ZERO;
+        self.velocity_ang = AngularVelocity::ZERO;
+        self.acceleration_lin = 0.0;
+        self.update_timesteps(duration);
+    }
+
+    /// Returns how many steps a countdown of `duration` keeps running, capped at `max_steps`.
+    fn countdown_steps(mut duration: Duration, simulation_step: Duration, max_steps: u32) -> u32 {
+        let mut steps = 0;
+        while duration > Duration::ZERO && steps < max_steps {
+            duration = duration - simulation_step;
+            steps += 1;
+        }
+        steps
+    }
+
     /// Updates timestep vec with current state
     fn update_timesteps(&mut self, duration: Duration) {
         self.timesteps.push(AgentTimestep {
             duration,
             state: self.state.clone(),
             pose: self.pose.clone(),
+            localization_error: self.localization_error(),
             battery_energy: self.battery.energy,
             task: self.current_task.clone(),
+            yielding: self.yielding,
         });
     }
 
@@ -124,39 +375,131 @@ impl Agent {
         if let Some(mut new_state) = maybe_new_state {
             current_state.on_exit(self);
             new_state.on_enter(self);
+            if new_state == AgentState::Discharged {
+                self.pending_events
+                    .push(EventKind::BatteryDischarged { agent_id: self.id });
+            }
+            self.pending_events.push(EventKind::StateChanged {
+                agent_id: self.id,
+                from: current_state,
+                to: new_state.clone(),
+            });
             self.state = new_state;
         } else {
             self.state = current_state;
         }
     }
 
-    /// Moves the agent by calculating new pose and velocities based on inputs.
-    fn _move(&mut self, simulation_step: Duration, inputs: MovementInputs) {
+    /// Moves the agent toward its next target pose, sub-stepping the movement model if needed.
+    /// Waypoints of a path reached within the step are passed and the rest of the step is
+    /// spent driving toward the next one.
+    fn _move(&mut self, simulation_step: Duration) {
         let current_task_velocity = self
             .current_task
             .as_ref()
             .map(|task| task.get_velocity())
             .unwrap_or(LinearVelocity::ZERO);
-        let (new_pose, new_velocity_l, new_velocity_a) =
-            self.movement.calculate_new_pose_from_inputs(
-                simulation_step,
-                inputs,
-                self.pose.clone(),
+        let along_arc = self
+            .current_task
+            .as_ref()
+            .is_some_and(|task| task.is_smoothed());
+        let start_velocity = self.velocity_lin;
+        let mut moved_distance = 0.0;
+        let mut remaining = simulation_step;
+        loop {
+            let target_pose = self.get_target_pose().clone();
+            let (new_pose, new_velocity_l, new_velocity_a, unused) = self.movement.move_to_target(
+                &mut self.movement_state,
+                remaining,
+                self.estimated_pose.clone(),
+                &target_pose,
                 current_task_velocity,
+                along_arc,
             );
-        self.pose = new_pose;
-        self.velocity_lin = new_velocity_l;
-        self.velocity_ang = new_velocity_a;
+            moved_distance += self.estimated_pose.position.distance(new_pose.position);
+            self.apply_motion(new_pose);
+            self.velocity_lin = new_velocity_l;
+            self.velocity_ang = new_velocity_a;
+            if unused <= Duration::ZERO || !self.pass_reached_waypoint() {
+                break;
+            }
+            remaining = unused;
+        }
+        if let Some(noise) = self.movement.noise().copied() {
+            // Fixes are fused while driving only, a fix taken while turning on the spot
+            // could move the estimate off the target it has just reached
+            if noise.has_gnss() && moved_distance > 0.0 {
+                self.since_gnss_fix = self.since_gnss_fix + simulation_step;
+                if self.since_gnss_fix >= noise.gnss_interval {
+                    self.since_gnss_fix = Duration::ZERO;
+                    self.estimated_pose.position = noise.fuse_gnss_fix(
+                        self.estimated_pose.position,
+                        &mut self.position_variance,
+                        self.pose.position,
+                        &mut self.noise_rng,
+                    );
+                    self.estimated_pose.orientation = noise.fuse_heading_fix(
+                        self.estimated_pose.orientation,
+                        &mut self.heading_variance,
+                        self.pose.orientation,
+                        &mut self.noise_rng,
+                    );
+                }
+            }
+        }
+        self.acceleration_lin =
+            (self.velocity_lin - start_velocity).to_base_unit() / simulation_step.to_base_unit();
     }
 
-    /// Computes movement inputs required to reach the next target in the current task.
-    fn get_inputs(&self) -> MovementInputs {
-        let next_pose = match &self.current_task {
-            Some(task) => task.get_first_pose().unwrap_or(&self.pose),
-            None => &self.pose,
-        };
-        self.movement
-            .calculate_inputs_for_target(&self.pose, next_pose)
+    /// Moves the estimated pose to `new_pose` and the true pose along with it, adding odometry
+    /// noise to the true motion if the movement model has any.
+    fn apply_motion(&mut self, new_pose: Pose) {
+        match self.movement.noise().copied() {
+            Some(noise) => {
+                let distance = self.estimated_pose.position.distance(new_pose.position);
+                self.heading_variance += noise.heading_variance(&self.estimated_pose, &new_pose);
+                self.pose = noise.apply_motion(
+                    &self.pose,
+                    &self.estimated_pose,
+                    &new_pose,
+                    &mut self.noise_rng,
+                );
+                self.estimated_pose = new_pose;
+                self.position_variance += noise.motion_variance(distance);
+            }
+            None => {
+                self.estimated_pose = new_pose.clone();
+                self.pose = new_pose;
+            }
+        }
+    }
+
+    /// Drops the first pose of the current path if it has been reached and another pose follows.
+    /// The last pose is left for `update_task_and_path` to complete the task.
+    /// Returns true if a pose was dropped.
+    fn pass_reached_waypoint(&mut self) -> bool {
+        match &mut self.current_task {
+            Some(Task::Moving { path, .. } | Task::Travel { path, .. })
+                if path.len() > 1 && self.movement.has_reached(&self.estimated_pose, &path[0]) =>
+            {
+                path.pop_front();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the next target pose in the current task, or the estimated pose if there is none.
+    fn get_target_pose(&self) -> &Pose {
+        match &self.current_task {
+            Some(task) => task.get_first_pose().unwrap_or(&self.estimated_pose),
+            None => &self.estimated_pose,
+        }
+    }
+
+    /// Returns the distance between the true and the estimated position.
+    pub fn localization_error(&self) -> Length {
+        Length::meters(self.pose.position.distance(self.estimated_pose.position))
     }
 
     /// Updates the current task and its path based on agent's progress and pose.
@@ -165,7 +508,7 @@ impl Agent {
             match task {
                 Task::Stationary { pose, duration, .. } => {
                     if self
-                        .pose
+                        .estimated_pose
                         .position
                         .is_close_to(pose.position, TOLERANCE_DISTANCE)
                     {
@@ -187,10 +530,7 @@ impl Agent {
                 Task::WaitInfinite { .. } => {}
                 Task::Moving { path, .. } | Task::Travel { path, .. } => {
                     if let Some(front_pose) = path.front() {
-                        if self
-                            .pose
-                            .is_close_to(front_pose, TOLERANCE_DISTANCE, TOLERANCE_ANGLE)
-                        {
+                        if self.movement.has_reached(&self.estimated_pose, front_pose) {
                             path.pop_front();
                         }
                     }
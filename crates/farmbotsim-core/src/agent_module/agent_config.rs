@@ -1,22 +1,52 @@
 use std::path::Path;
 
-use crate::utilities::utils::load_json_or_panic;
+use crate::{
+    agent_module::power_model::PowerModel,
+    error::FarmbotSimError,
+    units::length::Length,
+    utilities::utils::{load_json, load_json_or_panic},
+};
 
-/// Configuration for an agent, including movement type, battery type, and state of charge.
+/// Configuration for an agent, including movement type, battery type, state of charge, footprint,
+/// capabilities and power model.
 #[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
 pub struct AgentConfig {
     pub movement: String,
     pub battery: String,
     pub battery_soc: f32,
+    /// Radius of the circle the agent occupies.
+    #[serde(default = "default_footprint_radius")]
+    pub footprint_radius: Length,
+    /// Tools the agent carries, e.g. "seeder", required by some farm entity actions.
+    #[serde(default)]
+    pub capabilities: Vec<String>,
+    /// Physical properties that determine the power drawn from the battery.
+    #[serde(default)]
+    pub power_model: PowerModel,
+}
+
+/// Returns the footprint radius of the default agent.
+fn default_footprint_radius() -> Length {
+    Length::meters(0.15)
 }
 
 impl AgentConfig {
     /// Creates a new agent configuration.
-    pub fn new(movement: String, battery: String, battery_soc: f32) -> Self {
+    pub fn new(
+        movement: String,
+        battery: String,
+        battery_soc: f32,
+        footprint_radius: Length,
+        capabilities: Vec<String>,
+        power_model: PowerModel,
+    ) -> Self {
         Self {
             movement,
             battery,
             battery_soc,
+            footprint_radius,
+            capabilities,
+            power_model,
         }
     }
 
@@ -24,4 +54,9 @@ impl AgentConfig {
     pub fn from_json_file<P: AsRef<Path>>(file_path: P) -> Self {
         load_json_or_panic(file_path)
     }
+
+    /// Loads an agent configuration from a JSON file.
+    pub fn try_from_json_file<P: AsRef<Path>>(file_path: P) -> Result<Self, FarmbotSimError> {
+        load_json(file_path)
+    }
 }
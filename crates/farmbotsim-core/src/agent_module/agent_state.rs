@@ -1,16 +1,15 @@
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     agent_module::agent::Agent,
     battery_module::is_battery::IsBattery,
-    cfg::{POWER_CONSUMPTION_TRAVEL, POWER_CONSUMPTION_WAIT},
     environment::datetime::DateTimeManager,
     task_module::task::Task,
     units::{duration::Duration, power::Power},
 };
 
 /// Represents states an agent can be in during simulation.
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum AgentState {
     Wait,
     Travel,
@@ -27,8 +26,7 @@ impl AgentState {
             AgentState::Travel => {}
             AgentState::Work => {}
             AgentState::Charging => {
-                agent.battery.start_index =
-                    HashMap::from([("jan".to_string(), 1), ("jun".to_string(), 1)]);
+                agent.battery.reset_curve_search();
             }
             AgentState::Discharged => {}
         }
@@ -45,9 +43,8 @@ impl AgentState {
         match self {
             AgentState::Wait => {
                 // discharge battery
-                agent
-                    .battery
-                    .discharge(POWER_CONSUMPTION_WAIT, simulation_step);
+                let power = self.power_consumption(agent);
+                agent.battery.discharge(power, simulation_step);
                 // check battery
                 if let Some(discharge) = Self::check_battery(agent) {
                     return Some(discharge);
@@ -67,7 +64,7 @@ impl AgentState {
             }
             AgentState::Travel => {
                 // discharge battery
-                let power = Self::calculate_power_travel(agent);
+                let power = self.power_consumption(agent);
                 agent.battery.discharge(power, simulation_step);
                 // check battery
                 if let Some(discharge) = Self::check_battery(agent) {
@@ -92,12 +89,7 @@ impl AgentState {
             }
             AgentState::Work => {
                 // discharge battery
-                let power = match &agent.current_task {
-                    Some(task) => Self::calculate_power_work(agent, task),
-                    None => {
-                        Power::ZERO // when task is complete and removed but state is still Work instead of other
-                    }
-                };
+                let power = self.power_consumption(agent);
                 agent.battery.discharge(power, simulation_step);
                 // check battery
                 if let Some(discharge) = Self::check_battery(agent) {
@@ -116,9 +108,11 @@ impl AgentState {
             }
             AgentState::Charging => {
                 // charge battery
-                agent
-                    .battery
-                    .charge(simulation_step, date_time_manager.get_month());
+                agent.battery.charge_limited(
+                    simulation_step,
+                    date_time_manager.get_day_of_year(),
+                    agent.charging_power_limit,
+                );
                 // transitions
                 if let Some(task) = &agent.current_task {
                     if !task.is_wait() && !task.is_charge_intent() {
@@ -135,16 +129,33 @@ impl AgentState {
     }
 
     /// Called when exiting this state.
-    pub fn on_exit(&mut self, _agent: &mut Agent) {
+    pub fn on_exit(&mut self, agent: &mut Agent) {
         match self {
             AgentState::Wait => {}
             AgentState::Travel => {}
             AgentState::Work => {}
-            AgentState::Charging => {}
+            AgentState::Charging => {
+                agent.charging_power_limit = None;
+            }
             AgentState::Discharged => {}
         }
     }
 
+    /// Returns the power the agent draws from its battery in this state.
+    pub fn power_consumption(&self, agent: &Agent) -> Power {
+        match self {
+            AgentState::Wait => agent.power_model.base_load,
+            AgentState::Travel => Self::calculate_power_travel(agent),
+            AgentState::Work => match &agent.current_task {
+                Some(task) => Self::calculate_power_work(agent, task),
+                None => {
+                    Power::ZERO // when task is complete and removed but state is still Work instead of other
+                }
+            },
+            AgentState::Charging | AgentState::Discharged => Power::ZERO,
+        }
+    }
+
     // Checks if the battery is depleted, triggering Discharged state
     fn check_battery(agent: &Agent) -> Option<AgentState> {
         if agent.battery.get_soc() <= 0.0 {
@@ -152,14 +163,19 @@ impl AgentState {
         }
         None
     }
-    // Power consumption when traveling, scaled by velocity ratio
+    // Power consumption when traveling, from the power model of the agent
     fn calculate_power_travel(agent: &Agent) -> Power {
-        POWER_CONSUMPTION_TRAVEL * (agent.velocity_lin / agent.movement.max_velocity())
+        agent.power_model.drive_power(
+            agent.velocity_lin,
+            agent.velocity_ang,
+            agent.acceleration_lin,
+            agent.movement.turning_lever_arm(),
+        )
     }
     // Power consumption while working, depends on task type
     fn calculate_power_work(agent: &Agent, task: &Task) -> Power {
         match task {
-            Task::Stationary { power, .. } => *power,
+            Task::Stationary { power, .. } => *power + agent.power_model.base_load,
             Task::Moving { power, .. } => *power + Self::calculate_power_travel(agent),
             _ => Power::ZERO,
         }
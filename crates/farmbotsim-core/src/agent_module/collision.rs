@@ -0,0 +1,113 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    agent_module::agent::{Agent, AgentId},
+    cfg::{MAX_BLOCKED_DURATION, NEAR_MISS_DISTANCE, YIELD_DISTANCE},
+    units::length::Length,
+};
+
+/// Behaviour of agents that come too close to each other.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CollisionAvoidance {
+    /// Contacts are only detected, agents drive through each other.
+    #[default]
+    None,
+    /// An agent stops and waits while it heads towards a close moving agent with higher priority.
+    /// It also waits up to `MAX_BLOCKED_DURATION` for a close agent standing still in its way,
+    /// e.g. working, charging or queuing, before passing it.
+    YieldByPriority,
+}
+
+/// Kind of contact between two agents, ordered by severity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum ContactKind {
+    /// Footprints are closer than `NEAR_MISS_DISTANCE` but don't touch.
+    NearMiss,
+    /// Footprints overlap.
+    Collision,
+}
+
+/// Contact between two agents, the lower agent ID comes first.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Contact {
+    pub agent_ids: (AgentId, AgentId),
+    pub kind: ContactKind,
+}
+
+/// Returns contacts between all pairs of agents, ordered by agent IDs.
+pub fn detect_contacts(agents: &[Agent]) -> Vec<Contact> {
+    let mut contacts = vec![];
+    for (i, a) in agents.iter().enumerate() {
+        for b in &agents[i + 1..] {
+            let gap = gap_between(a, b);
+            let kind = if gap < Length::ZERO {
+                ContactKind::Collision
+            } else if gap < NEAR_MISS_DISTANCE {
+                ContactKind::NearMiss
+            } else {
+                continue;
+            };
+            let agent_ids = if a.id < b.id {
+                (a.id, b.id)
+            } else {
+                (b.id, a.id)
+            };
+            contacts.push(Contact { agent_ids, kind });
+        }
+    }
+    contacts.sort_by_key(|contact| contact.agent_ids);
+    contacts
+}
+
+/// Returns for each agent whether it has to stop and wait during the next step.
+pub fn yielding_agents(agents: &[Agent], avoidance: CollisionAvoidance) -> Vec<bool> {
+    match avoidance {
+        CollisionAvoidance::None => vec![false; agents.len()],
+        CollisionAvoidance::YieldByPriority => agents
+            .iter()
+            .map(|agent| {
+                let gives_way = agents.iter().any(|other| {
+                    other.id != agent.id
+                        && other.is_moving()
+                        && agent.has_lower_priority_than(other)
+                        && gap_between(agent, other) < YIELD_DISTANCE
+                        && agent.is_heading_towards(other.pose.position)
+                });
+                gives_way
+                    || (agent.blocked_duration < MAX_BLOCKED_DURATION && is_blocked(agent, agents))
+            })
+            .collect(),
+    }
+}
+
+/// Returns for each agent whether a close agent standing still is in its way.
+pub fn blocked_agents(agents: &[Agent], avoidance: CollisionAvoidance) -> Vec<bool> {
+    match avoidance {
+        CollisionAvoidance::None => vec![false; agents.len()],
+        CollisionAvoidance::YieldByPriority => agents
+            .iter()
+            .map(|agent| is_blocked(agent, agents))
+            .collect(),
+    }
+}
+
+/// Returns true if the agent moves and a close agent standing still is in its way.
+fn is_blocked(agent: &Agent, agents: &[Agent]) -> bool {
+    agent.is_moving()
+        && agents.iter().any(|other| {
+            other.id != agent.id
+                && !other.is_moving()
+                && gap_between(agent, other) < YIELD_DISTANCE
+                && agent.is_heading_through(
+                    other.pose.position,
+                    agent.footprint_radius + other.footprint_radius,
+                )
+        })
+}
+
+/// Returns the distance between the footprints of two agents, negative if they overlap.
+fn gap_between(a: &Agent, b: &Agent) -> Length {
+    Length::meters(a.pose.position.distance(b.pose.position))
+        - a.footprint_radius
+        - b.footprint_radius
+}
@@ -6,4 +6,6 @@
 pub mod agent;
 pub mod agent_config;
 pub mod agent_state;
+pub mod collision;
+pub mod power_model;
 pub mod work_schedule;
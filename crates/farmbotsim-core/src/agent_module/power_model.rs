@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cfg::GRAVITY,
+    units::{
+        angular_velocity::AngularVelocity, length::Length, linear_velocity::LinearVelocity,
+        mass::Mass, power::Power,
+    },
+};
+
+/// Physical properties that determine how much power an agent draws from its battery.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PowerModel {
+    /// Mass of the agent including its load.
+    pub mass: Mass,
+    /// Rolling resistance coefficient of the wheels on the ground.
+    pub rolling_resistance: f32,
+    /// Fraction of the electric power the motors and gears turn into motion, in 0..=1.
+    pub drivetrain_efficiency: f32,
+    /// Friction coefficient of the wheels scrubbing sideways while the agent turns.
+    pub turning_resistance: f32,
+    /// Power drawn by electronics and sensors whenever the agent is not charging.
+    pub base_load: Power,
+}
+impl Default for PowerModel {
+    /// Returns a power model that draws about 700 W at 3 km/h on soft ground.
+    fn default() -> Self {
+        Self {
+            mass: Mass::kilograms(300.0),
+            rolling_resistance: 0.25,
+            drivetrain_efficiency: 0.85,
+            turning_resistance: 0.5,
+            base_load: Power::watts(10.0),
+        }
+    }
+}
+
+impl PowerModel {
+    /// Returns the power needed to drive at `velocity` while accelerating by `acceleration` in m/s²
+    /// and turning at `angular_velocity`, including the base load. `lever_arm` is the distance of
+    /// the wheels from the center of the agent. Energy of braking is not recovered.
+    pub fn drive_power(
+        &self,
+        velocity: LinearVelocity,
+        angular_velocity: AngularVelocity,
+        acceleration: f32,
+        lever_arm: Length,
+    ) -> Power {
+        let mass = self.mass.to_base_unit();
+        let weight = mass * GRAVITY;
+        let speed = velocity.to_base_unit().abs();
+
+        let rolling = self.rolling_resistance * weight * speed;
+        let accelerating = (mass * acceleration * speed).max(0.0);
+        let turning = self.turning_resistance
+            * weight
+            * angular_velocity.to_base_unit().abs()
+            * lever_arm.to_base_unit();
+
+        Power::watts((rolling + accelerating + turning) / self.drivetrain_efficiency)
+            + self.base_load
+    }
+}
@@ -1,9 +1,10 @@
+use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 
 use crate::task_module::task::{Intent, Task};
 
 /// Represents a schedule of tasks assigned to an agent.
-#[derive(Clone, PartialEq, Debug, Default)]
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
 pub struct WorkSchedule {
     /// Queue of tasks to be performed.
     pub tasks: VecDeque<Task>,
@@ -1,31 +1,33 @@
-use std::{
-    collections::{HashMap, VecDeque},
-    fs::File,
-    io::{BufRead, BufReader},
-    path::Path,
-};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 
 use crate::{
     battery_module::{
-        battery_config::BatteryConfig, battery_error::BatteryError, is_battery::IsBattery,
+        battery_ageing::BatteryHealth,
+        battery_config::BatteryConfig,
+        battery_error::BatteryError,
+        battery_model::BatteryModel,
+        charging_curve::{ChargingCurve, nearest_curves},
+        is_battery::IsBattery,
     },
     cfg::BATTERIES_PATH,
-    logger::log_error_and_panic,
+    error::FarmbotSimError,
     units::{duration::Duration, energy::Energy, power::Power, voltage::Voltage},
-    utilities::utils::linear_interpolate,
 };
 
-/// Represents a rechargeable battery with energy capacity, voltage, and seasonal data models.
-#[derive(Clone, Debug, PartialEq)]
+/// Represents a rechargeable battery with energy capacity, voltage, a charging and an optional ageing model.
+/// With ageing, `capacity` is the effective capacity that shrinks as the battery wears.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Battery {
     pub voltage: Voltage,
     pub capacity: Energy,
     pub soc: f32,
     pub energy: Energy,
-    pub jan_max_data: Vec<(u32, f32)>,
-    pub jan_min_data: Vec<(u32, f32)>,
-    pub jun_max_data: Vec<(u32, f32)>,
-    pub start_index: HashMap<String, usize>,
+    pub model: BatteryModel,
+    /// Measured curves of the `CurveMorph` model sorted by day, empty for other models.
+    pub curves: Vec<ChargingCurve>,
+    /// Wear of the battery, `None` if it doesn't age.
+    pub health: Option<BatteryHealth>,
 
     update_count: u32,
     pub soc_history: VecDeque<f32>,
@@ -37,39 +39,27 @@ impl IsBattery for Battery {
         if self.energy <= Energy::ZERO {
             return;
         } // is empty
-        let energy_removed = power * duration;
-        let new_energy = self.energy - energy_removed;
-        if new_energy < Energy::ZERO {
-            self.energy = Energy::ZERO;
-        } else {
-            self.energy = new_energy;
+        let new_energy = self.energy_after_discharge(power, duration);
+        if let Some(health) = &mut self.health {
+            health.on_discharge(self.energy - new_energy);
         }
+        self.energy = new_energy;
+        self.apply_health();
         self.soc = (self.energy / self.capacity) * 100.0; // Update SoC
 
         self.update();
     }
 
-    /// Increases battery energy using solar charge interpolation curves.
-    fn charge(&mut self, duration: Duration, month: u32) {
+    /// Increases battery energy according to the charging model.
+    fn charge(&mut self, duration: Duration, day_of_year: u32) {
         if self.energy >= self.capacity {
             return; // Battery is full
         }
-
-        match self.get_morph_x_y(
-            self.energy.to_watt_hour(),
-            month,
-            duration.to_base_unit() as u32,
-        ) {
-            Ok((_, new_energy)) => {
-                let new_energy = Energy::watt_hours(new_energy);
-                if new_energy < self.capacity {
-                    self.energy = new_energy;
-                } else {
-                    self.energy = self.capacity;
-                }
-                self.soc = (self.energy / self.capacity) * 100.0; // Update SoC
-
-                self.update();
+        let start_energy = self.energy;
+        match self.energy_after_charge(duration, day_of_year) {
+            Ok(new_energy) => {
+                self.energy = new_energy;
+                self.on_charged(start_energy);
             }
             Err(e) => {
                 println!("⚠️ Failed to charge: {e}");
@@ -90,183 +80,227 @@ impl IsBattery for Battery {
 
 impl Battery {
     /// Creates a battery from configuration and an initial SoC.
+    /// Panics if a charging data file can't be read.
     pub fn from_config(config: BatteryConfig, initial_soc: f32) -> Self {
+        Self::try_from_config(config, initial_soc).unwrap_or_else(|e| e.log_and_panic())
+    }
+
+    /// Creates a battery from configuration and an initial SoC.
+    /// Returns an error if a charging data file can't be read or has too few data points,
+    /// or if a curve date or a parameter of the charging or ageing model is invalid.
+    pub fn try_from_config(
+        config: BatteryConfig,
+        initial_soc: f32,
+    ) -> Result<Self, FarmbotSimError> {
         let soc = initial_soc.clamp(0.0, 100.0);
         let path = format!("{}/{}/", BATTERIES_PATH, config.name);
-        Self {
+        let curves = match &config.model {
+            BatteryModel::CurveMorph(model) => {
+                model
+                    .validate()
+                    .map_err(|e| e.in_file(format!("{path}config.json")))?;
+                let mut curves = model
+                    .curves
+                    .iter()
+                    .map(|curve| {
+                        let day_of_year = curve.try_day_of_year()?;
+                        ChargingCurve::try_from_file(day_of_year, format!("{}{}", path, curve.file))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                curves.sort_by_key(|curve| curve.day_of_year);
+                curves
+            }
+            BatteryModel::CcCv(model) => {
+                model
+                    .validate()
+                    .map_err(|e| e.in_file(format!("{path}config.json")))?;
+                vec![]
+            }
+        };
+        let health = match config.ageing {
+            Some(ageing) => {
+                ageing
+                    .validate()
+                    .map_err(|e| e.in_file(format!("{path}config.json")))?;
+                let mut health = BatteryHealth::new(ageing, config.capacity);
+                health.depth = 1.0 - soc / 100.0;
+                Some(health)
+            }
+            None => None,
+        };
+        Ok(Self {
             voltage: config.voltage,
             capacity: config.capacity,
             soc,
             energy: (soc / 100.0) * config.capacity,
-            jan_max_data: Self::get_month_data_points(format!("{}{}", path, config.jan_max)),
-            jan_min_data: Self::get_month_data_points(format!("{}{}", path, config.jan_min)),
-            jun_max_data: Self::get_month_data_points(format!("{}{}", path, config.jun_max)),
-            start_index: [("jan".to_string(), 1), ("jun".to_string(), 1)]
-                .iter()
-                .cloned()
-                .collect(),
+            model: config.model,
+            curves,
+            health,
 
             update_count: 0,
             soc_history: VecDeque::from(vec![soc; 100]),
-        }
+        })
     }
 
-    /// Parses charging data points from a whitespace-delimited file.
-    fn get_month_data_points<P: AsRef<Path>>(file_path: P) -> Vec<(u32, f32)> {
-        let path_ref = file_path.as_ref();
-        let file = File::open(path_ref).unwrap_or_else(|e| {
-            let msg = format!("Failed to open file {path_ref:?}: {e}");
-            log_error_and_panic(&msg)
-        });
-        let reader = BufReader::new(file);
-        let mut points = Vec::new();
-
-        for line in reader.lines().skip(1).map_while(Result::ok) {
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            if let (Some(t), Some(v)) = (parts.first(), parts.last()) {
-                if let (Ok(t), Ok(v)) = (t.parse::<f32>(), v.parse::<f32>()) {
-                    // Time (s) - Energy (Wh)
-                    points.push(((t * 3600.0) as u32, v));
-                }
-            }
-        }
-        points
-    }
-
-    /// Periodically stores the latest SoC in the history.
-    fn update(&mut self) {
-        self.update_count += 1;
-        if self.update_count % 300 == 0 {
-            self.soc_history.pop_front();
-            self.soc_history.push_back(self.soc);
+    /// Returns the energy left after discharging with given power for given duration.
+    pub fn energy_after_discharge(&self, power: Power, duration: Duration) -> Energy {
+        let energy_removed = power * duration;
+        let new_energy = self.energy - energy_removed;
+        if new_energy < Energy::ZERO {
+            Energy::ZERO
+        } else {
+            new_energy
         }
     }
 
-    /// Finds interpolated energy output for a given input time in the month’s dataset.
-    fn find_y_for_x_month(&mut self, month: &str, x: u32) -> Result<f32, BatteryError> {
-        let data = match month {
-            "jan" => &self.jan_min_data,
-            "jun" => &self.jun_max_data,
-            _ => return Err(BatteryError::UnsupportedMonth(month.to_string())),
+    /// Returns the energy after charging for `duration`, capped at the capacity, without charging.
+    /// Needs `&mut self` to keep the search position in the charging curves.
+    pub fn energy_after_charge(
+        &mut self,
+        duration: Duration,
+        day_of_year: u32,
+    ) -> Result<Energy, BatteryError> {
+        // A worn battery takes longer to charge
+        let duration = match &self.health {
+            Some(health) => duration / health.charge_time_factor(),
+            None => duration,
         };
+        let new_energy = match &self.model {
+            BatteryModel::CcCv(model) => {
+                model.energy_after_charge(self.energy, self.capacity, self.voltage, duration)
+            }
+            BatteryModel::CurveMorph(_) => {
+                let (_, new_energy) = self.get_morph_x_y(
+                    self.energy.to_watt_hour(),
+                    day_of_year,
+                    duration.to_base_unit(),
+                )?;
+                Energy::watt_hours(new_energy)
+            }
+        };
+        Ok(new_energy.min(self.capacity))
+    }
 
-        if data.len() < 2 {
-            return Err(BatteryError::NoYForX(format!("No data for {month}")));
-        }
-
-        let first = data
-            .first()
-            .ok_or(BatteryError::NoYForX(format!("No data for {month}")))?;
-        let last = data
-            .last()
-            .ok_or(BatteryError::NoYForX(format!("No data for {month}")))?;
-
-        // Clamp x within range
-        if x <= first.0 {
-            return Ok(first.1);
-        } else if x >= last.0 {
-            return Ok(last.1);
+    /// Returns the mean power the battery would take when charging for `duration`.
+    pub fn charge_power(&mut self, duration: Duration, day_of_year: u32) -> Power {
+        if self.energy >= self.capacity || duration <= Duration::ZERO {
+            return Power::ZERO;
         }
-
-        // Safe starting index
-        let mut start = *self.start_index.get(month).unwrap_or(&1);
-        start = start.max(1).min(data.len() - 1);
-
-        // Reset if x goes backward
-        if let Some(&prev_i) = self.start_index.get(month) {
-            if x < data[prev_i.saturating_sub(1)].0 {
-                start = 1;
+        match self.energy_after_charge(duration, day_of_year) {
+            Ok(new_energy) => {
+                Power::watts((new_energy - self.energy).to_base_unit() / duration.to_base_unit())
             }
+            Err(_) => Power::ZERO,
         }
+    }
 
-        for i in start..data.len() {
-            let (x0, y0) = data[i - 1];
-            let (x1, y1) = data[i];
-            if x0 <= x && x <= x1 {
-                self.start_index
-                    .insert(month.to_string(), std::cmp::max(1, i - 1));
-                return Ok(linear_interpolate(x0 as f32, y0, x1 as f32, y1, x as f32));
+    /// Charges for `duration`, drawing at most `max_power` if given.
+    /// A lower power stretches the charging curve, so the battery gains what it would in a shorter time.
+    pub fn charge_limited(
+        &mut self,
+        duration: Duration,
+        day_of_year: u32,
+        max_power: Option<Power>,
+    ) {
+        let duration = match max_power {
+            Some(max_power) => {
+                let power = self.charge_power(duration, day_of_year);
+                if power > max_power {
+                    duration * (max_power / power)
+                } else {
+                    duration
+                }
             }
-        }
+            None => duration,
+        };
+        self.charge(duration, day_of_year);
+    }
 
-        Err(BatteryError::NoYForX(x.to_string()))
+    /// Returns the state of health in percent, 100 for a battery that doesn't age.
+    pub fn state_of_health(&self) -> f32 {
+        self.health.as_ref().map_or(100.0, BatteryHealth::soh)
     }
 
-    /// Finds interpolated time needed to reach a given energy in the month’s dataset.
-    fn find_x_for_y_month(&mut self, month: &str, y: f32) -> Result<u32, BatteryError> {
-        let data = match month {
-            "jan" => &self.jan_min_data,
-            "jun" => &self.jun_max_data,
-            _ => return Err(BatteryError::UnsupportedMonth(month.to_string())),
-        };
+    /// Returns the charge and discharge throughput in full cycles, 0 for a battery that doesn't age.
+    pub fn equivalent_full_cycles(&self) -> f32 {
+        self.health
+            .as_ref()
+            .map_or(0.0, BatteryHealth::equivalent_full_cycles)
+    }
 
-        if data.len() < 2 {
-            return Err(BatteryError::NoXForY(format!("No data for {month}")));
+    /// Adds calendar ageing for the elapsed `duration`.
+    pub fn age(&mut self, duration: Duration) {
+        if let Some(health) = &mut self.health {
+            health.age(duration);
+            self.apply_health();
+            self.soc = (self.energy / self.capacity) * 100.0;
         }
+    }
 
-        let first = data
-            .first()
-            .ok_or(BatteryError::NoXForY(format!("No data for {month}")))?;
-        let last = data
-            .last()
-            .ok_or(BatteryError::NoXForY(format!("No data for {month}")))?;
+    /// Replaces the wear with the one of a previously used battery of the same config,
+    /// keeping the state of charge.
+    pub fn restore_health(&mut self, mut health: BatteryHealth) {
+        let soc = self.get_soc();
+        self.capacity = health.effective_capacity();
+        self.energy = self.capacity * soc / 100.0;
+        self.soc = soc;
+        health.depth = 1.0 - self.energy / health.nominal_capacity;
+        self.health = Some(health);
+    }
 
-        // Clamp y within dataset range
-        if y <= first.1 {
-            return Ok(first.0);
-        } else if y >= last.1 {
-            return Ok(last.0);
+    /// Shrinks the capacity to the effective one of the current state of health.
+    fn apply_health(&mut self) {
+        if let Some(health) = &self.health {
+            self.capacity = health.effective_capacity();
+            self.energy = self.energy.min(self.capacity);
         }
+    }
 
-        // Safe starting index
-        let mut start = *self.start_index.get(month).unwrap_or(&1);
-        start = start.max(1).min(data.len() - 1);
-
-        // Reset if y goes backward
-        if let Some(&prev_i) = self.start_index.get(month) {
-            if y < data[prev_i.saturating_sub(1)].1 {
-                start = 1;
-            }
+    /// Records the energy charged since `start_energy` and updates the SoC.
+    fn on_charged(&mut self, start_energy: Energy) {
+        if let Some(health) = &mut self.health {
+            health.on_charge(self.energy - start_energy);
         }
+        self.soc = (self.energy / self.capacity) * 100.0; // Update SoC
+        self.update();
+    }
 
-        for i in start..data.len() {
-            let (x0, y0) = data[i - 1];
-            let (x1, y1) = data[i];
-            if (y0 <= y && y <= y1) || (y1 <= y && y <= y0) {
-                self.start_index
-                    .insert(month.to_string(), std::cmp::max(1, i - 1));
-                let x = linear_interpolate(y0, x0 as f32, y1, x1 as f32, y);
-                return Ok(x.round() as u32);
-            }
+    /// Periodically stores the latest SoC in the history.
+    fn update(&mut self) {
+        self.update_count += 1;
+        if self.update_count % 300 == 0 {
+            self.soc_history.pop_front();
+            self.soc_history.push_back(self.soc);
         }
+    }
 
-        Err(BatteryError::NoXForY(y.to_string()))
+    /// Restarts the interpolation search of all charging curves.
+    pub fn reset_curve_search(&mut self) {
+        for curve in &mut self.curves {
+            curve.reset_search();
+        }
     }
 
-    /// Calculates morphing time and energy for seasonal solar models.
-    /// Uses cosine-based weighting between January and June datasets.
+    /// Calculates time and energy after charging for `time` seconds from energy `y`.
+    /// Interpolates linearly between the two curves nearest to `day_of_year`.
     pub fn get_morph_x_y(
         &mut self,
         y: f32,
-        month: u32,
-        time: u32,
-    ) -> Result<(u32, f32), BatteryError> {
-        let jan_time = self.find_x_for_y_month("jan", y)?;
-        let jun_time = self.find_x_for_y_month("jun", y)?;
-
-        let jan_next_time = jan_time + time;
-        let jun_next_time = jun_time + time;
-
-        let jan_new_wh = self.find_y_for_x_month("jan", jan_next_time)?;
-        let jun_new_wh = self.find_y_for_x_month("jun", jun_next_time)?;
-
-        let month_f = month as f32;
-        let weight1 = (1.0 + (std::f32::consts::PI * (month_f - 1.0) / 6.0).cos()) / 2.0;
-        let weight2 = 1.0 - weight1;
+        day_of_year: u32,
+        time: f32,
+    ) -> Result<(f32, f32), BatteryError> {
+        let (previous, next, weight) =
+            nearest_curves(&self.curves, day_of_year).ok_or(BatteryError::NoCurves)?;
+
+        let (previous_time, previous_wh) = self.curves[previous].next_x_y(y, time)?;
+        if previous == next {
+            return Ok((previous_time, previous_wh));
+        }
+        let (next_time, next_wh) = self.curves[next].next_x_y(y, time)?;
 
-        let new_time = weight1 * jan_next_time as f32 + weight2 * jun_next_time as f32;
-        let new_wh = weight1 * jan_new_wh + weight2 * jun_new_wh;
+        let new_time = (1.0 - weight) * previous_time + weight * next_time;
+        let new_wh = (1.0 - weight) * previous_wh + weight * next_wh;
 
-        Ok((new_time.round() as u32, new_wh))
+        Ok((new_time, new_wh))
     }
 }
@@ -0,0 +1,146 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::FarmbotSimError,
+    units::{duration::Duration, energy::Energy},
+};
+
+/// Seconds in an average year, used for calendar ageing.
+const SECONDS_PER_YEAR: f64 = 365.25 * 24.0 * 3600.0;
+
+/// Parameters of capacity fade from cycling and calendar ageing.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AgeingModel {
+    /// Number of full cycles (100 % depth of discharge) until the state of health drops to `end_of_life_soh`.
+    pub cycle_life: f32,
+    /// State of health in percent at the end of the cycle life.
+    pub end_of_life_soh: f32,
+    /// Exponent of the depth of discharge stress. 1.0 counts only the energy throughput,
+    /// larger values make deep discharges wear the battery more than shallow ones.
+    pub dod_exponent: f32,
+    /// Capacity lost per year in percent of the nominal capacity, regardless of use.
+    pub calendar_fade: f32,
+    /// Relative increase of the charging time per lost state of health,
+    /// e.g. 1.0 charges 20 % slower at 80 % state of health.
+    pub resistance_growth: f32,
+}
+impl Default for AgeingModel {
+    /// Returns typical lithium-ion ageing parameters.
+    fn default() -> Self {
+        Self {
+            cycle_life: 2000.0,
+            end_of_life_soh: 80.0,
+            dod_exponent: 1.5,
+            calendar_fade: 2.0,
+            resistance_growth: 1.0,
+        }
+    }
+}
+
+impl AgeingModel {
+    /// Returns an error if a parameter is out of range.
+    pub fn validate(&self) -> Result<(), FarmbotSimError> {
+        if self.cycle_life <= 0.0 {
+            let msg = format!("{} must be positive", self.cycle_life);
+            return Err(FarmbotSimError::invalid_field("ageing.cycle_life", msg));
+        }
+        if !(0.0..100.0).contains(&self.end_of_life_soh) {
+            let msg = format!("{} is outside 0..100 %", self.end_of_life_soh);
+            return Err(FarmbotSimError::invalid_field(
+                "ageing.end_of_life_soh",
+                msg,
+            ));
+        }
+        if self.dod_exponent < 1.0 {
+            let msg = format!("{} must be at least 1.0", self.dod_exponent);
+            return Err(FarmbotSimError::invalid_field("ageing.dod_exponent", msg));
+        }
+        if self.calendar_fade < 0.0 {
+            let msg = format!("{} must not be negative", self.calendar_fade);
+            return Err(FarmbotSimError::invalid_field("ageing.calendar_fade", msg));
+        }
+        if self.resistance_growth < 0.0 {
+            let msg = format!("{} must not be negative", self.resistance_growth);
+            return Err(FarmbotSimError::invalid_field(
+                "ageing.resistance_growth",
+                msg,
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Ageing state of a battery, tracked from when it was new.
+/// Losses and throughput add up in f64, the wear of a single step is lost in f32 after years of use.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatteryHealth {
+    pub model: AgeingModel,
+    pub nominal_capacity: Energy,
+    /// Capacity lost from cycling in percent of the nominal capacity.
+    pub cycle_loss: f64,
+    /// Capacity lost from calendar ageing in percent of the nominal capacity.
+    pub calendar_loss: f64,
+    /// Energy charged and discharged in total, in J.
+    pub throughput: f64,
+    /// Depth below full charge as a fraction of the nominal capacity.
+    pub depth: f32,
+}
+
+impl BatteryHealth {
+    /// Creates the health of a new battery.
+    pub fn new(model: AgeingModel, nominal_capacity: Energy) -> Self {
+        Self {
+            model,
+            nominal_capacity,
+            cycle_loss: 0.0,
+            calendar_loss: 0.0,
+            throughput: 0.0,
+            depth: 0.0,
+        }
+    }
+
+    /// Returns the state of health in percent, the usable capacity relative to the nominal one.
+    pub fn soh(&self) -> f32 {
+        (100.0 - self.cycle_loss - self.calendar_loss).max(0.0) as f32
+    }
+
+    /// Returns the usable capacity.
+    pub fn effective_capacity(&self) -> Energy {
+        self.nominal_capacity * self.soh() / 100.0
+    }
+
+    /// Returns the throughput in full charge and discharge cycles of the nominal capacity.
+    pub fn equivalent_full_cycles(&self) -> f32 {
+        (self.throughput / (2.0 * f64::from(self.nominal_capacity.to_base_unit()))) as f32
+    }
+
+    /// Returns how many times longer charging takes than for a new battery.
+    pub fn charge_time_factor(&self) -> f32 {
+        1.0 + self.model.resistance_growth * (1.0 - self.soh() / 100.0)
+    }
+
+    /// Adds the wear of discharging `energy`.
+    /// A full discharge from full charge wears one cycle, the stress grows with the depth
+    /// below full charge as `depth^dod_exponent`.
+    pub fn on_discharge(&mut self, energy: Energy) {
+        let start_depth = self.depth;
+        self.depth = (self.depth + energy / self.nominal_capacity).min(1.0);
+        let exponent = self.model.dod_exponent;
+        let cycles = f64::from(self.depth.powf(exponent) - start_depth.powf(exponent));
+        let loss_per_cycle = (100.0 - self.model.end_of_life_soh) / self.model.cycle_life;
+        self.cycle_loss += cycles * f64::from(loss_per_cycle);
+        self.throughput += f64::from(energy.to_base_unit());
+    }
+
+    /// Reduces the depth of discharge by the charged `energy`.
+    pub fn on_charge(&mut self, energy: Energy) {
+        self.depth = (self.depth - energy / self.nominal_capacity).max(0.0);
+        self.throughput += f64::from(energy.to_base_unit());
+    }
+
+    /// Adds calendar ageing for the elapsed `duration`.
+    pub fn age(&mut self, duration: Duration) {
+        let years = f64::from(duration.to_base_unit()) / SECONDS_PER_YEAR;
+        self.calendar_loss += f64::from(self.model.calendar_fade) * years;
+    }
+}
@@ -1,18 +1,59 @@
 use crate::{
+    battery_module::{
+        battery_ageing::AgeingModel,
+        battery_model::{BatteryModel, CurveMorphModel},
+    },
+    error::FarmbotSimError,
     units::{energy::Energy, voltage::Voltage},
-    utilities::utils::load_json_or_panic,
+    utilities::utils::{load_json, load_json_or_panic},
 };
 
-/// Configuration for a battery model, including capacity, voltage, and seasonal characteristics.
+/// Configuration for a battery, including capacity, voltage, charging and ageing model.
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(try_from = "BatteryConfigFile")]
 pub struct BatteryConfig {
     pub name: String,
     pub capacity: Energy,
     pub voltage: Voltage,
-    pub jan_max: String,
-    pub jan_min: String,
-    pub jun_max: String,
+    pub model: BatteryModel,
+    /// Capacity fade over time and use, the battery doesn't age if `None`.
+    pub ageing: Option<AgeingModel>,
 }
+
+/// Battery config as found in files, also in the format saved before charging models.
+#[derive(serde::Deserialize)]
+struct BatteryConfigFile {
+    name: String,
+    capacity: Energy,
+    voltage: Voltage,
+    model: Option<BatteryModel>,
+    ageing: Option<AgeingModel>,
+    // Configs saved before charging models list the curves instead of a model.
+    // Their `jan_max` curve was never used for charging and is ignored.
+    jan_max: Option<String>,
+    jan_min: Option<String>,
+    jun_max: Option<String>,
+}
+impl TryFrom<BatteryConfigFile> for BatteryConfig {
+    type Error = String;
+    fn try_from(file: BatteryConfigFile) -> Result<Self, Self::Error> {
+        let model = match (file.model, file.jan_max, file.jan_min, file.jun_max) {
+            (Some(model), ..) => model,
+            (None, _, Some(jan_min), Some(jun_max)) => {
+                BatteryModel::CurveMorph(CurveMorphModel::jan_jun(jan_min, jun_max))
+            }
+            _ => return Err("missing field `model`".to_string()),
+        };
+        Ok(Self {
+            name: file.name,
+            capacity: file.capacity,
+            voltage: file.voltage,
+            model,
+            ageing: file.ageing,
+        })
+    }
+}
+
 impl BatteryConfig {
     /// Loads a BatteryConfig from a config.json file inside the given folder.
     /// Panics if the file is missing or invalid.
@@ -20,4 +61,9 @@ impl BatteryConfig {
         let path_str = format!("{folder_name}/config.json");
         load_json_or_panic(path_str)
     }
+    /// Loads a BatteryConfig from a config.json file inside the given folder.
+    pub fn try_from_json_file(folder_name: String) -> Result<Self, FarmbotSimError> {
+        let path_str = format!("{folder_name}/config.json");
+        load_json(path_str)
+    }
 }
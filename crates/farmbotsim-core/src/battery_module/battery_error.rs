@@ -1,7 +1,7 @@
 /// Represents errors that can occur in battery processing.
 #[derive(Debug)]
 pub enum BatteryError {
-    UnsupportedMonth(String),
+    NoCurves,
     NoXForY(String),
     NoYForX(String),
 }
@@ -9,8 +9,8 @@ pub enum BatteryError {
 impl std::fmt::Display for BatteryError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            BatteryError::UnsupportedMonth(month) => {
-                write!(f, "Unsupported month: {month}")
+            BatteryError::NoCurves => {
+                write!(f, "No charging curves")
             }
             BatteryError::NoXForY(y) => {
                 write!(f, "No x found for y: {y}")
@@ -0,0 +1,192 @@
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    environment::datetime::DATE_FORMAT,
+    error::FarmbotSimError,
+    units::{current::Current, duration::Duration, energy::Energy, voltage::Voltage},
+};
+
+/// Format of the date of a charging curve, day and month without a year.
+pub const CURVE_DATE_FORMAT: &str = "%d.%m.";
+
+/// Measured charging curves that are interpolated by day of year.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CurveMorphModel {
+    pub curves: Vec<DatedCurve>,
+}
+
+/// A measured charging curve and the date it applies to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DatedCurve {
+    /// Date string (format "dd.mm.").
+    pub date: String,
+    /// File name relative to the battery folder.
+    pub file: String,
+}
+
+impl CurveMorphModel {
+    /// Returns the fixed January and June curves of battery configs saved before dated curves.
+    /// They were blended by month, `jan_min` fully in January and `jun_max` fully in July.
+    pub fn jan_jun(jan_min: String, jun_max: String) -> Self {
+        Self {
+            curves: vec![
+                DatedCurve {
+                    date: "01.01.".to_string(),
+                    file: jan_min,
+                },
+                DatedCurve {
+                    date: "01.07.".to_string(),
+                    file: jun_max,
+                },
+            ],
+        }
+    }
+    /// Returns an error if there are no curves or two curves share a date.
+    pub fn validate(&self) -> Result<(), FarmbotSimError> {
+        if self.curves.is_empty() {
+            let msg = "At least one charging curve is required".to_string();
+            return Err(FarmbotSimError::invalid_field("curves", msg));
+        }
+        let mut days = Vec::with_capacity(self.curves.len());
+        for curve in &self.curves {
+            let day = curve.try_day_of_year()?;
+            if days.contains(&day) {
+                let msg = format!("More than one curve for '{}'", curve.date);
+                return Err(FarmbotSimError::invalid_field("curves.date", msg));
+            }
+            days.push(day);
+        }
+        Ok(())
+    }
+}
+
+impl DatedCurve {
+    /// Returns the day of the year (1-366) of the curve date, counted as in a leap year.
+    /// Returns an error if the date doesn't match `CURVE_DATE_FORMAT`.
+    pub fn try_day_of_year(&self) -> Result<u32, FarmbotSimError> {
+        // A leap year accepts 29.02.
+        NaiveDate::parse_from_str(&format!("{}2000", self.date), DATE_FORMAT)
+            .map(|date| date.ordinal())
+            .map_err(|e| {
+                let msg = format!(
+                    "'{}' doesn't match format '{CURVE_DATE_FORMAT}': {e}",
+                    self.date
+                );
+                FarmbotSimError::invalid_field("curves.date", msg)
+            })
+    }
+}
+
+/// Parametric constant current / constant voltage charging.
+/// The current stays at `charge_current` up to `cv_threshold`, then tapers off proportionally
+/// to the missing energy until it drops to `cutoff_current` and the battery counts as full.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CcCvModel {
+    /// Current during the constant current phase.
+    pub charge_current: Current,
+    /// State of charge in percent at which the constant voltage phase starts.
+    pub cv_threshold: f32,
+    /// Current at which the constant voltage phase ends.
+    pub cutoff_current: Current,
+}
+impl Default for CcCvModel {
+    /// Returns a typical lithium-ion charging profile.
+    fn default() -> Self {
+        Self {
+            charge_current: Current::amperes(5.0),
+            cv_threshold: 80.0,
+            cutoff_current: Current::amperes(0.25),
+        }
+    }
+}
+
+impl CcCvModel {
+    /// Returns an error if a parameter is out of range.
+    pub fn validate(&self) -> Result<(), FarmbotSimError> {
+        if self.charge_current <= Current::ZERO {
+            let msg = format!("{} must be positive", self.charge_current);
+            return Err(FarmbotSimError::invalid_field("charge_current", msg));
+        }
+        if !(0.0..=100.0).contains(&self.cv_threshold) {
+            let msg = format!("{} is outside 0..=100 %", self.cv_threshold);
+            return Err(FarmbotSimError::invalid_field("cv_threshold", msg));
+        }
+        if self.cutoff_current < Current::ZERO || self.cutoff_current > self.charge_current {
+            let msg = format!("{} is outside 0..=charge_current", self.cutoff_current);
+            return Err(FarmbotSimError::invalid_field("cutoff_current", msg));
+        }
+        Ok(())
+    }
+
+    /// Returns the energy of a battery charged from `energy` for `duration`.
+    pub fn energy_after_charge(
+        &self,
+        energy: Energy,
+        capacity: Energy,
+        voltage: Voltage,
+        duration: Duration,
+    ) -> Energy {
+        let power = (self.charge_current * voltage).to_base_unit();
+        let full = capacity.to_base_unit();
+        let cv_start = full * self.cv_threshold / 100.0;
+        let mut energy = energy.to_base_unit();
+        let mut time = duration.to_base_unit();
+        if power <= 0.0 {
+            return Energy::joules(energy);
+        }
+
+        // Constant current phase
+        if energy < cv_start {
+            let cc_time = (cv_start - energy) / power;
+            if time <= cc_time {
+                return Energy::joules(energy + power * time);
+            }
+            energy = cv_start;
+            time -= cc_time;
+        }
+
+        // Constant voltage phase, the missing energy decays exponentially
+        let cv_gap = full - cv_start;
+        if cv_gap <= 0.0 {
+            return capacity;
+        }
+        let cutoff_gap = cv_gap * (self.cutoff_current / self.charge_current).clamp(0.0, 1.0);
+        let gap = (full - energy) * (-power * time / cv_gap).exp();
+        if gap <= cutoff_gap {
+            capacity
+        } else {
+            Energy::joules(full - gap)
+        }
+    }
+
+    /// Returns the charging curve of an empty battery until it is full as
+    /// (time in s, energy in Wh) points, sampled every `step`.
+    pub fn charge_curve(
+        &self,
+        capacity: Energy,
+        voltage: Voltage,
+        step: Duration,
+    ) -> Vec<(f32, f32)> {
+        let mut points = vec![(0.0, 0.0)];
+        let mut energy = Energy::ZERO;
+        let mut time = 0.0;
+        // Cap the number of points for parameters that barely charge
+        while energy < capacity && points.len() < 10_000 {
+            energy = self.energy_after_charge(energy, capacity, voltage, step);
+            time += step.to_base_unit();
+            points.push((time, energy.to_watt_hour()));
+        }
+        points
+    }
+}
+
+/// Represents how a battery charges.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "params")]
+pub enum BatteryModel {
+    /// Interpolates measured charging curves.
+    CurveMorph(CurveMorphModel),
+    /// Charges with a parametric constant current / constant voltage profile.
+    CcCv(CcCvModel),
+}
@@ -0,0 +1,206 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    path::Path,
+};
+
+use crate::{
+    battery_module::battery_error::BatteryError, error::FarmbotSimError,
+    utilities::utils::linear_interpolate,
+};
+
+/// Number of days in a leap year, the period over which curves are interpolated.
+pub const DAYS_IN_YEAR: u32 = 366;
+
+/// A measured charging curve of (time in s, energy in Wh) points for a day of the year.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ChargingCurve {
+    pub day_of_year: u32,
+    pub data: Vec<(f32, f32)>,
+    pub start_index: usize,
+}
+
+impl ChargingCurve {
+    /// Loads a charging curve for a day of the year from a data file.
+    /// Returns an error if the file can't be read or has too few data points.
+    pub fn try_from_file<P: AsRef<Path>>(
+        day_of_year: u32,
+        file_path: P,
+    ) -> Result<Self, FarmbotSimError> {
+        Ok(Self {
+            day_of_year,
+            data: Self::get_data_points(file_path)?,
+            start_index: 1,
+        })
+    }
+
+    /// Restarts the interpolation search from the beginning of the curve.
+    pub fn reset_search(&mut self) {
+        self.start_index = 1;
+    }
+
+    /// Parses charging data points from a whitespace-delimited file.
+    fn get_data_points<P: AsRef<Path>>(file_path: P) -> Result<Vec<(f32, f32)>, FarmbotSimError> {
+        let path_ref = file_path.as_ref();
+        let file = File::open(path_ref).map_err(|e| FarmbotSimError::io(path_ref, e))?;
+        let reader = BufReader::new(file);
+        let mut points = Vec::new();
+
+        for line in reader.lines().skip(1).map_while(Result::ok) {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            if let (Some(t), Some(v)) = (parts.first(), parts.last())
+                && let (Ok(t), Ok(v)) = (t.parse::<f32>(), v.parse::<f32>())
+            {
+                // Time (s) - Energy (Wh)
+                points.push((t * 3600.0, v));
+            }
+        }
+        if points.len() < 2 {
+            let msg = format!("Expected at least 2 data points, found {}", points.len());
+            return Err(FarmbotSimError::parse::<Vec<(f32, f32)>, _>(path_ref, msg));
+        }
+        Ok(points)
+    }
+
+    /// Finds interpolated energy output for a given input time.
+    pub fn find_y_for_x(&mut self, x: f32) -> Result<f32, BatteryError> {
+        let data = &self.data;
+        if data.len() < 2 {
+            let msg = format!("No data for day {}", self.day_of_year);
+            return Err(BatteryError::NoYForX(msg));
+        }
+        let (first, last) = (data[0], data[data.len() - 1]);
+
+        // Clamp x within range
+        if x <= first.0 {
+            return Ok(first.1);
+        } else if x >= last.0 {
+            return Ok(last.1);
+        }
+
+        // Safe starting index
+        let mut start = self.start_index.max(1).min(data.len() - 1);
+
+        // Reset if x goes backward
+        if x < data[start - 1].0 {
+            start = 1;
+        }
+
+        for i in start..data.len() {
+            let (x0, y0) = data[i - 1];
+            let (x1, y1) = data[i];
+            if x0 <= x && x <= x1 {
+                self.start_index = std::cmp::max(1, i - 1);
+                return Ok(linear_interpolate(x0, y0, x1, y1, x));
+            }
+        }
+
+        Err(BatteryError::NoYForX(x.to_string()))
+    }
+
+    /// Finds interpolated time needed to reach a given energy.
+    pub fn find_x_for_y(&mut self, y: f32) -> Result<f32, BatteryError> {
+        let data = &self.data;
+        if data.len() < 2 {
+            let msg = format!("No data for day {}", self.day_of_year);
+            return Err(BatteryError::NoXForY(msg));
+        }
+        let (first, last) = (data[0], data[data.len() - 1]);
+
+        // Clamp y within dataset range
+        if y <= first.1 {
+            return Ok(first.0);
+        } else if y >= last.1 {
+            return Ok(last.0);
+        }
+
+        // Safe starting index
+        let mut start = self.start_index.max(1).min(data.len() - 1);
+
+        // Reset if y goes backward
+        if y < data[start - 1].1 {
+            start = 1;
+        }
+
+        for i in start..data.len() {
+            let (x0, y0) = data[i - 1];
+            let (x1, y1) = data[i];
+            if (y0 <= y && y <= y1) || (y1 <= y && y <= y0) {
+                self.start_index = std::cmp::max(1, i - 1);
+                return Ok(linear_interpolate(y0, x0, y1, x1, y));
+            }
+        }
+
+        Err(BatteryError::NoXForY(y.to_string()))
+    }
+
+    /// Returns the time after charging for `time` seconds from energy `y` and the energy reached.
+    pub fn next_x_y(&mut self, y: f32, time: f32) -> Result<(f32, f32), BatteryError> {
+        let next_time = self.find_x_for_y(y)? + time;
+        let new_wh = self.find_y_for_x(next_time)?;
+        Ok((next_time, new_wh))
+    }
+}
+
+/// Returns the indices of the curves before and after `day_of_year` and the weight of the later one.
+/// Curves must be sorted by day, the year wraps around so December blends into January.
+pub fn nearest_curves(curves: &[ChargingCurve], day_of_year: u32) -> Option<(usize, usize, f32)> {
+    if curves.is_empty() {
+        return None;
+    }
+    let next = curves
+        .iter()
+        .position(|curve| curve.day_of_year > day_of_year)
+        .unwrap_or(0);
+    let previous = (next + curves.len() - 1) % curves.len();
+    if previous == next {
+        return Some((previous, next, 0.0));
+    }
+    let days_between = |from: u32, to: u32| (to + DAYS_IN_YEAR - from) % DAYS_IN_YEAR;
+    let span = days_between(curves[previous].day_of_year, curves[next].day_of_year);
+    let elapsed = days_between(curves[previous].day_of_year, day_of_year);
+    Some((previous, next, elapsed as f32 / span as f32))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn curves(days: &[u32]) -> Vec<ChargingCurve> {
+        days.iter()
+            .map(|&day_of_year| ChargingCurve {
+                day_of_year,
+                data: vec![(0.0, 0.0), (3600.0, 100.0)],
+                start_index: 1,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn days_between_curves_blend_the_neighbours() {
+        assert_eq!(
+            nearest_curves(&curves(&[15, 196, 350]), 100),
+            Some((0, 1, 85.0 / 181.0))
+        );
+    }
+
+    #[test]
+    fn december_blends_into_january() {
+        let curves = curves(&[15, 196, 350]);
+
+        assert_eq!(nearest_curves(&curves, 360), Some((2, 0, 10.0 / 31.0)));
+        assert_eq!(nearest_curves(&curves, 5), Some((2, 0, 21.0 / 31.0)));
+        assert_eq!(nearest_curves(&curves, 350), Some((2, 0, 0.0)));
+    }
+
+    #[test]
+    fn a_single_curve_is_used_all_year() {
+        let curves = curves(&[172]);
+
+        for day in [1, 172, 366] {
+            assert_eq!(nearest_curves(&curves, day), Some((0, 0, 0.0)));
+        }
+        assert_eq!(nearest_curves(&[], 172), None);
+    }
+}
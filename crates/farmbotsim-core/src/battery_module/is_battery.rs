@@ -6,7 +6,7 @@ pub trait IsBattery {
     fn discharge(&mut self, power: Power, duration: Duration);
 
     /// Increases battery energy based on solar charge estimation.
-    fn charge(&mut self, duration: Duration, month: u32);
+    fn charge(&mut self, duration: Duration, day_of_year: u32);
 
     /// Returns the current state of charge as a percentage.
     fn get_soc(&self) -> f32;
@@ -1,6 +1,9 @@
 //! Battery module containing all battery-related logic.
 
 pub mod battery;
+pub mod battery_ageing;
 pub mod battery_config;
 pub mod battery_error;
+pub mod battery_model;
+pub mod charging_curve;
 pub mod is_battery;
@@ -1,12 +1,30 @@
-use crate::units::{angle::Angle, length::Length, linear_velocity::LinearVelocity, power::Power};
+use crate::units::{
+    angle::Angle, duration::Duration, length::Length, linear_velocity::LinearVelocity,
+};
 
 pub const RNG_SEED: u64 = 741;
 
 pub const TOLERANCE_DISTANCE: Length = Length::meters(0.005);
 pub const TOLERANCE_ANGLE: Angle = Angle::degrees(0.05);
+pub const TOLERANCE_ARC_HEADING: Angle = Angle::degrees(5.0); // heading error accepted when following arcs
 
-pub const POWER_CONSUMPTION_WAIT: Power = Power::watts(10.0); // W/s
-pub const POWER_CONSUMPTION_TRAVEL: Power = Power::watts(2.0 * 350.0); // W/s
+pub const GRAVITY: f32 = 9.81; // m/s²
+
+pub const NEAR_MISS_DISTANCE: Length = Length::meters(0.1); // gap between footprints
+pub const YIELD_DISTANCE: Length = Length::meters(0.5); // gap at which lower priority agents stop
+pub const MAX_BLOCKED_DURATION: Duration = Duration::minutes(1.0); // wait for an agent standing in the way before passing it
+
+pub const DEFAULT_GRID_CELL_SIZE: Length = Length::meters(0.1); // occupancy grid planner
+pub const GRAPH_POINT_CLEARANCE: Length = Length::meters(0.1); // gap between user obstacles and their graph points
+
+pub const MIN_ARC_RADIUS: Length = Length::meters(0.01); // smaller corners of smoothed paths are turned in place
+pub const ARC_SAMPLE_ANGLE: Angle = Angle::degrees(10.0); // arc sampling for collision checks
+
+pub const RESERVATION_WAIT_STEP: Duration = Duration::seconds(1.0); // smallest planned wait
+pub const RESERVATION_MAX_WAIT: Duration = Duration::minutes(2.0); // longest planned wait per path
+pub const RESERVATION_TIME_MARGIN: Duration = Duration::seconds(2.0); // padding around reserved segments
+
+pub const MAX_FAST_FORWARD_DURATION: Duration = Duration::days(1.0); // longest single jump
 
 pub const MAX_VELOCITY_BETWEEN_POINTS: LinearVelocity = LinearVelocity::kilometers_per_hour(3.0); // between farm entities
 
@@ -17,6 +35,8 @@ pub const DEFAULT_LINE_FARM_ENTITY_PLAN_PATH: &str = "configs/farm_entity_plans/
 
 pub const BATTERIES_PATH: &str = "configs/batteries/";
 
+pub const IRRADIANCE_PATH: &str = "configs/irradiance/";
+
 pub const MOVEMENT_CONFIGS_PATH: &str = "configs/movement_configs/";
 pub const DEFAULT_ROMBA_MOVEMENT_CONFIG_PATH: &str = "configs/movement_configs/default_romba.json";
 
@@ -31,5 +51,9 @@ pub const DEFAULT_SCENE_CONFIG_PATH: &str = "configs/scene_configs/default.json"
 
 pub const PERFORMANCE_MATRIX_PATH: &str = "performance_matrix/";
 
+pub const SNAPSHOTS_PATH: &str = "snapshots/";
+
+pub const EVENT_LOGS_PATH: &str = "event_logs/";
+
 pub const TASK_MANAGER_CONFIGS_PATH: &str = "configs/task_manager_configs/";
 pub const DEFAULT_TASK_MANAGER_CONFIG_PATH: &str = "configs/task_manager_configs/default.json";
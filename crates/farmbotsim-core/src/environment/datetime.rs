@@ -1,12 +1,13 @@
-use chrono::{Datelike, Duration, NaiveDateTime};
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta};
 use serde::{Deserialize, Serialize};
 
-use crate::logger::log_error_and_panic;
+use crate::{error::FarmbotSimError, logger::log_error_and_panic, units::duration::Duration};
 
 /// Manages date and time based on a configuration.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DateTimeManager {
     pub config: DateTimeConfig,
+    pub start_time: NaiveDateTime,
     pub current_time: NaiveDateTime,
 }
 
@@ -18,46 +19,81 @@ impl DateTimeManager {
     /// Creates a new `DateTimeManager` from `DateTimeConfig`.
     /// Panics if parsing fails.
     pub fn from_config(config: DateTimeConfig) -> Self {
-        let combined = format!("{} {}", config.date, config.time);
-
-        let dt = NaiveDateTime::parse_from_str(&combined, DATETIME_FORMAT).unwrap_or_else(|e| {
-            let msg = format!(
-                "Failed to parse datetime '{combined}' with format '{DATETIME_FORMAT}': {e}"
-            );
-            log_error_and_panic(&msg)
-        });
-
-        DateTimeManager {
+        Self::try_from_config(config).unwrap_or_else(|e| e.log_and_panic())
+    }
+    /// Creates a new `DateTimeManager` from `DateTimeConfig`.
+    /// Returns an error if the date or time doesn't match its format.
+    pub fn try_from_config(config: DateTimeConfig) -> Result<Self, FarmbotSimError> {
+        let dt = Self::parse_start_time(&config)?;
+        Ok(DateTimeManager {
             config,
+            start_time: dt,
             current_time: dt,
-        }
+        })
+    }
+    /// Advances the current time by the specified duration, keeping sub-second precision.
+    pub fn advance_time(&mut self, duration: Duration) {
+        let nanoseconds = (duration.to_base_unit() as f64 * 1e9).round() as i64;
+        self.current_time += TimeDelta::nanoseconds(nanoseconds);
     }
-    /// Advances the current time by the specified number of seconds.
-    pub fn advance_time(&mut self, seconds: i64) {
-        self.current_time += Duration::seconds(seconds);
+    /// Returns the duration elapsed since the start time.
+    pub fn elapsed(&self) -> Duration {
+        let elapsed = self.current_time - self.start_time;
+        let seconds = elapsed.num_nanoseconds().unwrap_or(i64::MAX) as f64 * 1e-9;
+        Duration::seconds(seconds as f32)
     }
     /// Returns the current date and time formatted as a string.
     pub fn get_time(&self) -> String {
         self.current_time.format(DATETIME_FORMAT).to_string()
     }
-    /// Returns the current month (1-12).
-    pub fn get_month(&self) -> u32 {
-        self.current_time.month()
+    /// Returns the current day of the year (1-366), counted as in a leap year
+    /// so that a date maps to the same day every year.
+    pub fn get_day_of_year(&self) -> u32 {
+        leap_year_day(self.current_time.month(), self.current_time.day())
+    }
+    /// Returns the duration until the start of the next day.
+    pub fn duration_until_next_day(&self) -> Duration {
+        let next_day_start = self
+            .current_time
+            .date()
+            .succ_opt()
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .unwrap_or(self.current_time);
+        let remaining = next_day_start - self.current_time;
+        Duration::seconds((remaining.num_nanoseconds().unwrap_or(i64::MAX) as f64 * 1e-9) as f32)
     }
     /// Resets the current time to the initial configured date and time.
     /// Panics if parsing fails.
     pub fn reset(&mut self) {
-        let combined = format!("{} {}", self.config.date, self.config.time);
-        self.current_time = NaiveDateTime::parse_from_str(&combined, DATETIME_FORMAT)
-            .unwrap_or_else(|e| {
-                let msg = format!(
-                    "Failed to parse datetime '{combined}' with format '{DATETIME_FORMAT}': {e}"
-                );
-                log_error_and_panic(&msg)
-            });
+        self.start_time =
+            Self::parse_start_time(&self.config).unwrap_or_else(|e| e.log_and_panic());
+        self.current_time = self.start_time;
+    }
+    /// Parses the configured date and time into the start time.
+    fn parse_start_time(config: &DateTimeConfig) -> Result<NaiveDateTime, FarmbotSimError> {
+        let date = NaiveDate::parse_from_str(&config.date, DATE_FORMAT).map_err(|e| {
+            let msg = format!(
+                "'{}' doesn't match format '{DATE_FORMAT}': {e}",
+                config.date
+            );
+            FarmbotSimError::invalid_field("datetime_config.date", msg)
+        })?;
+        let time = NaiveTime::parse_from_str(&config.time, TIME_FORMAT).map_err(|e| {
+            let msg = format!(
+                "'{}' doesn't match format '{TIME_FORMAT}': {e}",
+                config.time
+            );
+            FarmbotSimError::invalid_field("datetime_config.time", msg)
+        })?;
+        Ok(date.and_time(time))
     }
 }
 
+/// Returns the day of the year (1-366) of the given month and day in a leap year.
+pub fn leap_year_day(month: u32, day: u32) -> u32 {
+    NaiveDate::from_ymd_opt(2000, month, day).map_or(1, |date| date.ordinal())
+}
+
 /// Configuration for date and time used by `DateTimeManager`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DateTimeConfig {
@@ -70,10 +106,13 @@ impl DateTimeConfig {
     /// Creates a `DateTimeConfig` from a single combined datetime string.
     /// Panics if parsing fails.
     pub fn from_string(datetime_str: String) -> Self {
-        let dt = NaiveDateTime::parse_from_str(&datetime_str, DATETIME_FORMAT).unwrap_or_else(|e| {
-            let msg = format!("Failed to parse datetime '{datetime_str}' with format '{DATETIME_FORMAT}': {e}");
-            log_error_and_panic(&msg)
-        });
+        let dt =
+            NaiveDateTime::parse_from_str(&datetime_str, DATETIME_FORMAT).unwrap_or_else(|e| {
+                let msg = format!(
+                    "Failed to parse datetime '{datetime_str}' with format '{DATETIME_FORMAT}': {e}"
+                );
+                log_error_and_panic(&msg)
+            });
         let date = dt.format(DATE_FORMAT).to_string();
         let time = dt.format(TIME_FORMAT).to_string();
         Self { date, time }
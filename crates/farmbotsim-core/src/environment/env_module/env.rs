@@ -1,24 +1,41 @@
 use egui::Vec2;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
 use std::collections::HashMap;
 
 use crate::{
-    agent_module::agent::{Agent, AgentId},
+    agent_module::{
+        agent::{Agent, AgentId},
+        agent_config::AgentConfig,
+        agent_state::AgentState,
+        collision::{
+            CollisionAvoidance, Contact, ContactKind, blocked_agents, detect_contacts,
+            yielding_agents,
+        },
+    },
+    battery_module::{battery_ageing::BatteryHealth, is_battery::IsBattery},
+    cfg::MAX_FAST_FORWARD_DURATION,
     environment::{
         datetime::{DateTimeConfig, DateTimeManager},
-        env_module::env_config::EnvConfig,
+        env_module::{
+            env_config::{EnvConfig, FleetGroup, n_agents, planning_radius},
+            env_snapshot::{ENV_SNAPSHOT_VERSION, EnvSnapshot},
+        },
         field_config::FieldConfig,
         obstacle::Obstacle,
         scene_config::SceneConfig,
         spawn_area_module::spawn_area::SpawnArea,
-        station_module::station::Station,
+        station_module::{power_budget::PowerRequest, station::Station},
     },
+    error::FarmbotSimError,
+    event_module::{event::EventKind, event_log::EventLog},
     path_finding_module::visibility_graph::VisibilityGraph,
-    statistics::{AgentEpisodeStats, EnvEpisodeStats},
+    statistics::{AgentEpisodeStats, EnvEpisodeStats, GroupEpisodeStats},
     task_module::{task_manager::TaskManager, task_manager_config::TaskManagerConfig},
     units::duration::Duration,
     utilities::{
         pos2::random_pos2_in_rect,
-        utils::{generate_colors, load_json_or_panic},
+        utils::{generate_colors, load_json},
         vec2::random_vec2,
     },
 };
@@ -31,12 +48,24 @@ pub struct Env {
     pub step_count: u32,
     /// Total duration elapsed.
     pub duration: Duration,
-    /// Number of agents in the environment.
-    pub n_agents: u32,
-    /// File path to the agent configuration.
-    pub agent_path: String,
+    /// Simulated time advanced by a single step.
+    pub simulation_step: Duration,
+    /// Whether idle periods are skipped by jumping to the next event.
+    pub fast_forward: bool,
+    /// Whether agent batteries keep their wear when the environment is reset.
+    pub persist_battery_health: bool,
+    /// Seed of the current episode.
+    pub seed: u64,
+    /// Random number generator of the current episode, seeded with `seed`.
+    pub rng: ChaCha12Rng,
+    /// Groups of agents sharing an agent configuration, spawned in order.
+    pub fleet: Vec<FleetGroup>,
     /// Collection of agents in the environment.
     pub agents: Vec<Agent>,
+    /// How agents react when they come too close to each other.
+    pub collision_avoidance: CollisionAvoidance,
+    /// Contacts between agents after the last step.
+    pub contacts: Vec<Contact>,
     /// Configuration of the field layout.
     pub field_config: FieldConfig,
     /// List of stations in the environment.
@@ -45,6 +74,8 @@ pub struct Env {
     pub spawn_area: SpawnArea,
     /// Obstacles present in the env.
     pub obstacles: Vec<Obstacle>,
+    /// Obstacles inflated by the planning radius, used for path finding.
+    pub planning_obstacles: Vec<Obstacle>,
     /// Visibility graph used for pathfinding.
     pub visibility_graph: VisibilityGraph,
     /// Configuration of the datetime system.
@@ -53,117 +84,461 @@ pub struct Env {
     pub date_time_manager: DateTimeManager,
     /// Manages tasks assigned to agents.
     pub task_manager: TaskManager,
+    /// Events of the current episode.
+    pub event_log: EventLog,
 }
 
 impl Env {
     /// Creates a new `Env` instance from a given `EnvConfig`.
     /// Panics if any JSON file can't be parsed or is not present.
     pub fn from_config(config: EnvConfig) -> Self {
-        let scene_config: SceneConfig = load_json_or_panic(config.scene_config_path);
-        let field_config: FieldConfig = load_json_or_panic(scene_config.field_config_path);
-        let spawn_area = SpawnArea::from_config(scene_config.spawn_area_config.clone());
+        Self::try_from_config(config).unwrap_or_else(|e| e.log_and_panic())
+    }
 
-        let n_agents = config.n_agents;
-        let agent_colors = generate_colors(n_agents as usize, 0.1);
-        let mut agents = Vec::new();
-        for i in 0..n_agents {
-            agents.push(Agent::from_config(
-                load_json_or_panic(config.agent_config_path.clone()),
-                i,
-                random_pos2_in_rect(
-                    egui::Rect {
-                        min: spawn_area.left_top_pos,
-                        max: spawn_area.left_top_pos
-                            + Vec2::new(
-                                spawn_area.width.to_base_unit(),
-                                spawn_area.height.to_base_unit(),
-                            ),
-                    },
-                    spawn_area.angle,
-                ),
-                random_vec2(),
-                agent_colors[i as usize],
-            ))
-        }
+    /// Creates a new `Env` instance from a given `EnvConfig`.
+    /// Returns an error if any JSON file can't be parsed or is not present.
+    pub fn try_from_config(config: EnvConfig) -> Result<Self, FarmbotSimError> {
+        let scene_config: SceneConfig = load_json(&config.scene_config_path)?;
+        let field_config: FieldConfig = load_json(&scene_config.field_config_path)?;
+        let spawn_area = SpawnArea::from_config(scene_config.spawn_area_config.clone());
 
         let station_colors = generate_colors(scene_config.station_configs.len(), 0.0);
         let mut stations = Vec::new();
         for (i, station_config) in scene_config.station_configs.iter().enumerate() {
+            let mut station_config = station_config.clone();
+            if let Some(power_budget) = &mut station_config.power_budget {
+                power_budget
+                    .validate()
+                    .map_err(|e| e.in_file(&config.scene_config_path))?;
+                power_budget.try_load_irradiance()?;
+            }
             stations.push(Station::from_config(
                 i as u32,
                 station_colors[i],
-                station_config.clone(),
+                station_config,
             ))
         }
         let obstacles = field_config.get_obstacles();
-        let visibility_graph =
-            VisibilityGraph::new(&field_config.get_graph_points(), obstacles.clone());
+        let planning_radius = planning_radius(&config.fleet)?;
+        let planning_obstacles = field_config.get_inflated_obstacles(planning_radius);
+        let visibility_graph = VisibilityGraph::new(
+            &field_config.get_inflated_graph_points(planning_radius),
+            planning_obstacles.clone(),
+        );
 
-        let date_time_manager = DateTimeManager::from_config(config.datetime_config.clone());
+        let date_time_manager = DateTimeManager::try_from_config(config.datetime_config.clone())?;
 
-        let task_manager_config: TaskManagerConfig =
-            load_json_or_panic(config.task_manager_config_path);
-        let task_manager = TaskManager::from_config(task_manager_config, field_config.clone());
-        Self {
+        let task_manager_config: TaskManagerConfig = load_json(&config.task_manager_config_path)?;
+        let task_manager = TaskManager::try_from_config(
+            task_manager_config,
+            field_config.clone(),
+            scene_config.path_planner,
+            planning_radius,
+            scene_config.path_smoothing,
+        )
+        .map_err(|e| e.in_file(&scene_config.field_config_path))?;
+        let mut env = Self {
             step_count: 0,
             duration: Duration::ZERO,
-            n_agents,
-            agent_path: config.agent_config_path,
-            agents,
+            simulation_step: config.simulation_step,
+            fast_forward: config.fast_forward,
+            persist_battery_health: config.persist_battery_health,
+            seed: config.seed,
+            rng: ChaCha12Rng::seed_from_u64(config.seed),
+            fleet: config.fleet,
+            agents: vec![],
+            collision_avoidance: config.collision_avoidance,
+            contacts: vec![],
             field_config,
             stations,
             spawn_area,
             obstacles,
+            planning_obstacles,
             visibility_graph,
             datetime_config: config.datetime_config,
             date_time_manager,
             task_manager,
+            event_log: EventLog::default(),
+        };
+        env.spawn_agents()?;
+        Ok(env)
+    }
+
+    /// Creates a new `Env` instance from a snapshot, rebuilding obstacles and visibility graphs
+    /// from the field configuration.
+    pub fn from_snapshot(snapshot: EnvSnapshot) -> Self {
+        let obstacles = snapshot.field_config.get_obstacles();
+        let mut task_manager = snapshot.task_manager;
+        task_manager.rebuild_path_planner();
+        task_manager.add_path_cache_key_points(&snapshot.agents, &snapshot.stations);
+        let planning_obstacles = task_manager.planning_obstacles().to_vec();
+        let visibility_graph = VisibilityGraph::new(
+            &snapshot
+                .field_config
+                .get_inflated_graph_points(task_manager.planning_radius()),
+            planning_obstacles.clone(),
+        );
+        let duration = snapshot.date_time_manager.elapsed();
+        Self {
+            step_count: snapshot.step_count,
+            duration,
+            simulation_step: snapshot.simulation_step,
+            fast_forward: snapshot.fast_forward,
+            persist_battery_health: snapshot.persist_battery_health,
+            seed: snapshot.seed,
+            rng: snapshot.rng,
+            fleet: snapshot.fleet,
+            agents: snapshot.agents,
+            collision_avoidance: snapshot.collision_avoidance,
+            contacts: snapshot.contacts,
+            field_config: snapshot.field_config,
+            stations: snapshot.stations,
+            spawn_area: snapshot.spawn_area,
+            obstacles,
+            planning_obstacles,
+            visibility_graph,
+            datetime_config: snapshot.datetime_config,
+            date_time_manager: snapshot.date_time_manager,
+            task_manager,
+            event_log: snapshot.event_log,
+        }
+    }
+
+    /// Captures the current state as a snapshot.
+    /// Recorded agent timesteps are only included if `include_timesteps` is set,
+    /// without them episode statistics of a restored env start at the snapshot.
+    pub fn to_snapshot(&self, include_timesteps: bool) -> EnvSnapshot {
+        let mut agents = self.agents.clone();
+        if !include_timesteps {
+            for agent in &mut agents {
+                agent.timesteps.clear();
+            }
+        }
+        EnvSnapshot {
+            version: ENV_SNAPSHOT_VERSION,
+            step_count: self.step_count,
+            simulation_step: self.simulation_step,
+            fast_forward: self.fast_forward,
+            persist_battery_health: self.persist_battery_health,
+            seed: self.seed,
+            rng: self.rng.clone(),
+            fleet: self.fleet.clone(),
+            agents,
+            collision_avoidance: self.collision_avoidance,
+            contacts: self.contacts.clone(),
+            field_config: self.field_config.clone(),
+            stations: self.stations.clone(),
+            spawn_area: self.spawn_area.clone(),
+            datetime_config: self.datetime_config.clone(),
+            date_time_manager: self.date_time_manager.clone(),
+            task_manager: self.task_manager.clone(),
+            event_log: self.event_log.clone(),
         }
     }
 
-    /// Resets the environment to its initial state.
+    /// Replaces the whole state of the environment with the snapshot.
+    pub fn restore_snapshot(&mut self, snapshot: EnvSnapshot) {
+        *self = Self::from_snapshot(snapshot);
+    }
+
+    /// Resets the environment to its initial state and advances the seed to the next episode.
     pub fn reset(&mut self) {
-        self.agents.clear();
-        let agent_colors = generate_colors(self.n_agents as usize, 0.1);
-        for i in 0..self.n_agents {
-            self.agents.push(Agent::from_config(
-                load_json_or_panic(self.agent_path.clone()),
-                i,
-                random_pos2_in_rect(
-                    egui::Rect {
-                        min: self.spawn_area.left_top_pos,
-                        max: self.spawn_area.left_top_pos
-                            + Vec2::new(
-                                self.spawn_area.width.to_base_unit(),
-                                self.spawn_area.height.to_base_unit(),
-                            ),
-                    },
-                    self.spawn_area.angle,
-                ),
-                random_vec2(),
-                agent_colors[i as usize],
-            ))
+        self.reset_with_seed(self.seed.wrapping_add(1));
+    }
+
+    /// Resets the environment to its initial state using the given episode seed.
+    /// With `persist_battery_health` agents keep the wear of their batteries.
+    /// Panics if the agent JSON file can't be parsed or is not present.
+    pub fn reset_with_seed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.rng = ChaCha12Rng::seed_from_u64(seed);
+        let battery_health: Vec<Option<BatteryHealth>> = if self.persist_battery_health {
+            self.agents
+                .iter()
+                .map(|agent| agent.battery.health.clone())
+                .collect()
+        } else {
+            vec![]
+        };
+        self.spawn_agents().unwrap_or_else(|e| e.log_and_panic());
+        // Agents are spawned in the same order every episode
+        for (agent, health) in self.agents.iter_mut().zip(battery_health) {
+            if let Some(health) = health {
+                agent.battery.restore_health(health);
+            }
         }
         for station in &mut self.stations {
             station.reset();
         }
         self.date_time_manager.reset();
         self.task_manager.reset();
+        self.event_log.clear();
         self.step_count = 0;
         self.duration = Duration::ZERO;
     }
 
+    /// Spawns the agents of all groups at random poses inside their spawn areas.
+    /// Agent ids are assigned in group order.
+    /// Returns an error if an agent JSON file can't be parsed or is not present.
+    fn spawn_agents(&mut self) -> Result<(), FarmbotSimError> {
+        self.agents.clear();
+        let agent_colors = generate_colors(n_agents(&self.fleet) as usize, 0.1);
+        let mut id = 0;
+        for group in self.fleet.clone() {
+            let mut agent_config: AgentConfig = load_json(&group.agent_config_path)?;
+            if let Some(soc) = group.start_soc {
+                agent_config.battery_soc = soc;
+            }
+            let spawn_area = group
+                .spawn_area
+                .map_or_else(|| self.spawn_area.clone(), SpawnArea::from_config);
+            for _ in 0..group.n_agents {
+                self.agents.push(Agent::try_from_config(
+                    agent_config.clone(),
+                    id,
+                    random_pos2_in_rect(
+                        egui::Rect {
+                            min: spawn_area.left_top_pos,
+                            max: spawn_area.left_top_pos
+                                + Vec2::new(
+                                    spawn_area.width.to_base_unit(),
+                                    spawn_area.height.to_base_unit(),
+                                ),
+                        },
+                        spawn_area.angle,
+                        &mut self.rng,
+                    ),
+                    random_vec2(&mut self.rng),
+                    agent_colors[id as usize],
+                )?);
+                id += 1;
+            }
+        }
+        // Seeded after spawning so the spawn positions don't depend on odometry noise
+        for agent in &mut self.agents {
+            agent.noise_rng = ChaCha12Rng::seed_from_u64(self.rng.random());
+        }
+        // Agents spawned on top of each other don't count as collisions
+        self.contacts = detect_contacts(&self.agents);
+        self.task_manager
+            .add_path_cache_key_points(&self.agents, &self.stations);
+        Ok(())
+    }
+
     /// Advances the environment by one step.
     pub fn step(&mut self) {
-        let simulation_step = Duration::seconds(1.0);
-        self.duration = self.duration + simulation_step;
+        self.record_events();
+        let simulation_step = self.simulation_step;
         self.step_count += 1;
-        self.date_time_manager
-            .advance_time(simulation_step.to_base_unit() as i64);
+        self.date_time_manager.advance_time(simulation_step);
+        self.duration = self.date_time_manager.elapsed();
         self.task_manager.update_waiting_list(simulation_step);
-        for agent in &mut self.agents {
+        self.allocate_station_power();
+        let yielding = yielding_agents(&self.agents, self.collision_avoidance);
+        let blocked = blocked_agents(&self.agents, self.collision_avoidance);
+        for ((agent, yielding), blocked) in self.agents.iter_mut().zip(yielding).zip(blocked) {
+            agent.yielding = yielding;
+            agent.blocked_duration = if blocked {
+                agent.blocked_duration + simulation_step
+            } else {
+                Duration::ZERO
+            };
             agent.update(simulation_step, &self.date_time_manager);
         }
+        self.update_contacts();
+        self.record_events();
+    }
+
+    /// Divides the power of stations with a power budget among the agents charging in their slots
+    /// and sets their charging power limits for the next step.
+    fn allocate_station_power(&mut self) {
+        let simulation_step = self.simulation_step;
+        let day_of_year = self.date_time_manager.get_day_of_year();
+        for station in &self.stations {
+            let Some(power_budget) = &station.power_budget else {
+                continue;
+            };
+            let mut requests = vec![];
+            for agent_id in &station.slot_order {
+                if let Some(agent) = self
+                    .agents
+                    .iter_mut()
+                    .find(|agent| agent.id == *agent_id && agent.state == AgentState::Charging)
+                {
+                    requests.push(PowerRequest {
+                        agent_id: *agent_id,
+                        power: agent.battery.charge_power(simulation_step, day_of_year),
+                        soc: agent.battery.get_soc(),
+                    });
+                }
+            }
+            let available = power_budget.available_power(&self.date_time_manager.current_time);
+            let allocated = power_budget.allocate(available, &requests);
+            for (request, power) in requests.iter().zip(allocated) {
+                if let Some(agent) = self
+                    .agents
+                    .iter_mut()
+                    .find(|agent| agent.id == request.agent_id)
+                {
+                    agent.charging_power_limit = Some(power);
+                }
+            }
+        }
+    }
+
+    /// Detects contacts between agents and counts those that are new or got more severe
+    /// since the last step.
+    fn update_contacts(&mut self) {
+        let contacts = detect_contacts(&self.agents);
+        for contact in &contacts {
+            let previous_kind = self
+                .contacts
+                .iter()
+                .find(|previous| previous.agent_ids == contact.agent_ids)
+                .map(|previous| previous.kind);
+            if previous_kind.is_some_and(|kind| kind >= contact.kind) {
+                continue;
+            }
+            let (agent_id, other_agent_id) = contact.agent_ids;
+            for agent in &mut self.agents {
+                if agent.id != agent_id && agent.id != other_agent_id {
+                    continue;
+                }
+                match contact.kind {
+                    ContactKind::Collision => agent.n_collisions += 1,
+                    ContactKind::NearMiss => agent.n_near_misses += 1,
+                }
+                if agent.id == agent_id {
+                    agent.pending_events.push(match contact.kind {
+                        ContactKind::Collision => EventKind::Collision {
+                            agent_id,
+                            other_agent_id,
+                        },
+                        ContactKind::NearMiss => EventKind::NearMiss {
+                            agent_id,
+                            other_agent_id,
+                        },
+                    });
+                }
+            }
+        }
+        self.contacts = contacts;
+    }
+
+    /// Moves pending events of the task manager, stations and agents into the event log,
+    /// stamped with the current step and time.
+    fn record_events(&mut self) {
+        let mut kinds = std::mem::take(&mut self.task_manager.pending_events);
+        for station in &mut self.stations {
+            kinds.append(&mut station.pending_events);
+        }
+        for agent in &mut self.agents {
+            kinds.append(&mut agent.pending_events);
+        }
+        if !kinds.is_empty() {
+            self.event_log.record(self.step_count, self.duration, kinds);
+        }
+    }
+
+    /// Jumps over idle steps up to the next event, or performs a single step if nothing can be skipped.
+    /// Must be called right after `TaskManager::assign_tasks`, never advances more than `max_steps` steps
+    /// and returns the number of steps advanced.
+    pub fn step_to_next_event(&mut self, max_steps: u32) -> u32 {
+        let n_steps = self.idle_steps(max_steps);
+        if n_steps > 1 {
+            self.record_events();
+            let n_skipped = self.skip_steps(n_steps);
+            if n_skipped > 0 {
+                self.record_events();
+                return n_skipped;
+            }
+        }
+        self.step();
+        1
+    }
+
+    /// Returns how many steps all agents stay idle, capped at `max_steps`.
+    /// Moving agents reach their next waypoint only in regular steps, so any moving agent yields 0.
+    fn idle_steps(&self, max_steps: u32) -> u32 {
+        let simulation_step = self.simulation_step;
+        let mut n_steps = max_steps.min((MAX_FAST_FORWARD_DURATION / simulation_step) as u32);
+
+        // Charging depends on the day of the year
+        if self
+            .agents
+            .iter()
+            .any(|agent| agent.state == AgentState::Charging)
+        {
+            let day_steps =
+                (self.date_time_manager.duration_until_next_day() / simulation_step).ceil() as u32;
+            n_steps = n_steps.min(day_steps.saturating_sub(1));
+        }
+
+        for agent in &self.agents {
+            if n_steps <= 1 {
+                break;
+            }
+            let work_available = self.task_manager.has_work_for(agent);
+            n_steps = agent.idle_steps(simulation_step, n_steps, work_available);
+        }
+        n_steps
+    }
+
+    /// Advances an idle environment by up to `n_steps` steps, stopping early after a wait expires
+    /// or a battery event (release SoC reached or charging threshold crossed) and before a battery
+    /// is depleted.
+    /// Returns the number of steps skipped.
+    fn skip_steps(&mut self, n_steps: u32) -> u32 {
+        let simulation_step = self.simulation_step;
+        let day_of_year = self.date_time_manager.get_day_of_year();
+        let soc_thresholds = self.task_manager.charging_strategy.soc_thresholds();
+        let pending_soc_thresholds: Vec<Vec<f32>> = self
+            .agents
+            .iter()
+            .map(|agent| agent.pending_soc_thresholds(&soc_thresholds))
+            .collect();
+
+        let mut n_skipped = 0;
+        while n_skipped < n_steps {
+            if self
+                .agents
+                .iter()
+                .any(|agent| agent.is_depleted_by_step(simulation_step))
+            {
+                break;
+            }
+            self.date_time_manager.advance_time(simulation_step);
+            let n_waiting = self.task_manager.waiting.len();
+            let n_work = self.task_manager.work_list.len();
+            self.task_manager.update_waiting_list(simulation_step);
+            let wait_expired = self.task_manager.waiting.len() != n_waiting
+                || self.task_manager.work_list.len() != n_work;
+            self.allocate_station_power();
+            for agent in &mut self.agents {
+                agent.skip_step(simulation_step, day_of_year);
+            }
+            n_skipped += 1;
+            if wait_expired
+                || self
+                    .agents
+                    .iter()
+                    .zip(&pending_soc_thresholds)
+                    .any(|(agent, thresholds)| agent.has_battery_event(thresholds))
+            {
+                break;
+            }
+        }
+        if n_skipped == 0 {
+            return 0;
+        }
+
+        let skipped_duration = simulation_step * n_skipped as f32;
+        self.step_count += n_skipped;
+        self.duration = self.date_time_manager.elapsed();
+        for agent in &mut self.agents {
+            agent.record_skipped_steps(skipped_duration);
+        }
+        n_skipped
     }
 
     /// Compute EnvEpisodeStats from the current environment state
@@ -172,7 +547,11 @@ impl Env {
 
         for agent in &self.agents {
             // Compute per-agent episode stats from timesteps
-            let stats = AgentEpisodeStats::from_timesteps(&agent.timesteps);
+            let mut stats = AgentEpisodeStats::from_timesteps(&agent.timesteps);
+            stats.n_collisions = agent.n_collisions as f32;
+            stats.n_near_misses = agent.n_near_misses as f32;
+            stats.state_of_health = agent.battery.state_of_health();
+            stats.equivalent_full_cycles = agent.battery.equivalent_full_cycles();
             agents.insert(agent.id, stats);
         }
 
@@ -180,10 +559,49 @@ impl Env {
         let n_completed_tasks = self.task_manager.completed_tasks.len() as u32;
         let env_duration = self.duration;
 
+        // Agents are spawned in group order
+        let mut first = 0;
+        let groups = self
+            .fleet
+            .iter()
+            .map(|group| {
+                let agent_ids: Vec<AgentId> = self
+                    .agents
+                    .iter()
+                    .skip(first)
+                    .take(group.n_agents as usize)
+                    .map(|agent| agent.id)
+                    .collect();
+                first += group.n_agents as usize;
+                GroupEpisodeStats {
+                    combined: AgentEpisodeStats::total(
+                        agent_ids.iter().filter_map(|id| agents.get(id)),
+                    ),
+                    agent_config_path: group.agent_config_path.clone(),
+                    agent_ids,
+                }
+            })
+            .collect();
+
+        let mut n_blocked_tasks = 0;
+        let mut blocked_capabilities: Vec<Vec<String>> = vec![];
+        for task in self.task_manager.blocked_tasks(&self.agents) {
+            n_blocked_tasks += 1;
+            let capabilities = task.get_required_capabilities().to_vec();
+            if !blocked_capabilities.contains(&capabilities) {
+                blocked_capabilities.push(capabilities);
+            }
+        }
+
         EnvEpisodeStats {
+            seed: self.seed,
             n_completed_tasks,
             env_duration,
             agents,
+            groups,
+            n_blocked_tasks,
+            blocked_capabilities,
+            n_planning_fallbacks: self.task_manager.n_planning_fallbacks(),
         }
     }
 }
@@ -2,57 +2,200 @@ use serde::{Deserialize, Serialize};
 use std::path::Path;
 
 use crate::{
-    cfg::{DEFAULT_AGENT_CONFIG_PATH, DEFAULT_SCENE_CONFIG_PATH, DEFAULT_TASK_MANAGER_CONFIG_PATH},
-    environment::datetime::DateTimeConfig,
-    utilities::utils::load_json_or_panic,
+    agent_module::{agent_config::AgentConfig, collision::CollisionAvoidance},
+    cfg::{
+        DEFAULT_AGENT_CONFIG_PATH, DEFAULT_SCENE_CONFIG_PATH, DEFAULT_TASK_MANAGER_CONFIG_PATH,
+        RNG_SEED,
+    },
+    environment::{
+        datetime::DateTimeConfig, spawn_area_module::spawn_area_config::SpawnAreaConfig,
+    },
+    error::FarmbotSimError,
+    units::{duration::Duration, length::Length},
+    utilities::utils::{load_json, load_json_or_panic},
 };
 
+/// Group of agents sharing an agent configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FleetGroup {
+    /// Path to the agent configuration file of the group.
+    pub agent_config_path: String,
+    /// Number of agents in the group.
+    pub n_agents: u32,
+    /// Area the group spawns in, the spawn area of the scene if `None`.
+    pub spawn_area: Option<SpawnAreaConfig>,
+    /// State of charge in percent the group starts with, the one of the agent config if `None`.
+    pub start_soc: Option<f32>,
+}
+
+impl Default for FleetGroup {
+    /// Creates a group of one agent with the default agent configuration.
+    fn default() -> Self {
+        Self {
+            agent_config_path: DEFAULT_AGENT_CONFIG_PATH.to_string(),
+            n_agents: 1,
+            spawn_area: None,
+            start_soc: None,
+        }
+    }
+}
+
+impl FleetGroup {
+    /// Creates a group spawned in the scene's spawn area with the agent config's state of charge.
+    pub fn new(agent_config_path: String, n_agents: u32) -> Self {
+        Self {
+            agent_config_path,
+            n_agents,
+            spawn_area: None,
+            start_soc: None,
+        }
+    }
+}
+
 /// Configuration settings for the environment.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "EnvConfigFile")]
 pub struct EnvConfig {
-    /// Number of agents in the environment.
-    pub n_agents: u32,
-    /// Path to the agent configuration file.
-    pub agent_config_path: String,
+    /// Groups of agents sharing an agent configuration, spawned in order.
+    pub fleet: Vec<FleetGroup>,
     /// Configuration for date and time settings.
     #[serde(rename = "date_time")]
     pub datetime_config: DateTimeConfig,
+    /// Simulated time advanced by a single env step.
+    pub simulation_step: Duration,
+    /// Whether idle periods are skipped by jumping to the next event.
+    pub fast_forward: bool,
+    /// Seed of the random number generator for the first episode.
+    pub seed: u64,
+    /// How agents react when they come too close to each other.
+    pub collision_avoidance: CollisionAvoidance,
     /// Path to the scene configuration file.
     pub scene_config_path: String,
     /// Path to task manager configuration file.
     pub task_manager_config_path: String,
+    /// Whether agent batteries keep their wear from one episode to the next.
+    pub persist_battery_health: bool,
 }
 
 impl Default for EnvConfig {
     /// Creates a default environment configuration with preset values.
     fn default() -> Self {
         Self {
-            n_agents: 1,
-            agent_config_path: DEFAULT_AGENT_CONFIG_PATH.to_string(),
+            fleet: vec![FleetGroup::default()],
             datetime_config: DateTimeConfig::from_string("01.01.2025 00:00:00".to_string()),
+            simulation_step: Duration::seconds(1.0),
+            fast_forward: false,
+            seed: RNG_SEED,
+            collision_avoidance: CollisionAvoidance::default(),
             scene_config_path: DEFAULT_SCENE_CONFIG_PATH.to_string(),
             task_manager_config_path: DEFAULT_TASK_MANAGER_CONFIG_PATH.to_string(),
+            persist_battery_health: false,
         }
     }
 }
 
+/// Env config as found in files, also in the format saved before heterogeneous fleets.
+#[derive(Deserialize)]
+struct EnvConfigFile {
+    #[serde(default)]
+    fleet: Vec<FleetGroup>,
+    // Configs saved before heterogeneous fleets have a single group given by these fields,
+    // later ones with both put that group before the `fleet` groups.
+    n_agents: Option<u32>,
+    agent_config_path: Option<String>,
+    #[serde(rename = "date_time")]
+    datetime_config: DateTimeConfig,
+    #[serde(default = "default_simulation_step")]
+    simulation_step: Duration,
+    #[serde(default)]
+    fast_forward: bool,
+    #[serde(default = "default_seed")]
+    seed: u64,
+    #[serde(default)]
+    collision_avoidance: CollisionAvoidance,
+    scene_config_path: String,
+    task_manager_config_path: String,
+    #[serde(default)]
+    persist_battery_health: bool,
+}
+impl From<EnvConfigFile> for EnvConfig {
+    fn from(file: EnvConfigFile) -> Self {
+        let mut fleet = file.fleet;
+        if let (Some(n_agents), Some(agent_config_path)) = (file.n_agents, file.agent_config_path) {
+            fleet.insert(0, FleetGroup::new(agent_config_path, n_agents));
+        }
+        Self {
+            fleet,
+            datetime_config: file.datetime_config,
+            simulation_step: file.simulation_step,
+            fast_forward: file.fast_forward,
+            seed: file.seed,
+            collision_avoidance: file.collision_avoidance,
+            scene_config_path: file.scene_config_path,
+            task_manager_config_path: file.task_manager_config_path,
+            persist_battery_health: file.persist_battery_health,
+        }
+    }
+}
+
+/// Returns the simulation step of the default env configuration.
+fn default_simulation_step() -> Duration {
+    EnvConfig::default().simulation_step
+}
+
+/// Returns the seed of the default env configuration.
+fn default_seed() -> u64 {
+    RNG_SEED
+}
+
 impl EnvConfig {
     /// Creates a new `EnvConfig` with specified parameters.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
-        n_agents: u32,
-        agent_config_path: String,
+        fleet: Vec<FleetGroup>,
         datetime_config: DateTimeConfig,
+        simulation_step: Duration,
+        fast_forward: bool,
+        seed: u64,
+        collision_avoidance: CollisionAvoidance,
         scene_config_path: String,
         task_manager_config_path: String,
+        persist_battery_health: bool,
     ) -> Self {
         Self {
-            n_agents,
-            agent_config_path,
+            fleet,
             datetime_config,
+            simulation_step,
+            fast_forward,
+            seed,
+            collision_avoidance,
             scene_config_path,
             task_manager_config_path,
+            persist_battery_health,
         }
     }
+
+    /// Returns the total number of agents of all groups.
+    pub fn n_agents(&self) -> u32 {
+        n_agents(&self.fleet)
+    }
+}
+
+/// Returns the total number of agents of the groups.
+pub fn n_agents(fleet: &[FleetGroup]) -> u32 {
+    fleet.iter().map(|group| group.n_agents).sum()
+}
+
+/// Returns the footprint radius of the largest agent of the groups, obstacles are inflated by it
+/// for path planning. Returns an error if an agent config can't be loaded.
+pub fn planning_radius(groups: &[FleetGroup]) -> Result<Length, FarmbotSimError> {
+    groups
+        .iter()
+        .filter(|group| group.n_agents > 0)
+        .try_fold(Length::ZERO, |radius, group| {
+            let agent_config = AgentConfig::try_from_json_file(&group.agent_config_path)?;
+            Ok(radius.max(agent_config.footprint_radius))
+        })
 }
 
 impl EnvConfig {
@@ -61,4 +204,51 @@ impl EnvConfig {
     pub fn from_json_file<P: AsRef<Path>>(file_path: P) -> Self {
         load_json_or_panic(file_path)
     }
+
+    /// Loads an `EnvConfig` from a JSON file at the given path.
+    pub fn try_from_json_file<P: AsRef<Path>>(file_path: P) -> Result<Self, FarmbotSimError> {
+        load_json(file_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn configs_before_fleets_load_as_the_first_group() {
+        let json = r#"{
+            "n_agents": 3,
+            "agent_config_path": "configs/agent_configs/ack.json",
+            "date_time": { "date": "01.01.2025", "time": "00:00:00" },
+            "scene_config_path": "configs/scene_configs/default.json",
+            "task_manager_config_path": "configs/task_manager_configs/default.json",
+            "fleet": [{ "agent_config_path": "configs/agent_configs/default.json", "n_agents": 2 }]
+        }"#;
+        let config: EnvConfig = serde_json::from_str(json).unwrap();
+
+        let groups: Vec<(&str, u32)> = config
+            .fleet
+            .iter()
+            .map(|group| (group.agent_config_path.as_str(), group.n_agents))
+            .collect();
+        assert_eq!(
+            groups,
+            vec![
+                ("configs/agent_configs/ack.json", 3),
+                ("configs/agent_configs/default.json", 2)
+            ]
+        );
+        assert_eq!(config.n_agents(), 5);
+    }
+
+    #[test]
+    fn saved_configs_load_unchanged() {
+        let config = EnvConfig::default();
+        let json = serde_json::to_string(&config).unwrap();
+        let loaded: EnvConfig = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(loaded.fleet.len(), 1);
+        assert_eq!(loaded.n_agents(), config.n_agents());
+    }
 }
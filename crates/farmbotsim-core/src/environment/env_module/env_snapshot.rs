@@ -0,0 +1,97 @@
+use rand_chacha::ChaCha12Rng;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::{
+    agent_module::{
+        agent::Agent,
+        collision::{CollisionAvoidance, Contact},
+    },
+    environment::{
+        datetime::{DateTimeConfig, DateTimeManager},
+        env_module::{env_config::FleetGroup, snapshot_error::SnapshotError},
+        field_config::FieldConfig,
+        spawn_area_module::spawn_area::SpawnArea,
+        station_module::station::Station,
+    },
+    event_module::event_log::EventLog,
+    task_module::task_manager::TaskManager,
+    units::duration::Duration,
+};
+
+/// Version of the snapshot format, increased on every incompatible change.
+pub const ENV_SNAPSHOT_VERSION: u32 = 2;
+
+/// Serializable state of an `Env`, used to save a running simulation and restore it later.
+/// Obstacles and visibility graphs are not stored, they are rebuilt from the field configuration.
+/// Unit values keep the precision of their JSON format, so a restored run may differ slightly
+/// in energy from an uninterrupted one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnvSnapshot {
+    /// Version of the snapshot format.
+    pub version: u32,
+    /// Number of simulation steps performed.
+    pub step_count: u32,
+    /// Simulated time advanced by a single step.
+    pub simulation_step: Duration,
+    /// Whether idle periods are skipped by jumping to the next event.
+    pub fast_forward: bool,
+    /// Whether agent batteries keep their wear when the environment is reset.
+    pub persist_battery_health: bool,
+    /// Seed of the current episode.
+    pub seed: u64,
+    /// Random number generator of the current episode.
+    pub rng: ChaCha12Rng,
+    /// Groups of agents sharing an agent configuration, spawned in order.
+    pub fleet: Vec<FleetGroup>,
+    /// Agents including their battery, tasks, state and recorded timesteps.
+    pub agents: Vec<Agent>,
+    /// How agents react when they come too close to each other.
+    pub collision_avoidance: CollisionAvoidance,
+    /// Contacts between agents at the time of the snapshot.
+    pub contacts: Vec<Contact>,
+    /// Configuration of the field layout.
+    pub field_config: FieldConfig,
+    /// Stations including their slots and queues.
+    pub stations: Vec<Station>,
+    /// Spawn area for agent placement.
+    pub spawn_area: SpawnArea,
+    /// Configuration of the datetime system.
+    pub datetime_config: DateTimeConfig,
+    /// Date and time of the snapshot.
+    pub date_time_manager: DateTimeManager,
+    /// Work lists, waiting actions and farm entity stages.
+    pub task_manager: TaskManager,
+    /// Events of the episode up to the snapshot.
+    pub event_log: EventLog,
+}
+
+impl EnvSnapshot {
+    /// Saves the snapshot as JSON to the given path.
+    pub fn save_json_file<P: AsRef<Path>>(&self, file_path: P) -> Result<(), SnapshotError> {
+        let path = file_path.as_ref();
+        let json = serde_json::to_string(self).map_err(|e| SnapshotError::Json(e.to_string()))?;
+        std::fs::write(path, json).map_err(|e| SnapshotError::Io(format!("{path:?}: {e}")))
+    }
+
+    /// Loads a snapshot from a JSON file at the given path.
+    /// Fails if the file can't be read or parsed, or was saved with another snapshot version.
+    pub fn from_json_file<P: AsRef<Path>>(file_path: P) -> Result<Self, SnapshotError> {
+        let path = file_path.as_ref();
+        let data = std::fs::read_to_string(path)
+            .map_err(|e| SnapshotError::Io(format!("{path:?}: {e}")))?;
+        let value: serde_json::Value =
+            serde_json::from_str(&data).map_err(|e| SnapshotError::Json(e.to_string()))?;
+
+        // Check the version before parsing the rest, the format may differ between versions
+        let version = value
+            .get("version")
+            .and_then(|v| v.as_u64())
+            .ok_or_else(|| SnapshotError::Json("missing field `version`".to_string()))?;
+        if version != ENV_SNAPSHOT_VERSION as u64 {
+            return Err(SnapshotError::UnsupportedVersion(version as u32));
+        }
+
+        serde_json::from_value(value).map_err(|e| SnapshotError::Json(e.to_string()))
+    }
+}
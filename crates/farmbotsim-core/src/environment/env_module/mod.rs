@@ -2,3 +2,6 @@
 
 pub mod env;
 pub mod env_config;
+pub mod env_snapshot;
+pub mod snapshot_error;
+pub mod validation;
@@ -0,0 +1,24 @@
+/// Represents errors that can occur while saving or loading an env snapshot.
+#[derive(Debug)]
+pub enum SnapshotError {
+    Io(String),
+    Json(String),
+    UnsupportedVersion(u32),
+}
+
+impl std::fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SnapshotError::Io(msg) => {
+                write!(f, "Snapshot file error: {msg}")
+            }
+            SnapshotError::Json(msg) => {
+                write!(f, "Snapshot JSON error: {msg}")
+            }
+            SnapshotError::UnsupportedVersion(version) => {
+                write!(f, "Unsupported snapshot version: {version}")
+            }
+        }
+    }
+}
+impl std::error::Error for SnapshotError {}
@@ -0,0 +1,421 @@
+use egui::Pos2;
+
+use crate::{
+    agent_module::agent_config::AgentConfig,
+    battery_module::{battery::Battery, battery_config::BatteryConfig},
+    environment::{
+        datetime::DateTimeManager,
+        env_module::env_config::{EnvConfig, FleetGroup, n_agents, planning_radius},
+        farm_entity_module::{
+            farm_entity::FarmEntity, farm_entity_action::FarmEntityAction,
+            farm_entity_plan::FarmEntityPlan,
+        },
+        field_config::{FieldConfig, VariantFieldConfig},
+        scene_config::SceneConfig,
+        spawn_area_module::spawn_area::SpawnArea,
+        station_module::station::Station,
+    },
+    movement_module::movement::Movement,
+    path_finding_module::{
+        path_finding::PathFinding, path_planner::PathPlannerKind, visibility_graph::VisibilityGraph,
+    },
+    task_module::task_manager_config::TaskManagerConfig,
+    units::length::Length,
+    utilities::utils::{load_json, polygons_overlap},
+};
+
+/// Represents a problem found while validating a scenario.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationIssue {
+    /// A config file can't be loaded or holds an invalid value.
+    Config(String),
+    /// A battery charging data file is missing or unreadable.
+    MissingBatteryData(String),
+    /// A farm entity plan doesn't match the type of the field using it.
+    IncompatiblePlan {
+        field_id: u32,
+        plan_path: String,
+        msg: String,
+    },
+    /// No agent of the fleet has all capabilities a farm entity action requires.
+    NoCapableAgent {
+        plan_path: String,
+        action_name: String,
+        capabilities: Vec<String>,
+    },
+    /// Two fields cover the same area.
+    OverlappingFields(u32, u32),
+    /// The spawn area intersects a row obstacle.
+    SpawnAreaIntersectsObstacle,
+    /// A user obstacle or no-go zone has fewer than three points.
+    DegeneratePolygon(String),
+    /// A user obstacle intersects a field.
+    ObstacleInField { obstacle: u32, field_id: u32 },
+    /// No path leads from a spawn point, station slot or queue position to a crop or row end.
+    UnreachableFarmEntity {
+        field_id: u32,
+        farm_entity_id: u32,
+        from: String,
+    },
+    /// No path leads from a spawn point to a station slot or queue position.
+    UnreachableStation(u32),
+}
+
+impl std::fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationIssue::Config(msg) => write!(f, "{msg}"),
+            ValidationIssue::MissingBatteryData(msg) => write!(f, "Missing battery data: {msg}"),
+            ValidationIssue::IncompatiblePlan {
+                field_id,
+                plan_path,
+                msg,
+            } => write!(
+                f,
+                "Field {field_id} uses incompatible plan \"{plan_path}\": {msg}"
+            ),
+            ValidationIssue::NoCapableAgent {
+                plan_path,
+                action_name,
+                capabilities,
+            } => write!(
+                f,
+                "No agent is capable of action '{action_name}' of plan \"{plan_path}\" requiring [{}]",
+                capabilities.join(", ")
+            ),
+            ValidationIssue::OverlappingFields(a, b) => write!(f, "Fields {a} and {b} overlap"),
+            ValidationIssue::SpawnAreaIntersectsObstacle => {
+                write!(f, "Spawn area intersects an obstacle")
+            }
+            ValidationIssue::DegeneratePolygon(name) => {
+                write!(f, "{name} has fewer than three points")
+            }
+            ValidationIssue::ObstacleInField { obstacle, field_id } => {
+                write!(f, "Obstacle {obstacle} intersects field {field_id}")
+            }
+            ValidationIssue::UnreachableFarmEntity {
+                field_id,
+                farm_entity_id,
+                from,
+            } => write!(
+                f,
+                "Farm entity {farm_entity_id} in field {field_id} is unreachable from the {from}"
+            ),
+            ValidationIssue::UnreachableStation(id) => {
+                write!(f, "Station {id} is unreachable from a spawn point")
+            }
+        }
+    }
+}
+
+/// Validates all configs referenced by the environment config and returns the found issues.
+pub fn validate(config: &EnvConfig) -> Vec<ValidationIssue> {
+    let mut issues = vec![];
+    let mut agent_capabilities = vec![];
+
+    for group in &config.fleet {
+        match AgentConfig::try_from_json_file(&group.agent_config_path) {
+            Ok(agent_config) => {
+                if group.n_agents > 0 {
+                    agent_capabilities.push(agent_config.capabilities.clone());
+                }
+                let efficiency = agent_config.power_model.drivetrain_efficiency;
+                if !(efficiency > 0.0 && efficiency <= 1.0) {
+                    issues.push(ValidationIssue::Config(format!(
+                        "Drivetrain efficiency {efficiency} of \"{}\" is outside 0..=1",
+                        group.agent_config_path
+                    )));
+                }
+                if let Err(e) = Movement::try_from_json_file(agent_config.movement) {
+                    issues.push(ValidationIssue::Config(e.to_string()));
+                }
+                match BatteryConfig::try_from_json_file(agent_config.battery) {
+                    Ok(battery_config) => {
+                        if let Err(e) = Battery::try_from_config(battery_config, 0.0) {
+                            issues.push(ValidationIssue::MissingBatteryData(e.to_string()));
+                        }
+                    }
+                    Err(e) => issues.push(ValidationIssue::Config(e.to_string())),
+                }
+            }
+            Err(e) => issues.push(ValidationIssue::Config(e.to_string())),
+        }
+        if let Some(soc) = group.start_soc
+            && !(0.0..=100.0).contains(&soc)
+        {
+            issues.push(ValidationIssue::Config(format!(
+                "Start state of charge {soc} of the agents of \"{}\" is outside 0..=100 %",
+                group.agent_config_path
+            )));
+        }
+    }
+
+    match load_json::<SceneConfig, _>(&config.scene_config_path) {
+        Ok(scene_config) => match load_json::<FieldConfig, _>(&scene_config.field_config_path) {
+            Ok(field_config) => {
+                issues.extend(validate_scene(&scene_config, &field_config, &config.fleet));
+                issues.extend(validate_capabilities(&field_config, &agent_capabilities));
+            }
+            Err(e) => issues.push(ValidationIssue::Config(e.to_string())),
+        },
+        Err(e) => issues.push(ValidationIssue::Config(e.to_string())),
+    }
+
+    if let Err(e) = load_json::<TaskManagerConfig, _>(&config.task_manager_config_path) {
+        issues.push(ValidationIssue::Config(e.to_string()));
+    }
+    if let Err(e) = DateTimeManager::try_from_config(config.datetime_config.clone()) {
+        issues.push(ValidationIssue::Config(e.to_string()));
+    }
+
+    issues
+}
+
+/// Validates a scene together with its field and returns the found issues.
+/// Reachability is checked for the largest agent of the `groups`, from the spawn areas of the scene
+/// and of the `groups`, station queues as long as the agents of the `groups` can make them.
+pub fn validate_scene(
+    scene_config: &SceneConfig,
+    field_config: &FieldConfig,
+    groups: &[FleetGroup],
+) -> Vec<ValidationIssue> {
+    let mut issues = validate_field(field_config);
+
+    if let PathPlannerKind::Grid { cell_size, .. } = scene_config.path_planner
+        && cell_size <= Length::ZERO
+    {
+        issues.push(ValidationIssue::Config(format!(
+            "Grid cell size {cell_size} of the path planner is not positive"
+        )));
+    }
+
+    let spawn_area = SpawnArea::from_config(scene_config.spawn_area_config.clone());
+    let obstacles = field_config.get_obstacles();
+    let spawn_corners = spawn_area.corners();
+    if obstacles
+        .iter()
+        .any(|obstacle| polygons_overlap(&spawn_corners, &obstacle.points))
+    {
+        issues.push(ValidationIssue::SpawnAreaIntersectsObstacle);
+    }
+
+    // Agent configs that can't be loaded are reported by `validate`
+    let planning_radius = planning_radius(groups).unwrap_or(Length::ZERO);
+    let mut visibility_graph = VisibilityGraph::new(
+        &field_config.get_inflated_graph_points(planning_radius),
+        field_config.get_inflated_obstacles(planning_radius),
+    );
+    let mut is_reachable =
+        |start: Pos2, target: Pos2| visibility_graph.find_path(start, target).is_some();
+
+    let mut spawn_points = vec![];
+    for spawn_area in std::iter::once(spawn_area).chain(
+        groups
+            .iter()
+            .filter_map(|group| group.spawn_area.clone())
+            .map(SpawnArea::from_config),
+    ) {
+        spawn_points.push(spawn_area.center());
+        spawn_points.extend(spawn_area.corners());
+    }
+    let n_agents = n_agents(groups);
+
+    // Agents travel to the field from every spawn point, slot and queue position
+    let mut origins: Vec<(String, Pos2)> = spawn_points
+        .iter()
+        .map(|&point| {
+            (
+                format!("spawn point ({:.2}, {:.2})", point.x, point.y),
+                point,
+            )
+        })
+        .collect();
+    for (i, station_config) in scene_config.station_configs.iter().enumerate() {
+        if let Some(power_budget) = &station_config.power_budget
+            && let Err(e) = power_budget
+                .validate()
+                .and_then(|_| power_budget.clone().try_load_irradiance())
+        {
+            issues.push(ValidationIssue::Config(format!("Station {i}: {e}")));
+        }
+        let station = Station::from_config(i as u32, egui::Color32::WHITE, station_config.clone());
+        let mut targets: Vec<(String, Pos2)> = (0..station.n_slots as usize)
+            .filter_map(|slot| {
+                let pose = station.get_pose_for_slot(slot)?;
+                Some((format!("slot {slot} of station {i}"), pose.position))
+            })
+            .collect();
+        // At least the first queue position, agents only queue when all slots are taken
+        let n_queue_positions = n_agents.saturating_sub(station.n_slots).max(1);
+        targets.extend((0..n_queue_positions as usize).map(|queue_index| {
+            (
+                format!("queue position {queue_index} of station {i}"),
+                station.get_waiting_pose(queue_index).position,
+            )
+        }));
+        let is_station_reachable = targets.iter().all(|(_, target)| {
+            spawn_points
+                .iter()
+                .all(|&spawn_point| is_reachable(spawn_point, *target))
+        });
+        if !is_station_reachable {
+            issues.push(ValidationIssue::UnreachableStation(i as u32));
+        }
+        origins.extend(targets);
+    }
+
+    if let Ok(farm_entities) = field_config.try_get_farm_entities() {
+        let mut farm_entities: Vec<_> = farm_entities.values().collect();
+        farm_entities.sort_by_key(|farm_entity| farm_entity.get_id());
+        for farm_entity in farm_entities {
+            let (field_id, targets) = match farm_entity {
+                FarmEntity::Crop(crop) => (crop.field_id, vec![crop.position]),
+                FarmEntity::Row(row) => (
+                    row.field_id,
+                    vec![row.path[0], row.path[row.path.len() - 1]],
+                ),
+            };
+            let unreachable_from = origins
+                .iter()
+                .find(|(_, origin)| !targets.iter().all(|&target| is_reachable(*origin, target)));
+            if let Some((from, _)) = unreachable_from {
+                issues.push(ValidationIssue::UnreachableFarmEntity {
+                    field_id,
+                    farm_entity_id: farm_entity.get_id(),
+                    from: from.clone(),
+                });
+            }
+        }
+    }
+
+    issues
+}
+
+/// Validates the fields and their farm entity plans and returns the found issues.
+pub fn validate_field(field_config: &FieldConfig) -> Vec<ValidationIssue> {
+    let mut issues = vec![];
+
+    for (i, config) in field_config.configs.iter().enumerate() {
+        let plan_path = config.farm_entity_plan_path();
+        match FarmEntityPlan::try_from_json_file(plan_path) {
+            Ok(plan) => {
+                if let Some(msg) = plan_incompatibility(config, &plan) {
+                    issues.push(ValidationIssue::IncompatiblePlan {
+                        field_id: i as u32,
+                        plan_path: plan_path.to_string(),
+                        msg,
+                    });
+                }
+            }
+            Err(e) => issues.push(ValidationIssue::Config(e.to_string())),
+        }
+    }
+
+    let bounds: Vec<Vec<Pos2>> = field_config.configs.iter().map(|c| c.bounds()).collect();
+    for i in 0..bounds.len() {
+        for j in (i + 1)..bounds.len() {
+            if polygons_overlap(&bounds[i], &bounds[j]) {
+                issues.push(ValidationIssue::OverlappingFields(i as u32, j as u32));
+            }
+        }
+    }
+
+    for (i, obstacle) in field_config.obstacles.iter().enumerate() {
+        if obstacle.points.len() < 3 {
+            issues.push(ValidationIssue::DegeneratePolygon(format!("Obstacle {i}")));
+        }
+        for (field_id, field_bounds) in bounds.iter().enumerate() {
+            if polygons_overlap(field_bounds, &obstacle.points) {
+                issues.push(ValidationIssue::ObstacleInField {
+                    obstacle: i as u32,
+                    field_id: field_id as u32,
+                });
+            }
+        }
+    }
+    for (i, zone) in field_config.no_go_zones.iter().enumerate() {
+        if zone.points.len() < 3 {
+            issues.push(ValidationIssue::DegeneratePolygon(format!(
+                "No-go zone {i}"
+            )));
+        }
+    }
+
+    issues
+}
+
+/// Checks that every action of the plans used by the fields can be performed by an agent with
+/// one of the given capability sets and returns the found issues.
+pub fn validate_capabilities(
+    field_config: &FieldConfig,
+    agent_capabilities: &[Vec<String>],
+) -> Vec<ValidationIssue> {
+    let mut issues = vec![];
+    let mut checked_plan_paths = vec![];
+
+    for config in &field_config.configs {
+        let plan_path = config.farm_entity_plan_path();
+        if checked_plan_paths.contains(&plan_path) {
+            continue;
+        }
+        checked_plan_paths.push(plan_path);
+        // Plans that can't be loaded are reported by `validate_field`
+        let Ok(plan) = FarmEntityPlan::try_from_json_file(plan_path) else {
+            continue;
+        };
+        for action in &plan.schedule {
+            let (action_name, required_capabilities) = match action {
+                FarmEntityAction::Point {
+                    action_name,
+                    required_capabilities,
+                    ..
+                }
+                | FarmEntityAction::Line {
+                    action_name,
+                    required_capabilities,
+                    ..
+                } => (action_name, required_capabilities),
+                FarmEntityAction::Wait { .. } => continue,
+            };
+            let is_capable = |capabilities: &Vec<String>| {
+                required_capabilities
+                    .iter()
+                    .all(|capability| capabilities.contains(capability))
+            };
+            if !agent_capabilities.iter().any(is_capable) {
+                issues.push(ValidationIssue::NoCapableAgent {
+                    plan_path: plan_path.to_string(),
+                    action_name: action_name.clone(),
+                    capabilities: required_capabilities.clone(),
+                });
+            }
+        }
+    }
+
+    issues
+}
+
+/// Returns why the plan can't be used for the field, if it can't.
+fn plan_incompatibility(config: &VariantFieldConfig, plan: &FarmEntityPlan) -> Option<String> {
+    let field_type = match config {
+        VariantFieldConfig::Line(_) => "line",
+        VariantFieldConfig::Point(_) => "point",
+    };
+    if plan.type_ != field_type {
+        return Some(format!(
+            "plan type is '{}' but the field type is '{field_type}'",
+            plan.type_
+        ));
+    }
+    plan.schedule
+        .iter()
+        .find_map(|action| match (config, action) {
+            (VariantFieldConfig::Line(_), FarmEntityAction::Point { action_name, .. }) => {
+                Some(format!("point action '{action_name}' in line field"))
+            }
+            (VariantFieldConfig::Point(_), FarmEntityAction::Line { action_name, .. }) => {
+                Some(format!("line action '{action_name}' in point field"))
+            }
+            _ => None,
+        })
+}
@@ -1,15 +1,16 @@
 use egui::Pos2;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     environment::farm_entity_module::{
         farm_entity_action::FarmEntityAction,
         farm_entity_action_instance::FarmEntityActionInstance, farm_entity_plan::FarmEntityPlan,
     },
-    logger::log_error_and_panic,
+    error::FarmbotSimError,
 };
 
 /// Represents a point farm entity
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
 pub struct Crop {
     pub id: u32,
     pub field_id: u32,
@@ -24,6 +25,18 @@ impl Crop {
     /// Creates a new `Crop` with explicit parameters.
     /// Panics if the plan contains line actions (unsupported for point crops).
     pub fn new(id: u32, field_id: u32, row_id: u32, position: Pos2, plan: FarmEntityPlan) -> Self {
+        Self::try_new(id, field_id, row_id, position, plan).unwrap_or_else(|e| e.log_and_panic())
+    }
+
+    /// Creates a new `Crop` with explicit parameters.
+    /// Returns an error if the plan contains line actions (unsupported for point crops).
+    pub fn try_new(
+        id: u32,
+        field_id: u32,
+        row_id: u32,
+        position: Pos2,
+        plan: FarmEntityPlan,
+    ) -> Result<Self, FarmbotSimError> {
         let mut stages = vec![];
         for action in &plan.schedule {
             let data = match action {
@@ -31,7 +44,7 @@ impl Crop {
                     action_name,
                     duration,
                     power,
-                    ..
+                    required_capabilities,
                 } => FarmEntityActionInstance::point(
                     id,
                     field_id,
@@ -40,18 +53,19 @@ impl Crop {
                     *duration,
                     *power,
                     action_name.clone(),
+                    required_capabilities.clone(),
                 ),
                 FarmEntityAction::Wait { duration, .. } => {
                     FarmEntityActionInstance::wait(id, *duration)
                 }
-                _ => {
-                    let msg = "Can't have line action for point crop";
-                    log_error_and_panic(msg)
+                FarmEntityAction::Line { action_name, .. } => {
+                    let msg = format!("Can't have line action '{action_name}' for point crop");
+                    return Err(FarmbotSimError::invalid_field("plan", msg));
                 }
             };
             stages.push(data);
         }
-        Self {
+        Ok(Self {
             id,
             field_id,
             row_id,
@@ -59,6 +73,6 @@ impl Crop {
             stage: None,
             plan,
             stages,
-        }
+        })
     }
 }
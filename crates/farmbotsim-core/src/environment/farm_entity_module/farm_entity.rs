@@ -1,10 +1,12 @@
+use serde::{Deserialize, Serialize};
+
 use crate::environment::farm_entity_module::{
     crop::Crop, farm_entity_action_instance::FarmEntityActionInstance, farm_stages::FarmStages,
     row::Row,
 };
 
 /// Represents a farm entity, either a point Crop or a line Row.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum FarmEntity {
     Crop(Crop),
     Row(Row),
@@ -6,6 +6,7 @@ use crate::units::{duration::Duration, linear_velocity::LinearVelocity, power::P
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum FarmEntityAction {
     /// A point action with a fixed duration and power (stationary action).
+    /// Only agents with all `required_capabilities` can perform it, likewise for line actions.
     Point {
         #[serde(rename = "action_name")]
         action_name: String,
@@ -13,6 +14,8 @@ pub enum FarmEntityAction {
         duration: Duration,
         #[serde(rename = "power")]
         power: Power,
+        #[serde(rename = "required_capabilities", default)]
+        required_capabilities: Vec<String>,
     },
     /// A line action representing movement along a path with velocity and power (moving action).
     Line {
@@ -22,6 +25,8 @@ pub enum FarmEntityAction {
         velocity: LinearVelocity,
         #[serde(rename = "power")]
         power: Power,
+        #[serde(rename = "required_capabilities", default)]
+        required_capabilities: Vec<String>,
     },
     /// A wait action with a specified duration.
     Wait {
@@ -38,6 +43,7 @@ impl FarmEntityAction {
             action_name: "point".to_string(),
             duration: Duration::seconds(40.0),
             power: Power::watts(100.0),
+            required_capabilities: vec![],
         }
     }
     /// Returns a default line action with preset velocity and power.
@@ -46,6 +52,7 @@ impl FarmEntityAction {
             action_name: "line".to_string(),
             velocity: LinearVelocity::kilometers_per_hour(2.0),
             power: Power::watts(150.0),
+            required_capabilities: vec![],
         }
     }
     /// Returns a default wait action with preset duration.
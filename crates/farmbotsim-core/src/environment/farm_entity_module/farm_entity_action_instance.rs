@@ -1,4 +1,5 @@
 use egui::Pos2;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     movement_module::pose::Pose,
@@ -7,7 +8,7 @@ use crate::{
 };
 
 /// An instance of a farm entity action, linked to a specific entity.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum FarmEntityActionInstance {
     /// A point action instance with position, duration, power, and identifiers.
     Point {
@@ -18,6 +19,7 @@ pub enum FarmEntityActionInstance {
         duration: Duration,
         power: Power,
         action_name: String,
+        required_capabilities: Vec<String>,
     },
     /// A line action instance with a path, velocity, power, and identifiers.
     Line {
@@ -27,6 +29,7 @@ pub enum FarmEntityActionInstance {
         velocity: LinearVelocity,
         power: Power,
         action_name: String,
+        required_capabilities: Vec<String>,
     },
     /// A wait action instance with duration and identifier.
     Wait {
@@ -37,6 +40,7 @@ pub enum FarmEntityActionInstance {
 }
 impl FarmEntityActionInstance {
     /// Creates a new point action instance.
+    #[allow(clippy::too_many_arguments)]
     pub fn point(
         id: u32,
         field_id: u32,
@@ -45,6 +49,7 @@ impl FarmEntityActionInstance {
         duration: Duration,
         power: Power,
         action_name: String,
+        required_capabilities: Vec<String>,
     ) -> Self {
         Self::Point {
             id,
@@ -54,6 +59,7 @@ impl FarmEntityActionInstance {
             duration,
             power,
             action_name,
+            required_capabilities,
         }
     }
     /// Creates a new line action instance.
@@ -64,6 +70,7 @@ impl FarmEntityActionInstance {
         velocity: LinearVelocity,
         power: Power,
         action_name: String,
+        required_capabilities: Vec<String>,
     ) -> Self {
         Self::Line {
             id,
@@ -72,6 +79,7 @@ impl FarmEntityActionInstance {
             velocity,
             power,
             action_name,
+            required_capabilities,
         }
     }
     /// Creates a new wait action instance.
@@ -95,6 +103,7 @@ impl FarmEntityActionInstance {
                 duration,
                 power,
                 action_name,
+                required_capabilities,
             } => Some(Task::Stationary {
                 id: task_id,
                 pose: Pose::new(*pos, Angle::ZERO),
@@ -105,6 +114,7 @@ impl FarmEntityActionInstance {
                 line_id: *line_id,
                 power: *power,
                 info: action_name.clone(),
+                required_capabilities: required_capabilities.clone(),
             }),
             FarmEntityActionInstance::Line {
                 id,
@@ -113,6 +123,7 @@ impl FarmEntityActionInstance {
                 velocity,
                 power,
                 action_name,
+                required_capabilities,
             } => {
                 let path = path
                     .iter()
@@ -130,6 +141,7 @@ impl FarmEntityActionInstance {
                     farm_entity_id: *id,
                     power: *power,
                     info: action_name.clone(),
+                    required_capabilities: required_capabilities.clone(),
                 })
             }
             _ => None,
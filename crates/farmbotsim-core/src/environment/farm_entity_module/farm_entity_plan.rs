@@ -3,7 +3,8 @@ use serde::{Deserialize, Serialize};
 use crate::{
     cfg::{DEFAULT_LINE_FARM_ENTITY_PLAN_PATH, DEFAULT_POINT_FARM_ENTITY_PLAN_PATH},
     environment::farm_entity_module::farm_entity_action::FarmEntityAction,
-    utilities::utils::load_json_or_panic,
+    error::FarmbotSimError,
+    utilities::utils::{load_json, load_json_or_panic},
 };
 
 /// Represents a plan for a farm entity, detailing its crop type, action cycle, and scheduled actions.
@@ -27,6 +28,10 @@ impl FarmEntityPlan {
     pub fn from_json_file(path: &str) -> Self {
         load_json_or_panic(path)
     }
+    /// Loads a farm entity plan from a JSON file.
+    pub fn try_from_json_file(path: &str) -> Result<Self, FarmbotSimError> {
+        load_json(path)
+    }
     /// Loads the default point-type farm entity plan, panicking on failure.
     pub fn default_point() -> Self {
         Self::from_json_file(DEFAULT_POINT_FARM_ENTITY_PLAN_PATH)
@@ -1,15 +1,16 @@
 use egui::Pos2;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     environment::farm_entity_module::{
         farm_entity_action::FarmEntityAction,
         farm_entity_action_instance::FarmEntityActionInstance, farm_entity_plan::FarmEntityPlan,
     },
-    logger::log_error_and_panic,
+    error::FarmbotSimError,
 };
 
 /// Represents a line farm entity
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
 pub struct Row {
     pub id: u32,
     pub field_id: u32,
@@ -23,6 +24,17 @@ impl Row {
     /// Creates a new `Row` with explicit parameters.
     /// Panics if the plan contains point actions (unsupported for line rows).
     pub fn new(id: u32, field_id: u32, path: Vec<Pos2>, plan: FarmEntityPlan) -> Self {
+        Self::try_new(id, field_id, path, plan).unwrap_or_else(|e| e.log_and_panic())
+    }
+
+    /// Creates a new `Row` with explicit parameters.
+    /// Returns an error if the plan contains point actions (unsupported for line rows).
+    pub fn try_new(
+        id: u32,
+        field_id: u32,
+        path: Vec<Pos2>,
+        plan: FarmEntityPlan,
+    ) -> Result<Self, FarmbotSimError> {
         let mut stages = vec![];
         for action in &plan.schedule {
             let data = match action {
@@ -30,7 +42,7 @@ impl Row {
                     action_name,
                     velocity,
                     power,
-                    ..
+                    required_capabilities,
                 } => FarmEntityActionInstance::line(
                     id,
                     field_id,
@@ -38,24 +50,25 @@ impl Row {
                     *velocity,
                     *power,
                     action_name.clone(),
+                    required_capabilities.clone(),
                 ),
                 FarmEntityAction::Wait { duration, .. } => {
                     FarmEntityActionInstance::wait(id, *duration)
                 }
-                _ => {
-                    let msg = "Can't have point action for line crop";
-                    log_error_and_panic(msg)
+                FarmEntityAction::Point { action_name, .. } => {
+                    let msg = format!("Can't have point action '{action_name}' for line crop");
+                    return Err(FarmbotSimError::invalid_field("plan", msg));
                 }
             };
             stages.push(data);
         }
-        Self {
+        Ok(Self {
             id,
             field_id,
             path,
             stage: None,
             plan,
             stages,
-        }
+        })
     }
 }
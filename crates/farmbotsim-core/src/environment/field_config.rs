@@ -3,18 +3,19 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use crate::{
-    cfg::{DEFAULT_LINE_FARM_ENTITY_PLAN_PATH, DEFAULT_POINT_FARM_ENTITY_PLAN_PATH},
+    cfg::{
+        DEFAULT_LINE_FARM_ENTITY_PLAN_PATH, DEFAULT_POINT_FARM_ENTITY_PLAN_PATH,
+        GRAPH_POINT_CLEARANCE,
+    },
     environment::{
         farm_entity_module::{
             crop::Crop, farm_entity::FarmEntity, farm_entity_plan::FarmEntityPlan, row::Row,
         },
-        obstacle::Obstacle,
+        obstacle::{NoGoZone, Obstacle},
     },
+    error::FarmbotSimError,
     units::{angle::Angle, length::Length},
-    utilities::{
-        utils::{generate_colors, load_json_or_panic},
-        vec2::Vec2Rotate,
-    },
+    utilities::{utils::generate_colors, vec2::Vec2Rotate},
 };
 
 /// Configuration for a line-based field layout.
@@ -129,17 +130,69 @@ pub enum VariantFieldConfig {
     Line(LineFieldConfig),
     Point(PointFieldConfig),
 }
+impl VariantFieldConfig {
+    /// Returns the corners of the area enclosed by the row obstacles of the field.
+    pub fn bounds(&self) -> Vec<Pos2> {
+        let height_offset = 0.2;
+        let (left_top_pos, angle, n_lines, line_spacing, length) = match self {
+            VariantFieldConfig::Line(c) => (
+                c.left_top_pos,
+                c.angle,
+                c.n_lines,
+                c.line_spacing.to_base_unit(),
+                c.length.to_base_unit(),
+            ),
+            VariantFieldConfig::Point(c) => (
+                c.left_top_pos,
+                c.angle,
+                c.n_lines,
+                c.line_spacing.to_base_unit(),
+                (c.n_points_per_line - 1) as f32 * c.point_spacing.to_base_unit(),
+            ),
+        };
+        let min_x = -line_spacing / 2.0;
+        let max_x = min_x + n_lines as f32 * line_spacing;
+        let min_y = -height_offset;
+        let max_y = length + height_offset;
+        [
+            Vec2::new(min_x, min_y),
+            Vec2::new(max_x, min_y),
+            Vec2::new(max_x, max_y),
+            Vec2::new(min_x, max_y),
+        ]
+        .iter()
+        .map(|corner| left_top_pos + corner.rotate(angle))
+        .collect()
+    }
+    /// Returns the farm entity plan path of the field.
+    pub fn farm_entity_plan_path(&self) -> &str {
+        match self {
+            VariantFieldConfig::Line(c) => &c.farm_entity_plan_path,
+            VariantFieldConfig::Point(c) => &c.farm_entity_plan_path,
+        }
+    }
+}
 
 /// Container for multiple field configurations.
 #[derive(PartialEq, Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct FieldConfig {
     pub configs: Vec<VariantFieldConfig>,
+    /// Obstacles outside the fields such as sheds, trees, ponds or fences.
+    #[serde(default)]
+    pub obstacles: Vec<Obstacle>,
+    /// Areas avoided by path planning unless going around them is too long.
+    #[serde(default)]
+    pub no_go_zones: Vec<NoGoZone>,
 }
 
 impl FieldConfig {
     /// Creates a new `FieldConfig` from a vector of `VariantFieldConfig`s.
     pub fn new(configs: Vec<VariantFieldConfig>) -> Self {
-        Self { configs }
+        Self {
+            configs,
+            obstacles: vec![],
+            no_go_zones: vec![],
+        }
     }
 
     /// Generates obstacle polygons representing physical obstacles for all configured fields.
@@ -194,15 +247,31 @@ impl FieldConfig {
                 }
             }
         }
+        obstacles.extend(self.obstacles.iter().map(Obstacle::closed));
         obstacles
     }
 
+    /// Generates the obstacle polygons inflated by `radius`, used for path planning.
+    pub fn get_inflated_obstacles(&self, radius: Length) -> Vec<Obstacle> {
+        self.get_obstacles()
+            .iter()
+            .map(|obstacle| obstacle.inflated(radius))
+            .collect()
+    }
+
     /// Returns key points for path finding.
     pub fn get_graph_points(&self) -> Vec<Pos2> {
+        self.get_inflated_graph_points(Length::ZERO)
+    }
+
+    /// Returns key points for path finding around obstacles inflated by `radius`.
+    /// Row ends get points 0.5 m beyond the inflated rows, obstacles and no-go zones are
+    /// surrounded by points `GRAPH_POINT_CLEARANCE` outside them.
+    pub fn get_inflated_graph_points(&self, radius: Length) -> Vec<Pos2> {
         let mut points = Vec::new();
 
         for config_variant in &self.configs {
-            let offset = Length::meters(0.5);
+            let offset = Length::meters(0.5) + radius;
             match config_variant {
                 VariantFieldConfig::Line(c) => {
                     let ls_val = c.line_spacing.to_base_unit();
@@ -239,11 +308,28 @@ impl FieldConfig {
                 }
             }
         }
+
+        let polygons = self.obstacles.iter().cloned().chain(
+            self.no_go_zones
+                .iter()
+                .map(|zone| Obstacle::new(zone.points.clone())),
+        );
+        for polygon in polygons {
+            points.extend(polygon.inflated(radius + GRAPH_POINT_CLEARANCE).points);
+        }
         points
     }
 
     /// Constructs a map of farm entities (rows or crops) keyed by unique IDs.
+    /// Panics if a farm entity plan can't be loaded or doesn't match its field type.
     pub fn get_farm_entities(&self) -> HashMap<u32, FarmEntity> {
+        self.try_get_farm_entities()
+            .unwrap_or_else(|e| e.log_and_panic())
+    }
+
+    /// Constructs a map of farm entities (rows or crops) keyed by unique IDs.
+    /// Returns an error if a farm entity plan can't be loaded or doesn't match its field type.
+    pub fn try_get_farm_entities(&self) -> Result<HashMap<u32, FarmEntity>, FarmbotSimError> {
         let mut farm_entities = HashMap::new();
 
         let mut id_counter = 0;
@@ -252,7 +338,8 @@ impl FieldConfig {
             let field_id = n as u32;
             match config_variant {
                 VariantFieldConfig::Line(c) => {
-                    let farm_entity_plan = FarmEntityPlan::from_json_file(&c.farm_entity_plan_path);
+                    let farm_entity_plan =
+                        FarmEntityPlan::try_from_json_file(&c.farm_entity_plan_path)?;
                     let ls_val = c.line_spacing.value;
                     for i in 0..c.n_lines {
                         let path = vec![
@@ -261,13 +348,16 @@ impl FieldConfig {
                                 + Vec2::new(i as f32 * ls_val, c.length.to_base_unit())
                                     .rotate(c.angle),
                         ];
-                        let row = Row::new(id_counter, field_id, path, farm_entity_plan.clone());
+                        let row =
+                            Row::try_new(id_counter, field_id, path, farm_entity_plan.clone())
+                                .map_err(|e| e.in_file(&c.farm_entity_plan_path))?;
                         farm_entities.insert(id_counter, FarmEntity::Row(row));
                         id_counter += 1;
                     }
                 }
                 VariantFieldConfig::Point(c) => {
-                    let farm_entity_plan = FarmEntityPlan::from_json_file(&c.farm_entity_plan_path);
+                    let farm_entity_plan =
+                        FarmEntityPlan::try_from_json_file(&c.farm_entity_plan_path)?;
                     for i in 0..c.n_lines {
                         for j in 0..c.n_points_per_line {
                             let pos = c.left_top_pos
@@ -276,8 +366,14 @@ impl FieldConfig {
                                     c.point_spacing.to_base_unit() * j as f32,
                                 )
                                 .rotate(c.angle);
-                            let crop =
-                                Crop::new(id_counter, field_id, i, pos, farm_entity_plan.clone());
+                            let crop = Crop::try_new(
+                                id_counter,
+                                field_id,
+                                i,
+                                pos,
+                                farm_entity_plan.clone(),
+                            )
+                            .map_err(|e| e.in_file(&c.farm_entity_plan_path))?;
                             farm_entities.insert(id_counter, FarmEntity::Crop(crop));
                             id_counter += 1;
                         }
@@ -286,18 +382,19 @@ impl FieldConfig {
             }
         }
 
-        farm_entities
+        Ok(farm_entities)
     }
 
-    /// Checks if any farm entity plan contains a cycle.
+    /// Checks if any farm entity plan contains a cycle. Plans that can't be loaded are skipped.
     pub fn has_cycle_farm_entity_plan(&self) -> bool {
         for config in &self.configs {
             let plan_path = match config {
                 VariantFieldConfig::Line(data) => data.farm_entity_plan_path.clone(),
                 VariantFieldConfig::Point(data) => data.farm_entity_plan_path.clone(),
             };
-            let plan: FarmEntityPlan = load_json_or_panic(plan_path);
-            if plan.cycle.is_some() {
+            if let Ok(plan) = FarmEntityPlan::try_from_json_file(&plan_path)
+                && plan.cycle.is_some()
+            {
                 return true;
             }
         }
@@ -305,7 +402,7 @@ impl FieldConfig {
     }
 
     /// Calculates the total number of scheduled actions across all farm entities,
-    /// returns `None` if any plan contains cycles.
+    /// returns `None` if any plan contains cycles or can't be loaded.
     pub fn number_of_actions(&self) -> Option<u32> {
         if self.has_cycle_farm_entity_plan() {
             return None;
@@ -315,13 +412,13 @@ impl FieldConfig {
         for config in &self.configs {
             match config {
                 VariantFieldConfig::Line(data) => {
-                    let plan: FarmEntityPlan =
-                        load_json_or_panic(data.farm_entity_plan_path.clone());
+                    let plan =
+                        FarmEntityPlan::try_from_json_file(&data.farm_entity_plan_path).ok()?;
                     n_actions += data.n_lines * plan.schedule.len() as u32;
                 }
                 VariantFieldConfig::Point(data) => {
-                    let plan: FarmEntityPlan =
-                        load_json_or_panic(data.farm_entity_plan_path.clone());
+                    let plan =
+                        FarmEntityPlan::try_from_json_file(&data.farm_entity_plan_path).ok()?;
                     n_actions += data.n_lines * data.n_points_per_line * plan.schedule.len() as u32;
                 }
             }
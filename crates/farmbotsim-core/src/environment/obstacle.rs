@@ -1,7 +1,14 @@
-use egui::Pos2;
+use egui::{Pos2, Vec2};
+use serde::{Deserialize, Serialize};
+use std::f32::consts::{PI, TAU};
+
+use crate::{units::length::Length, utilities::utils::segment_length_in_polygon};
+
+/// Number of segments approximating the circle around each point when inflating.
+const INFLATION_SEGMENTS: u32 = 16;
 
 /// Represents a polygonal obstacle defined by a series of 2D points.
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
 pub struct Obstacle {
     pub points: Vec<Pos2>,
 }
@@ -11,4 +18,102 @@ impl Obstacle {
     pub fn new(points: Vec<Pos2>) -> Self {
         Self { points }
     }
+
+    /// Returns the obstacle grown by `radius` (Minkowski sum with a disc), approximated by the
+    /// convex hull of polygons enclosing circles around the points.
+    /// Non-convex obstacles grow from their convex hull, so the space in their concave parts,
+    /// e.g. inside an L-shaped obstacle, is blocked as well; split such obstacles into convex
+    /// ones to keep it reachable. The outline is closed by repeating the first point, as the
+    /// visibility graph only checks edges between consecutive points.
+    pub fn inflated(&self, radius: Length) -> Obstacle {
+        if radius <= Length::ZERO || self.points.is_empty() {
+            return self.clone();
+        }
+        // Polygon vertices lie outside the circle so the polygon contains it
+        let vertex_distance = radius.to_base_unit() / (PI / INFLATION_SEGMENTS as f32).cos();
+        let points: Vec<Pos2> = self
+            .points
+            .iter()
+            .flat_map(|p| {
+                (0..INFLATION_SEGMENTS).map(move |i| {
+                    let angle = TAU * i as f32 / INFLATION_SEGMENTS as f32;
+                    *p + Vec2::angled(angle) * vertex_distance
+                })
+            })
+            .collect();
+        let mut hull = convex_hull(points);
+        if let Some(&first) = hull.first() {
+            hull.push(first);
+        }
+        Obstacle::new(hull)
+    }
+
+    /// Returns the obstacle with the first point repeated at the end, if it isn't already.
+    pub fn closed(&self) -> Obstacle {
+        let mut points = self.points.clone();
+        if let (Some(&first), Some(&last)) = (points.first(), points.last())
+            && first != last
+        {
+            points.push(first);
+        }
+        Obstacle::new(points)
+    }
+}
+
+/// Polygonal area that can be crossed but is avoided by path planning.
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct NoGoZone {
+    pub points: Vec<Pos2>,
+    /// Extra cost of travelling inside the zone relative to the distance, 1.0 doubles the cost.
+    pub penalty: f32,
+}
+
+impl NoGoZone {
+    /// Creates a new `NoGoZone` from a list of 2D points and a penalty.
+    pub fn new(points: Vec<Pos2>, penalty: f32) -> Self {
+        Self { points, penalty }
+    }
+
+    /// Returns the extra cost in meters of travelling the segment from `a` to `b`.
+    pub fn penalty_cost(&self, a: Pos2, b: Pos2) -> f32 {
+        if self.penalty <= 0.0 || self.points.len() < 3 {
+            return 0.0;
+        }
+        self.penalty * segment_length_in_polygon(a, b, &self.points)
+    }
+}
+
+/// Returns the extra cost in meters of travelling the segment from `a` to `b` through all zones.
+pub fn no_go_penalty(zones: &[NoGoZone], a: Pos2, b: Pos2) -> f32 {
+    zones.iter().map(|zone| zone.penalty_cost(a, b)).sum()
+}
+
+/// Returns the convex hull of the points in counter-clockwise order (monotone chain).
+fn convex_hull(mut points: Vec<Pos2>) -> Vec<Pos2> {
+    points.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+    points.dedup();
+    if points.len() < 3 {
+        return points;
+    }
+    let cross = |o: Pos2, a: Pos2, b: Pos2| (a - o).x * (b - o).y - (a - o).y * (b - o).x;
+
+    let mut hull: Vec<Pos2> = vec![];
+    for pass in 0..2 {
+        let start = hull.len();
+        let ordered: Box<dyn Iterator<Item = &Pos2>> = if pass == 0 {
+            Box::new(points.iter())
+        } else {
+            Box::new(points.iter().rev())
+        };
+        for &p in ordered {
+            while hull.len() >= start + 2
+                && cross(hull[hull.len() - 2], hull[hull.len() - 1], p) <= 0.0
+            {
+                hull.pop();
+            }
+            hull.push(p);
+        }
+        hull.pop(); // last point starts the other chain
+    }
+    hull
 }
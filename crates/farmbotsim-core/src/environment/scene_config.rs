@@ -1,6 +1,9 @@
-use crate::environment::{
-    spawn_area_module::spawn_area_config::SpawnAreaConfig,
-    station_module::station_config::StationConfig,
+use crate::{
+    environment::{
+        spawn_area_module::spawn_area_config::SpawnAreaConfig,
+        station_module::station_config::StationConfig,
+    },
+    path_finding_module::path_planner::PathPlannerKind,
 };
 
 /// Configuration data for a scene.
@@ -12,4 +15,10 @@ pub struct SceneConfig {
     pub station_configs: Vec<StationConfig>,
     /// Configuration for the spawn area within the scene.
     pub spawn_area_config: SpawnAreaConfig,
+    /// Path planner used by the task manager.
+    #[serde(default)]
+    pub path_planner: PathPlannerKind,
+    /// Whether travel paths are smoothed into arcs the agents drive without stopping at corners.
+    #[serde(default)]
+    pub path_smoothing: bool,
 }
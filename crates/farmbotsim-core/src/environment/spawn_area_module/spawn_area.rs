@@ -1,12 +1,14 @@
-use egui::Pos2;
+use egui::{Pos2, Vec2};
+use serde::{Deserialize, Serialize};
 
 use crate::{
     environment::spawn_area_module::spawn_area_config::SpawnAreaConfig,
     units::{angle::Angle, length::Length},
+    utilities::vec2::Vec2Rotate,
 };
 
 /// Represents a spawn area with position, orientation, and size.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct SpawnArea {
     pub left_top_pos: Pos2,
     pub angle: Angle,
@@ -33,6 +35,18 @@ impl SpawnArea {
             height: self.height,
         }
     }
+    /// Returns the corners of the spawn area, starting at the left top position.
+    pub fn corners(&self) -> Vec<Pos2> {
+        let width = Vec2::new(self.width.to_base_unit(), 0.0).rotate(self.angle);
+        let height = Vec2::new(0.0, self.height.to_base_unit()).rotate(self.angle);
+        let ltp = self.left_top_pos;
+        vec![ltp, ltp + width, ltp + width + height, ltp + height]
+    }
+    /// Returns the center of the spawn area.
+    pub fn center(&self) -> Pos2 {
+        let diagonal = Vec2::new(self.width.to_base_unit(), self.height.to_base_unit());
+        self.left_top_pos + (diagonal / 2.0).rotate(self.angle)
+    }
 }
 
 impl Default for SpawnArea {
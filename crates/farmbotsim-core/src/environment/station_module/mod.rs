@@ -1,4 +1,6 @@
 //! Station module containing core station logic and configuration.
 
+pub mod power_budget;
+pub mod solar;
 pub mod station;
 pub mod station_config;
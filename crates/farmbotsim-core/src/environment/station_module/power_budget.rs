@@ -0,0 +1,228 @@
+use chrono::{NaiveDateTime, Timelike};
+use serde::{Deserialize, Serialize};
+
+use super::solar::SolarModel;
+use crate::{agent_module::agent::AgentId, error::FarmbotSimError, units::power::Power};
+
+/// How the power of a station is divided among the agents charging in its slots.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PowerSharePolicy {
+    /// Every agent gets the same share, power an agent doesn't need goes to the others.
+    #[default]
+    EqualShare,
+    /// Agents with the lowest state of charge are served first.
+    LowestSocFirst,
+    /// Agents are served in the order they got their slot.
+    Fifo,
+}
+
+impl PowerSharePolicy {
+    /// Returns the policy name as a string.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PowerSharePolicy::EqualShare => "EqualShare",
+            PowerSharePolicy::LowestSocFirst => "LowestSocFirst",
+            PowerSharePolicy::Fifo => "Fifo",
+        }
+    }
+}
+
+/// Where the power of a station comes from.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(tag = "type", content = "params")]
+pub enum PowerSource {
+    /// Constant power from the grid.
+    #[default]
+    Grid,
+    /// Fraction of the maximum power available at each full hour of the day (24 values),
+    /// e.g. of a solar panel, interpolated in between.
+    HourlyProfile(Vec<f32>),
+    /// Solar panel whose power follows the sun, capped at the maximum power.
+    Solar(SolarModel),
+}
+
+/// Power a charging agent would draw from a station in the current step.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PowerRequest {
+    pub agent_id: AgentId,
+    pub power: Power,
+    pub soc: f32,
+}
+
+/// Power a station can deliver to all of its slots together.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PowerBudget {
+    /// Maximum power shared by all slots.
+    pub max_power: Power,
+    /// How the power is divided among occupied slots.
+    pub policy: PowerSharePolicy,
+    /// Where the power comes from.
+    pub source: PowerSource,
+}
+impl Default for PowerBudget {
+    /// Returns a 500 W grid connection shared equally.
+    fn default() -> Self {
+        Self {
+            max_power: Power::watts(500.0),
+            policy: PowerSharePolicy::default(),
+            source: PowerSource::default(),
+        }
+    }
+}
+
+impl PowerBudget {
+    /// Returns an error if the maximum power is negative or the power source is invalid.
+    pub fn validate(&self) -> Result<(), FarmbotSimError> {
+        if self.max_power < Power::ZERO {
+            let msg = format!("{} must not be negative", self.max_power);
+            return Err(FarmbotSimError::invalid_field(
+                "power_budget.max_power",
+                msg,
+            ));
+        }
+        if let PowerSource::HourlyProfile(profile) = &self.source {
+            if profile.len() != 24 {
+                let msg = format!("Expected 24 values, found {}", profile.len());
+                return Err(FarmbotSimError::invalid_field("power_budget.source", msg));
+            }
+            if let Some(value) = profile.iter().find(|value| !(0.0..=1.0).contains(*value)) {
+                let msg = format!("{value} is outside 0..=1");
+                return Err(FarmbotSimError::invalid_field("power_budget.source", msg));
+            }
+        }
+        if let PowerSource::Solar(solar) = &self.source {
+            solar.validate()?;
+        }
+        Ok(())
+    }
+
+    /// Loads the irradiance series of a solar power source.
+    /// Returns an error if its file can't be read.
+    pub fn try_load_irradiance(&mut self) -> Result<(), FarmbotSimError> {
+        if let PowerSource::Solar(solar) = &mut self.source {
+            solar.try_load_irradiance()?;
+        }
+        Ok(())
+    }
+
+    /// Returns the power the station can deliver at the given time.
+    pub fn available_power(&self, time: &NaiveDateTime) -> Power {
+        match &self.source {
+            PowerSource::Grid => self.max_power,
+            PowerSource::HourlyProfile(profile) if profile.is_empty() => Power::ZERO,
+            PowerSource::HourlyProfile(profile) => {
+                let hour = time.hour() as usize % profile.len();
+                let next_hour = (hour + 1) % profile.len();
+                let t = (time.minute() * 60 + time.second()) as f32 / 3600.0;
+                self.max_power * (profile[hour] + (profile[next_hour] - profile[hour]) * t)
+            }
+            PowerSource::Solar(solar) => solar.power(time).min(self.max_power),
+        }
+    }
+
+    /// Divides `available` power among the requests by the share policy.
+    /// Requests must be in the order the agents got their slots.
+    /// Returns the power of each request, never more than requested.
+    pub fn allocate(&self, available: Power, requests: &[PowerRequest]) -> Vec<Power> {
+        let mut allocated = vec![Power::ZERO; requests.len()];
+        let mut remaining = available;
+        let mut order: Vec<usize> = (0..requests.len()).collect();
+        match self.policy {
+            PowerSharePolicy::EqualShare => {
+                // Agents needing less than an equal share leave the rest to the others
+                order.sort_by_key(|&i| requests[i].power);
+                for (n_served, &i) in order.iter().enumerate() {
+                    let share = remaining / (order.len() - n_served) as f32;
+                    allocated[i] = requests[i].power.min(share);
+                    remaining = remaining - allocated[i];
+                }
+            }
+            PowerSharePolicy::LowestSocFirst | PowerSharePolicy::Fifo => {
+                if self.policy == PowerSharePolicy::LowestSocFirst {
+                    order.sort_by(|&a, &b| requests[a].soc.total_cmp(&requests[b].soc));
+                }
+                for i in order {
+                    allocated[i] = requests[i].power.min(remaining);
+                    remaining = remaining - allocated[i];
+                }
+            }
+        }
+        allocated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns requests of the given power in W and state of charge in percent, in slot order.
+    fn requests(requests: &[(f32, f32)]) -> Vec<PowerRequest> {
+        requests
+            .iter()
+            .enumerate()
+            .map(|(i, &(power, soc))| PowerRequest {
+                agent_id: AgentId::new(i as u32),
+                power: Power::watts(power),
+                soc,
+            })
+            .collect()
+    }
+
+    fn allocate(policy: PowerSharePolicy, available: f32, requests: &[PowerRequest]) -> Vec<f32> {
+        let budget = PowerBudget {
+            policy,
+            ..Default::default()
+        };
+        budget
+            .allocate(Power::watts(available), requests)
+            .iter()
+            .map(|power| power.to_base_unit())
+            .collect()
+    }
+
+    #[test]
+    fn equal_share_passes_unneeded_power_on() {
+        let policy = PowerSharePolicy::EqualShare;
+
+        let full = requests(&[(200.0, 50.0), (200.0, 50.0), (200.0, 50.0)]);
+        assert_eq!(allocate(policy, 300.0, &full), vec![100.0, 100.0, 100.0]);
+        let one_nearly_full = requests(&[(200.0, 50.0), (50.0, 95.0), (200.0, 50.0)]);
+        assert_eq!(
+            allocate(policy, 300.0, &one_nearly_full),
+            vec![125.0, 50.0, 125.0]
+        );
+    }
+
+    #[test]
+    fn lowest_soc_first_serves_by_state_of_charge() {
+        let requests = requests(&[(200.0, 80.0), (200.0, 20.0), (50.0, 50.0)]);
+
+        assert_eq!(
+            allocate(PowerSharePolicy::LowestSocFirst, 300.0, &requests),
+            vec![50.0, 200.0, 50.0]
+        );
+    }
+
+    #[test]
+    fn fifo_serves_in_slot_order() {
+        let requests = requests(&[(50.0, 80.0), (200.0, 20.0), (200.0, 50.0)]);
+
+        assert_eq!(
+            allocate(PowerSharePolicy::Fifo, 300.0, &requests),
+            vec![50.0, 200.0, 50.0]
+        );
+    }
+
+    #[test]
+    fn no_agent_gets_more_than_requested() {
+        let requests = requests(&[(200.0, 80.0), (50.0, 20.0)]);
+
+        for policy in [
+            PowerSharePolicy::EqualShare,
+            PowerSharePolicy::LowestSocFirst,
+            PowerSharePolicy::Fifo,
+        ] {
+            assert_eq!(allocate(policy, 1000.0, &requests), vec![200.0, 50.0]);
+        }
+    }
+}
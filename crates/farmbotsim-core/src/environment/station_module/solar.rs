@@ -0,0 +1,142 @@
+use chrono::{Datelike, NaiveDateTime, Timelike};
+use serde::{Deserialize, Serialize};
+use std::{
+    f32::consts::PI,
+    fs::File,
+    io::{BufRead, BufReader},
+};
+
+use crate::{
+    environment::datetime::DATETIME_FORMAT, error::FarmbotSimError, units::power::Power,
+    utilities::utils::linear_interpolate,
+};
+
+/// Irradiance outside the atmosphere in W/m².
+const SOLAR_CONSTANT: f32 = 1361.0;
+/// Tilt of the earth's axis in degrees.
+const AXIAL_TILT: f32 = 23.44;
+/// Share of the direct irradiance added as diffuse light from the sky.
+const DIFFUSE_FRACTION: f32 = 0.1;
+
+/// Solar panel powering a station, driven by a clear-sky model or a measured irradiance series.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SolarModel {
+    /// Latitude of the site in degrees, positive north.
+    pub latitude: f32,
+    /// Panel area in m², the panel lies flat.
+    pub panel_area: f32,
+    /// Fraction of the irradiance converted to electric power (0-1].
+    pub efficiency: f32,
+    /// Optional CSV file of hourly irradiance (`dd.mm.YYYY HH:MM:SS,W/m²` per line after a header).
+    /// Outside of its time range the clear-sky model is used.
+    pub irradiance_file: Option<String>,
+    /// Irradiance series loaded from `irradiance_file`, sorted by time.
+    /// Kept in env snapshots so they don't depend on the file.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub irradiance_series: Vec<(NaiveDateTime, f32)>,
+}
+impl Default for SolarModel {
+    /// Returns a 2 m² panel with 20 % efficiency at 46° north.
+    fn default() -> Self {
+        Self {
+            latitude: 46.0,
+            panel_area: 2.0,
+            efficiency: 0.2,
+            irradiance_file: None,
+            irradiance_series: vec![],
+        }
+    }
+}
+
+impl SolarModel {
+    /// Returns an error if a parameter is out of range.
+    pub fn validate(&self) -> Result<(), FarmbotSimError> {
+        if !(-90.0..=90.0).contains(&self.latitude) {
+            let msg = format!("{} is outside -90..=90°", self.latitude);
+            return Err(FarmbotSimError::invalid_field("solar.latitude", msg));
+        }
+        if self.panel_area <= 0.0 {
+            let msg = format!("{} must be positive", self.panel_area);
+            return Err(FarmbotSimError::invalid_field("solar.panel_area", msg));
+        }
+        if self.efficiency <= 0.0 || self.efficiency > 1.0 {
+            let msg = format!("{} is outside (0, 1]", self.efficiency);
+            return Err(FarmbotSimError::invalid_field("solar.efficiency", msg));
+        }
+        Ok(())
+    }
+
+    /// Loads the irradiance series from `irradiance_file`, if set and not loaded yet.
+    /// Returns an error if the file can't be read or has no valid lines.
+    pub fn try_load_irradiance(&mut self) -> Result<(), FarmbotSimError> {
+        let Some(path) = &self.irradiance_file else {
+            return Ok(());
+        };
+        if !self.irradiance_series.is_empty() {
+            return Ok(());
+        }
+        let file = File::open(path).map_err(|e| FarmbotSimError::io(path, e))?;
+        let mut series = Vec::new();
+        for line in BufReader::new(file).lines().skip(1).map_while(Result::ok) {
+            if let Some((time, irradiance)) = line.split_once(',')
+                && let Ok(time) = NaiveDateTime::parse_from_str(time.trim(), DATETIME_FORMAT)
+                && let Ok(irradiance) = irradiance.trim().parse::<f32>()
+            {
+                series.push((time, irradiance.max(0.0)));
+            }
+        }
+        if series.is_empty() {
+            let msg = format!("Expected lines formatted as '{DATETIME_FORMAT},W/m²'");
+            return Err(FarmbotSimError::parse::<Vec<(NaiveDateTime, f32)>, _>(
+                path, msg,
+            ));
+        }
+        series.sort_by_key(|(time, _)| *time);
+        self.irradiance_series = series;
+        Ok(())
+    }
+
+    /// Returns the irradiance on the panel in W/m² at the given time.
+    /// Interpolates the loaded series within its time range and uses the clear-sky model elsewhere.
+    pub fn irradiance(&self, time: &NaiveDateTime) -> f32 {
+        let series = &self.irradiance_series;
+        if let (Some(first), Some(last)) = (series.first(), series.last())
+            && first.0 <= *time
+            && *time <= last.0
+        {
+            let next = series.partition_point(|(t, _)| t <= time);
+            if next == series.len() {
+                return last.1;
+            }
+            let (t0, y0) = series[next - 1];
+            let (t1, y1) = series[next];
+            let x = (*time - t0).num_seconds() as f32;
+            return linear_interpolate(0.0, y0, (t1 - t0).num_seconds() as f32, y1, x);
+        }
+        self.clear_sky_irradiance(time)
+    }
+
+    /// Returns the irradiance on a flat panel under a clear sky in W/m².
+    /// The clock time is taken as local solar time, so the sun is highest at 12:00.
+    pub fn clear_sky_irradiance(&self, time: &NaiveDateTime) -> f32 {
+        let day = time.ordinal() as f32;
+        let declination = (AXIAL_TILT * (2.0 * PI * (284.0 + day) / 365.0).sin()).to_radians();
+        let hours = time.num_seconds_from_midnight() as f32 / 3600.0;
+        let hour_angle = (15.0 * (hours - 12.0)).to_radians();
+        let latitude = self.latitude.to_radians();
+        let sin_elevation = latitude.sin() * declination.sin()
+            + latitude.cos() * declination.cos() * hour_angle.cos();
+        if sin_elevation <= 0.0 {
+            return 0.0;
+        }
+        // Meinel model of the atmosphere's attenuation by air mass
+        let air_mass = 1.0 / sin_elevation;
+        let direct = SOLAR_CONSTANT * 0.7_f32.powf(air_mass.powf(0.678));
+        (1.0 + DIFFUSE_FRACTION) * direct * sin_elevation
+    }
+
+    /// Returns the electric power of the panel at the given time.
+    pub fn power(&self, time: &NaiveDateTime) -> Power {
+        Power::watts(self.irradiance(time) * self.panel_area * self.efficiency)
+    }
+}
@@ -1,22 +1,24 @@
 use egui::Color32;
+use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 
-use super::station_config::StationConfig;
+use super::{power_budget::PowerBudget, station_config::StationConfig};
 use crate::{
     agent_module::agent::AgentId,
+    event_module::event::EventKind,
     movement_module::pose::Pose,
     units::{angle::Angle, length::Length},
 };
 
 /// Represents the type of position an agent can occupy at a station.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum StationPosType {
     ChargingSlot,
     QueueSlot,
 }
 
 /// Represents station ID
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct StationId(u32);
 impl StationId {
     pub fn new(id: u32) -> Self {
@@ -30,7 +32,7 @@ impl std::fmt::Display for StationId {
 }
 
 /// Represents a station where agents can queue or occupy charging slots.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Station {
     /// Unique identifier of the station.
     pub id: StationId,
@@ -49,8 +51,14 @@ pub struct Station {
     pub slots_pose: Vec<Pose>,
     /// Current occupancy of each charging slot. `None` means empty.
     pub slots: Vec<Option<AgentId>>,
+    /// Agents in the charging slots in the order they got them.
+    pub slot_order: Vec<AgentId>,
+    /// Power shared by all slots, every slot charges at full speed if `None`.
+    pub power_budget: Option<PowerBudget>,
     /// Queue of agent IDs waiting for a slot.
     pub queue: VecDeque<AgentId>,
+    /// Slot and queue events not yet collected by the env.
+    pub pending_events: Vec<EventKind>,
 }
 
 impl Default for Station {
@@ -67,7 +75,10 @@ impl Default for Station {
             n_slots: config.n_slots,
             slots_pose: config.slots_pose,
             slots: vec![None; config.n_slots as usize],
+            slot_order: vec![],
+            power_budget: config.power_budget,
             queue: VecDeque::new(),
+            pending_events: vec![],
         }
     }
 }
@@ -84,7 +95,10 @@ impl Station {
             n_slots: config.n_slots,
             slots_pose: config.slots_pose,
             slots: vec![None; config.n_slots as usize],
+            slot_order: vec![],
+            power_budget: config.power_budget,
             queue: VecDeque::new(),
+            pending_events: vec![],
         }
     }
     /// Converts the current `Station` into a `StationConfig`.
@@ -95,12 +109,14 @@ impl Station {
             self.waiting_offset,
             self.n_slots,
             self.slots_pose.clone(),
+            self.power_budget.clone(),
         )
     }
 }
 
 impl Station {
     /// Creates a new `Station` with explicit parameters.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         id: u32,
         pose: Pose,
@@ -109,6 +125,7 @@ impl Station {
         color: Color32,
         n_slots: u32,
         slots_pose: Vec<Pose>,
+        power_budget: Option<PowerBudget>,
     ) -> Self {
         Self {
             id: StationId::new(id),
@@ -120,13 +137,18 @@ impl Station {
             n_slots,
             slots_pose,
             slots: vec![None; n_slots as usize],
+            slot_order: vec![],
+            power_budget,
             queue: VecDeque::new(),
+            pending_events: vec![],
         }
     }
     /// Resets the station: clears all slots and empties the queue.
     pub fn reset(&mut self) {
         self.slots = vec![None; self.n_slots as usize];
+        self.slot_order.clear();
         self.queue.clear();
+        self.pending_events.clear();
     }
     /// Returns the number of occupied charging slots.
     pub fn n_occupied_slots(&self) -> u32 {
@@ -144,12 +166,15 @@ impl Station {
         if let Some(index) = self.get_empty_slot() {
             self.slots[index] = Some(agent_id);
             if let Some(pose) = self.get_pose_for_slot(index) {
+                self.slot_order.push(agent_id);
+                self.push_event(agent_id, StationPosType::ChargingSlot, true);
                 return (pose, StationPosType::ChargingSlot);
             } else {
                 self.slots[index] = None;
             }
         }
         self.queue.push_back(agent_id);
+        self.push_event(agent_id, StationPosType::QueueSlot, true);
         (
             self.get_waiting_pose(self.queue.len() - 1),
             StationPosType::QueueSlot,
@@ -171,6 +196,8 @@ impl Station {
         if let Some(index) = self.get_empty_slot() {
             if self.remove_agent_from_queue(agent_id) {
                 self.slots[index] = Some(agent_id);
+                self.slot_order.push(agent_id);
+                self.push_event(agent_id, StationPosType::ChargingSlot, true);
                 return self.get_pose_for_slot(index);
             }
         }
@@ -201,6 +228,8 @@ impl Station {
                     *slot = None;
                 }
             });
+            self.slot_order.retain(|&id| id != agent_id);
+            self.push_event(agent_id, StationPosType::ChargingSlot, false);
             return true;
         }
         false
@@ -211,8 +240,26 @@ impl Station {
     fn remove_agent_from_queue(&mut self, agent_id: AgentId) -> bool {
         if self.queue.contains(&agent_id) {
             self.queue.retain(|&id| id != agent_id);
+            self.push_event(agent_id, StationPosType::QueueSlot, false);
             return true;
         }
         false
     }
+    /// Records that the agent entered or left a slot or the queue.
+    fn push_event(&mut self, agent_id: AgentId, pos_type: StationPosType, entered: bool) {
+        let station_id = self.id;
+        self.pending_events.push(if entered {
+            EventKind::StationEntered {
+                station_id,
+                agent_id,
+                pos_type,
+            }
+        } else {
+            EventKind::StationLeft {
+                station_id,
+                agent_id,
+                pos_type,
+            }
+        });
+    }
 }
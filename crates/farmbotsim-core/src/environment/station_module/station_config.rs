@@ -1,5 +1,6 @@
 use egui::Pos2;
 
+use super::power_budget::PowerBudget;
 use crate::{
     movement_module::pose::Pose,
     units::{angle::Angle, length::Length},
@@ -19,6 +20,8 @@ pub struct StationConfig {
     pub n_slots: u32,
     /// Relative poses for each slot.
     pub slots_pose: Vec<Pose>,
+    /// Power shared by all slots, every slot charges at full speed if `None`.
+    pub power_budget: Option<PowerBudget>,
 }
 
 impl Default for StationConfig {
@@ -30,6 +33,7 @@ impl Default for StationConfig {
             waiting_offset: Length::meters(1.0),
             n_slots: 1,
             slots_pose: vec![Pose::new(Pos2::ZERO, Angle::degrees(90.0))],
+            power_budget: None,
         }
     }
 }
@@ -42,6 +46,7 @@ impl StationConfig {
         waiting_offset: Length,
         n_slots: u32,
         slots_pose: Vec<Pose>,
+        power_budget: Option<PowerBudget>,
     ) -> Self {
         Self {
             pose,
@@ -49,6 +54,7 @@ impl StationConfig {
             waiting_offset,
             n_slots,
             slots_pose,
+            power_budget,
         }
     }
     /// Regenerates the slot poses based on current count and orientation.
@@ -0,0 +1,99 @@
+use std::path::Path;
+
+use crate::logger::log_error_and_panic;
+
+/// Represents errors that can occur while loading configs and building the simulation.
+#[derive(Debug)]
+pub enum FarmbotSimError {
+    /// File could not be opened or read.
+    Io { path: String, msg: String },
+    /// File content could not be parsed into the expected type.
+    Parse {
+        path: String,
+        type_name: String,
+        msg: String,
+    },
+    /// A config field holds an invalid value. `path` is the file the field comes from, if known.
+    InvalidField {
+        path: Option<String>,
+        field: String,
+        msg: String,
+    },
+}
+
+impl FarmbotSimError {
+    /// Creates an `Io` error for the given file.
+    pub fn io<P: AsRef<Path>>(path: P, e: impl std::fmt::Display) -> Self {
+        FarmbotSimError::Io {
+            path: path.as_ref().display().to_string(),
+            msg: e.to_string(),
+        }
+    }
+    /// Creates a `Parse` error for the given file and target type.
+    pub fn parse<T, P: AsRef<Path>>(path: P, e: impl std::fmt::Display) -> Self {
+        FarmbotSimError::Parse {
+            path: path.as_ref().display().to_string(),
+            type_name: std::any::type_name::<T>().to_string(),
+            msg: e.to_string(),
+        }
+    }
+    /// Creates an `InvalidField` error without a file path.
+    pub fn invalid_field(field: &str, msg: impl std::fmt::Display) -> Self {
+        FarmbotSimError::InvalidField {
+            path: None,
+            field: field.to_string(),
+            msg: msg.to_string(),
+        }
+    }
+    /// Attaches a file path to an `InvalidField` error that has none yet.
+    pub fn in_file<P: AsRef<Path>>(self, file_path: P) -> Self {
+        match self {
+            FarmbotSimError::InvalidField {
+                path: None,
+                field,
+                msg,
+            } => FarmbotSimError::InvalidField {
+                path: Some(file_path.as_ref().display().to_string()),
+                field,
+                msg,
+            },
+            other => other,
+        }
+    }
+    /// Logs the error and panics with its message.
+    pub fn log_and_panic(&self) -> ! {
+        log_error_and_panic(&self.to_string())
+    }
+}
+
+impl std::fmt::Display for FarmbotSimError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FarmbotSimError::Io { path, msg } => {
+                write!(f, "Failed to read file \"{path}\": {msg}")
+            }
+            FarmbotSimError::Parse {
+                path,
+                type_name,
+                msg,
+            } => {
+                write!(f, "Failed to parse \"{path}\" into {type_name}: {msg}")
+            }
+            FarmbotSimError::InvalidField {
+                path: Some(path),
+                field,
+                msg,
+            } => {
+                write!(f, "Invalid field '{field}' in \"{path}\": {msg}")
+            }
+            FarmbotSimError::InvalidField {
+                path: None,
+                field,
+                msg,
+            } => {
+                write!(f, "Invalid field '{field}': {msg}")
+            }
+        }
+    }
+}
+impl std::error::Error for FarmbotSimError {}
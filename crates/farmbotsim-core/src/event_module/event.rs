@@ -0,0 +1,109 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    agent_module::{agent::AgentId, agent_state::AgentState},
+    environment::station_module::station::{StationId, StationPosType},
+    task_module::task::Task,
+    units::duration::Duration,
+};
+
+/// Represents a discrete change in the simulation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event")]
+pub enum EventKind {
+    /// A work task was added to the work list.
+    TaskCreated {
+        task_id: u32,
+        farm_entity_id: u32,
+        info: String,
+    },
+    /// A work task was assigned to an agent.
+    TaskAssigned { task_id: u32, agent_id: AgentId },
+    /// An agent completed a work task.
+    TaskCompleted { task_id: u32, agent_id: AgentId },
+    /// A work task was taken from an agent and returned to the work list.
+    TaskReturned { task_id: u32, agent_id: AgentId },
+    /// An agent transitioned between states.
+    StateChanged {
+        agent_id: AgentId,
+        from: AgentState,
+        to: AgentState,
+    },
+    /// An agent took a charging slot or joined the queue of a station.
+    StationEntered {
+        station_id: StationId,
+        agent_id: AgentId,
+        pos_type: StationPosType,
+    },
+    /// An agent left a charging slot or the queue of a station.
+    StationLeft {
+        station_id: StationId,
+        agent_id: AgentId,
+        pos_type: StationPosType,
+    },
+    /// The battery of an agent was depleted.
+    BatteryDischarged { agent_id: AgentId },
+    /// The footprints of two agents started to overlap.
+    Collision {
+        agent_id: AgentId,
+        other_agent_id: AgentId,
+    },
+    /// Two agents came closer than the near miss distance without touching.
+    NearMiss {
+        agent_id: AgentId,
+        other_agent_id: AgentId,
+    },
+}
+
+impl EventKind {
+    /// Creates a `TaskCreated` event, `None` for tasks that are not work tasks.
+    pub fn task_created(task: &Task) -> Option<Self> {
+        match task {
+            Task::Stationary {
+                id,
+                farm_entity_id,
+                info,
+                ..
+            }
+            | Task::Moving {
+                id,
+                farm_entity_id,
+                info,
+                ..
+            } => Some(Self::TaskCreated {
+                task_id: *id,
+                farm_entity_id: *farm_entity_id,
+                info: info.clone(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Returns the agent involved in the event, if any.
+    pub fn agent_id(&self) -> Option<AgentId> {
+        match self {
+            Self::TaskCreated { .. } => None,
+            Self::TaskAssigned { agent_id, .. }
+            | Self::TaskCompleted { agent_id, .. }
+            | Self::TaskReturned { agent_id, .. }
+            | Self::StateChanged { agent_id, .. }
+            | Self::StationEntered { agent_id, .. }
+            | Self::StationLeft { agent_id, .. }
+            | Self::BatteryDischarged { agent_id }
+            | Self::Collision { agent_id, .. }
+            | Self::NearMiss { agent_id, .. } => Some(*agent_id),
+        }
+    }
+}
+
+/// A simulation event stamped with the simulation time it was recorded at.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Event {
+    /// Env step count at which the event was recorded.
+    pub step: u32,
+    /// Simulated time since the start of the episode.
+    pub time: Duration,
+    /// What happened.
+    #[serde(flatten)]
+    pub kind: EventKind,
+}
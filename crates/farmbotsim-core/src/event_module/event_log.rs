@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, BufWriter, Write},
+    path::Path,
+};
+
+use crate::{
+    event_module::event::{Event, EventKind},
+    units::duration::Duration,
+};
+
+/// Chronological record of the events of an episode, stored as JSON lines (one event per line).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EventLog {
+    pub events: Vec<Event>,
+}
+
+impl EventLog {
+    /// Appends events that occurred at the given step and time.
+    pub fn record(&mut self, step: u32, time: Duration, kinds: Vec<EventKind>) {
+        self.events
+            .extend(kinds.into_iter().map(|kind| Event { step, time, kind }));
+    }
+
+    /// Removes all events.
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+
+    /// Writes the events to a JSONL file at the given path.
+    pub fn save_jsonl_file<P: AsRef<Path>>(&self, file_path: P) -> std::io::Result<()> {
+        let mut writer = BufWriter::new(File::create(file_path)?);
+        for event in &self.events {
+            serde_json::to_writer(&mut writer, event)?;
+            writer.write_all(b"\n")?;
+        }
+        writer.flush()
+    }
+
+    /// Reads events from a JSONL file at the given path, skipping empty lines.
+    pub fn from_jsonl_file<P: AsRef<Path>>(file_path: P) -> std::io::Result<Self> {
+        let reader = BufReader::new(File::open(file_path)?);
+        let mut events = vec![];
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            events.push(serde_json::from_str(&line)?);
+        }
+        Ok(Self { events })
+    }
+}
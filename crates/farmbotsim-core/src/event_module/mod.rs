@@ -0,0 +1,4 @@
+//! Contains discrete simulation events and the event log.
+
+pub mod event;
+pub mod event_log;
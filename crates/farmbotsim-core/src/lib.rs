@@ -1,11 +1,20 @@
+//! Core of the farm robot simulator: environment, agents, batteries, tasks and statistics.
+//!
+//! Configs and results are read from JSON files that may have been written by an earlier version.
+//! Fields added later are `#[serde(default)]` so such files still load, and types whose format
+//! changed shape deserialize through a private `*File` struct that also accepts the old format.
+
 pub mod agent_module;
 pub mod battery_module;
 pub mod cfg;
 pub mod environment;
+pub mod error;
+pub mod event_module;
 pub mod logger;
 pub mod movement_module;
 pub mod path_finding_module;
 pub mod prelude;
+pub mod simulation_module;
 pub mod statistics;
 pub mod task_module;
 pub mod units;
@@ -0,0 +1,180 @@
+use egui::Vec2;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cfg::TOLERANCE_DISTANCE,
+    logger::log_error_and_panic,
+    movement_module::{
+        is_movement::IsMovement,
+        movement::{MovementInputs, MovementState, forward_strength},
+        odometry_noise::OdometryNoise,
+        pose::Pose,
+    },
+    units::{
+        angle::Angle, angular_velocity::AngularVelocity, duration::Duration, length::Length,
+        linear_velocity::LinearVelocity,
+    },
+};
+
+/// Throttle and steering input values for a car-like robot.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AckermannMovementInputs {
+    /// Fraction of the maximum velocity, negative values drive backwards.
+    pub throttle: f32,
+    /// Fraction of the maximum steering angle, positive values steer left.
+    pub steering: f32,
+}
+
+impl AckermannMovementInputs {
+    /// Creates new throttle and steering inputs.
+    pub fn new(throttle: f32, steering: f32) -> Self {
+        Self { throttle, steering }
+    }
+    /// Clamps throttle and steering values between -1.0 and 1.0.
+    pub fn clamped(self) -> Self {
+        Self {
+            throttle: self.throttle.clamp(-1.0, 1.0),
+            steering: self.steering.clamp(-1.0, 1.0),
+        }
+    }
+}
+
+/// Defines physical properties for an Ackermann (car-like) movement model that can't turn in place.
+/// The pose is the center of the rear axle.
+#[derive(Clone, PartialEq, Copy, Debug, Serialize, Deserialize)]
+pub struct AckermannMovement {
+    pub max_velocity: LinearVelocity,
+    /// Distance between the front and the rear axle.
+    pub wheelbase: Length,
+    pub max_steering_angle: Angle,
+    /// How fast the steering angle follows the steering input.
+    pub steering_rate: AngularVelocity,
+    pub max_sub_step: Duration,
+    /// Odometry noise, the agent knows its pose exactly if `None`.
+    pub noise: Option<OdometryNoise>,
+}
+impl Default for AckermannMovement {
+    /// Returns a default Ackermann movement configuration.
+    fn default() -> Self {
+        Self {
+            max_velocity: LinearVelocity::kilometers_per_hour(5.0),
+            wheelbase: Length::meters(1.0),
+            max_steering_angle: Angle::degrees(35.0),
+            steering_rate: AngularVelocity::degrees_per_second(30.0),
+            max_sub_step: Duration::seconds(0.5),
+            noise: None,
+        }
+    }
+}
+
+impl IsMovement for AckermannMovement {
+    /// Computes the new pose with the kinematic bicycle model after turning the steering angle
+    /// of the state toward the steering input.
+    fn calculate_new_pose_from_inputs(
+        &self,
+        state: &mut MovementState,
+        simulation_step: Duration,
+        inputs: MovementInputs,
+        current_pose: Pose,
+        max_velocity: LinearVelocity,
+    ) -> (Pose, LinearVelocity, AngularVelocity) {
+        match inputs {
+            MovementInputs::Ackermann(ackermann_inputs) => {
+                let ackermann_inputs = ackermann_inputs.clamped();
+                let dt = simulation_step.to_base_unit();
+
+                // Steering follows the input at the steering rate
+                let steering = state.steering_angle.to_radians();
+                let target_steering =
+                    ackermann_inputs.steering * self.max_steering_angle.to_radians();
+                let max_change = self.steering_rate.to_base_unit().abs() * dt;
+                let steering =
+                    steering + (target_steering - steering).clamp(-max_change, max_change);
+                state.steering_angle = Angle::radians(steering);
+
+                let max_velocity = if max_velocity > self.max_velocity {
+                    self.max_velocity
+                } else {
+                    max_velocity
+                };
+                let v = ackermann_inputs.throttle * max_velocity.to_base_unit();
+                let omega = v * steering.tan() / self.wheelbase.to_base_unit();
+
+                // Drive along the arc with the heading halfway through the step
+                let heading = current_pose.orientation.to_radians();
+                let mid_heading = heading + omega * dt / 2.0;
+                let direction = Vec2::new(mid_heading.cos(), mid_heading.sin());
+                let new_position = current_pose.position + direction * (v * dt);
+                let new_orientation =
+                    Angle::degrees((heading + omega * dt).to_degrees().rem_euclid(360.0));
+
+                (
+                    Pose::new(new_position, new_orientation),
+                    LinearVelocity::meters_per_second(v),
+                    AngularVelocity::radians_per_second(omega),
+                )
+            }
+            _ => log_error_and_panic("Invalid inputs for AckermannMovement"),
+        }
+    }
+    /// Computes throttle and steering inputs to drive an arc through the target position.
+    /// Targets that can't be reached with the tightest turn are approached by turning at full lock,
+    /// or by backing up with opposite lock while they lie inside the turning circle.
+    fn calculate_inputs_for_target(
+        &self,
+        current_pose: &Pose,
+        target_pose: &Pose,
+    ) -> MovementInputs {
+        let position_error = current_pose.position.distance(target_pose.position);
+        if position_error <= TOLERANCE_DISTANCE.to_base_unit() {
+            return MovementInputs::Ackermann(AckermannMovementInputs::new(0.0, 0.0));
+        }
+
+        let heading = current_pose.orientation.to_radians();
+        let offset = target_pose.position - current_pose.position;
+        // Target in the frame of the agent, x forward and y to the left
+        let target = Vec2::new(
+            offset.x * heading.cos() + offset.y * heading.sin(),
+            -offset.x * heading.sin() + offset.y * heading.cos(),
+        );
+        let max_curvature = self.max_curvature();
+        let curvature = 2.0 * target.y / offset.length_sq();
+        let forward_strength = forward_strength(position_error);
+
+        let (throttle, curvature) = if target.x > 0.0 && curvature.abs() <= max_curvature {
+            (forward_strength, curvature)
+        } else {
+            let side = if target.y >= 0.0 { 1.0 } else { -1.0 };
+            let turning_radius = 1.0 / max_curvature;
+            let turning_center = Vec2::new(0.0, side * turning_radius);
+            if (target - turning_center).length() > turning_radius {
+                (forward_strength, side * max_curvature)
+            } else {
+                (-forward_strength, -side * max_curvature)
+            }
+        };
+
+        let steering_angle = (curvature * self.wheelbase.to_base_unit()).atan();
+        let steering = steering_angle / self.max_steering_angle.to_radians();
+        MovementInputs::Ackermann(AckermannMovementInputs::new(throttle, steering))
+    }
+    /// Returns true if the position is reached, the orientation can't be matched in place.
+    fn has_reached(&self, current_pose: &Pose, target_pose: &Pose) -> bool {
+        current_pose.position.distance(target_pose.position) <= TOLERANCE_DISTANCE.to_base_unit()
+    }
+    /// Returns the longest time step integrated at once.
+    fn max_sub_step(&self) -> Duration {
+        self.max_sub_step
+    }
+}
+
+impl AckermannMovement {
+    /// Returns the curvature in 1/m of the tightest turn.
+    pub fn max_curvature(&self) -> f32 {
+        self.max_steering_angle.to_radians().abs().tan() / self.wheelbase.to_base_unit()
+    }
+    /// Returns the angular velocity when driving the tightest turn at maximum velocity.
+    pub fn max_angular_velocity(&self) -> AngularVelocity {
+        AngularVelocity::radians_per_second(self.max_velocity.to_base_unit() * self.max_curvature())
+    }
+}
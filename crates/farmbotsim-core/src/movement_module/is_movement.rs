@@ -1,5 +1,9 @@
 use crate::{
-    movement_module::{movement::MovementInputs, pose::Pose},
+    cfg::{TOLERANCE_ANGLE, TOLERANCE_DISTANCE},
+    movement_module::{
+        movement::{MovementInputs, MovementState},
+        pose::Pose,
+    },
     units::{
         angular_velocity::AngularVelocity, duration::Duration, linear_velocity::LinearVelocity,
     },
@@ -13,12 +17,75 @@ pub trait IsMovement {
         current_pose: &Pose,
         target_pose: &Pose,
     ) -> MovementInputs;
-    /// Computes the new pose after applying movement inputs over a time step.
+    /// Calculates inputs that drive a circular arc to the target pose when the arc tangent to
+    /// the current orientation ends in the target orientation, otherwise like
+    /// `calculate_inputs_for_target`.
+    fn calculate_inputs_along_arc(
+        &self,
+        current_pose: &Pose,
+        target_pose: &Pose,
+    ) -> MovementInputs {
+        self.calculate_inputs_for_target(current_pose, target_pose)
+    }
+    /// Computes the new pose after applying movement inputs over a time step,
+    /// updating the state of the model like the steering angle.
     fn calculate_new_pose_from_inputs(
         &self,
+        state: &mut MovementState,
         simulation_step: Duration,
         inputs: MovementInputs,
         current_pose: Pose,
         max_velocity: LinearVelocity,
     ) -> (Pose, LinearVelocity, AngularVelocity);
+    /// Returns true if the current pose counts as reaching the target pose.
+    fn has_reached(&self, current_pose: &Pose, target_pose: &Pose) -> bool {
+        current_pose.is_close_to(target_pose, TOLERANCE_DISTANCE, TOLERANCE_ANGLE)
+    }
+    /// Returns the longest time step integrated at once.
+    fn max_sub_step(&self) -> Duration;
+    /// Moves toward a target pose over a time step, recalculating inputs at every sub-step
+    /// so that coarse simulation steps stay close to the fine-grained trajectory.
+    /// Drives arcs between poses of smoothed paths if `along_arc` is set.
+    /// Stops once the target is reached and returns the unused part of the step, so the
+    /// caller can continue toward the next target.
+    fn move_to_target(
+        &self,
+        state: &mut MovementState,
+        simulation_step: Duration,
+        current_pose: Pose,
+        target_pose: &Pose,
+        max_velocity: LinearVelocity,
+        along_arc: bool,
+    ) -> (Pose, LinearVelocity, AngularVelocity, Duration) {
+        let max_sub_step = self.max_sub_step().to_base_unit();
+        let n_sub_steps = if max_sub_step > 0.0 {
+            (simulation_step.to_base_unit() / max_sub_step)
+                .ceil()
+                .max(1.0) as u32
+        } else {
+            1
+        };
+        let sub_step = simulation_step / n_sub_steps as f32;
+
+        let mut result = (current_pose, LinearVelocity::ZERO, AngularVelocity::ZERO);
+        for i in 0..n_sub_steps {
+            if i > 0 && self.has_reached(&result.0, target_pose) {
+                let unused = sub_step * (n_sub_steps - i) as f32;
+                return (result.0, result.1, result.2, unused);
+            }
+            let inputs = if along_arc {
+                self.calculate_inputs_along_arc(&result.0, target_pose)
+            } else {
+                self.calculate_inputs_for_target(&result.0, target_pose)
+            };
+            result = self.calculate_new_pose_from_inputs(
+                state,
+                sub_step,
+                inputs,
+                result.0,
+                max_velocity,
+            );
+        }
+        (result.0, result.1, result.2, Duration::ZERO)
+    }
 }
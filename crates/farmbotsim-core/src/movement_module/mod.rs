@@ -1,6 +1,8 @@
 //! Movement logic and models for robot motion control.
 
+pub mod ackermann_movement;
 pub mod is_movement;
 pub mod movement;
+pub mod odometry_noise;
 pub mod pose;
 pub mod romba_movement;
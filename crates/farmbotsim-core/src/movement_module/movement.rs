@@ -1,15 +1,19 @@
 use serde::{Deserialize, Serialize};
 
 use crate::{
+    error::FarmbotSimError,
     movement_module::{
+        ackermann_movement::{AckermannMovement, AckermannMovementInputs},
         is_movement::IsMovement,
+        odometry_noise::OdometryNoise,
         pose::Pose,
         romba_movement::{RombaMovement, RombaMovementInputs},
     },
     units::{
-        angular_velocity::AngularVelocity, duration::Duration, linear_velocity::LinearVelocity,
+        angle::Angle, angular_velocity::AngularVelocity, duration::Duration, length::Length,
+        linear_velocity::LinearVelocity,
     },
-    utilities::utils::load_json_or_panic,
+    utilities::utils::{load_json, load_json_or_panic},
 };
 
 /// Represents movement control inputs for different movement models.
@@ -17,6 +21,24 @@ use crate::{
 pub enum MovementInputs {
     /// Inputs for the Romba movement model.
     Romba(RombaMovementInputs),
+    /// Inputs for the Ackermann movement model.
+    Ackermann(AckermannMovementInputs),
+}
+impl MovementInputs {
+    /// Returns true if the inputs produce no motion.
+    pub fn is_zero(&self) -> bool {
+        match self {
+            MovementInputs::Romba(inputs) => inputs.left == 0.0 && inputs.right == 0.0,
+            MovementInputs::Ackermann(inputs) => inputs.throttle == 0.0,
+        }
+    }
+}
+
+/// State of a movement model that changes while the agent drives, kept apart from its config.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct MovementState {
+    /// Current steering angle of steered models, stays zero for others.
+    pub steering_angle: Angle,
 }
 
 /// Represents a configurable movement model.
@@ -25,6 +47,8 @@ pub enum MovementInputs {
 pub enum Movement {
     /// Romba-based movement.
     RombaMovement(RombaMovement),
+    /// Car-like movement with steered front wheels.
+    AckermannMovement(AckermannMovement),
 }
 impl IsMovement for Movement {
     /// Delegates input calculation to the underlying movement model.
@@ -37,11 +61,30 @@ impl IsMovement for Movement {
             Movement::RombaMovement(romba) => {
                 romba.calculate_inputs_for_target(current_pose, target_pose)
             }
+            Movement::AckermannMovement(ackermann) => {
+                ackermann.calculate_inputs_for_target(current_pose, target_pose)
+            }
+        }
+    }
+    /// Delegates arc input calculation to the underlying movement model.
+    fn calculate_inputs_along_arc(
+        &self,
+        current_pose: &Pose,
+        target_pose: &Pose,
+    ) -> MovementInputs {
+        match self {
+            Movement::RombaMovement(romba) => {
+                romba.calculate_inputs_along_arc(current_pose, target_pose)
+            }
+            Movement::AckermannMovement(ackermann) => {
+                ackermann.calculate_inputs_along_arc(current_pose, target_pose)
+            }
         }
     }
     /// Delegates pose update computation to the underlying movement model.
     fn calculate_new_pose_from_inputs(
         &self,
+        state: &mut MovementState,
         simulation_step: Duration,
         inputs: MovementInputs,
         current_pose: Pose,
@@ -49,6 +92,14 @@ impl IsMovement for Movement {
     ) -> (Pose, LinearVelocity, AngularVelocity) {
         match self {
             Movement::RombaMovement(romba) => romba.calculate_new_pose_from_inputs(
+                state,
+                simulation_step,
+                inputs,
+                current_pose,
+                max_velocity,
+            ),
+            Movement::AckermannMovement(ackermann) => ackermann.calculate_new_pose_from_inputs(
+                state,
                 simulation_step,
                 inputs,
                 current_pose,
@@ -56,16 +107,69 @@ impl IsMovement for Movement {
             ),
         }
     }
+    /// Delegates the reached check to the underlying movement model.
+    fn has_reached(&self, current_pose: &Pose, target_pose: &Pose) -> bool {
+        match self {
+            Movement::RombaMovement(romba) => romba.has_reached(current_pose, target_pose),
+            Movement::AckermannMovement(ackermann) => {
+                ackermann.has_reached(current_pose, target_pose)
+            }
+        }
+    }
+    /// Delegates maximum sub-step lookup to the underlying movement model.
+    fn max_sub_step(&self) -> Duration {
+        match self {
+            Movement::RombaMovement(romba) => romba.max_sub_step(),
+            Movement::AckermannMovement(ackermann) => ackermann.max_sub_step(),
+        }
+    }
 }
 impl Movement {
     /// Loads a movement model from a JSON file, panicking on failure.
     pub fn from_json_file(file_path: String) -> Self {
         load_json_or_panic(file_path)
     }
+    /// Loads a movement model from a JSON file.
+    pub fn try_from_json_file(file_path: String) -> Result<Self, FarmbotSimError> {
+        load_json(file_path)
+    }
     /// Returns the maximum allowed linear velocity for the movement model.
     pub fn max_velocity(&self) -> LinearVelocity {
         match &self {
             Movement::RombaMovement(rm) => rm.max_velocity,
+            Movement::AckermannMovement(am) => am.max_velocity,
         }
     }
+    /// Returns the maximum allowed angular velocity for the movement model.
+    pub fn max_angular_velocity(&self) -> AngularVelocity {
+        match &self {
+            Movement::RombaMovement(rm) => rm.max_angular_velocity,
+            Movement::AckermannMovement(am) => am.max_angular_velocity(),
+        }
+    }
+    /// Returns the distance of the wheels that scrub sideways while turning from the center.
+    /// Half the track of a Romba, zero for Ackermann steering where all wheels roll through turns.
+    pub fn turning_lever_arm(&self) -> Length {
+        match self {
+            Movement::RombaMovement(rm) => rm.wheel_distance / 2.0,
+            Movement::AckermannMovement(_) => Length::ZERO,
+        }
+    }
+    /// Returns the odometry noise of the movement model, if any.
+    pub fn noise(&self) -> Option<&OdometryNoise> {
+        match self {
+            Movement::RombaMovement(rm) => rm.noise.as_ref(),
+            Movement::AckermannMovement(am) => am.noise.as_ref(),
+        }
+    }
+}
+
+/// Computes the forward input for driving toward a target at the given distance in meters,
+/// slowing down close to it.
+pub fn forward_strength(position_error: f32) -> f32 {
+    if position_error < 0.5 {
+        position_error * 3.6 / 3.0
+    } else {
+        (position_error * 0.3).clamp(0.0, 1.0)
+    }
 }
@@ -0,0 +1,156 @@
+use egui::{Pos2, Vec2};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::f32::consts::PI;
+
+use crate::{
+    movement_module::pose::Pose,
+    units::{angle::Angle, duration::Duration, length::Length},
+    utilities::utils::random_normal,
+};
+
+/// Defines how far the true motion of an agent deviates from the motion it believes it made.
+#[derive(Clone, PartialEq, Copy, Debug, Serialize, Deserialize)]
+pub struct OdometryNoise {
+    /// Standard deviation of the relative error of driven distances and turned angles, e.g. 0.02 for 2 %.
+    pub wheel_slip: f32,
+    /// Standard deviation of the heading error accumulated over one meter of travel.
+    pub heading_drift: Angle,
+    /// Standard deviation of a GNSS position fix in each axis.
+    pub gnss_noise: Length,
+    /// Time between GNSS fixes while the agent drives, no fixes if zero.
+    pub gnss_interval: Duration,
+    /// Standard deviation of a heading fix (dual-antenna GNSS or compass) taken with every GNSS fix.
+    pub heading_noise: Angle,
+}
+impl Default for OdometryNoise {
+    /// Returns a default noise model of a wheeled robot with RTK-like GNSS fixes.
+    fn default() -> Self {
+        Self {
+            wheel_slip: 0.02,
+            heading_drift: Angle::degrees(1.0),
+            gnss_noise: Length::centimeters(2.0),
+            gnss_interval: Duration::seconds(1.0),
+            heading_noise: Angle::degrees(1.0),
+        }
+    }
+}
+
+impl OdometryNoise {
+    /// Applies the motion from `estimated_start` to `estimated_end` to the true pose,
+    /// distorted by wheel slip and heading drift.
+    pub fn apply_motion<R: Rng>(
+        &self,
+        true_pose: &Pose,
+        estimated_start: &Pose,
+        estimated_end: &Pose,
+        rng: &mut R,
+    ) -> Pose {
+        // Motion in the frame of the agent at the start, x forward and y to the left
+        let heading = estimated_start.orientation.to_radians();
+        let offset = estimated_end.position - estimated_start.position;
+        let forward = offset.x * heading.cos() + offset.y * heading.sin();
+        let left = -offset.x * heading.sin() + offset.y * heading.cos();
+        let turn = wrap_angle(estimated_end.orientation.to_radians() - heading);
+        if offset.length() == 0.0 && turn == 0.0 {
+            return true_pose.clone();
+        }
+
+        let slip = 1.0 + random_normal(rng, self.wheel_slip);
+        let drift = random_normal(
+            rng,
+            self.heading_drift.to_radians() * offset.length().sqrt(),
+        );
+
+        let true_heading = true_pose.orientation.to_radians();
+        let (forward, left) = (forward * slip, left * slip);
+        let position = true_pose.position
+            + Vec2::new(
+                forward * true_heading.cos() - left * true_heading.sin(),
+                forward * true_heading.sin() + left * true_heading.cos(),
+            );
+        let orientation = (true_heading + turn * slip + drift)
+            .to_degrees()
+            .rem_euclid(360.0);
+        Pose::new(position, Angle::degrees(orientation))
+    }
+
+    /// Returns how much the variance of the estimated position in m² grows over `distance` meters.
+    pub fn motion_variance(&self, distance: f32) -> f32 {
+        let slip = self.wheel_slip * distance;
+        // Heading drift displaces the agent sideways while it keeps driving
+        let drift = self.heading_drift.to_radians() * distance.sqrt() * distance;
+        slip * slip + drift * drift
+    }
+
+    /// Returns how much the variance of the estimated heading in rad² grows over the motion
+    /// from `estimated_start` to `estimated_end`.
+    pub fn heading_variance(&self, estimated_start: &Pose, estimated_end: &Pose) -> f32 {
+        let distance = estimated_start.position.distance(estimated_end.position);
+        let turn = wrap_angle(
+            estimated_end.orientation.to_radians() - estimated_start.orientation.to_radians(),
+        );
+        let drift = self.heading_drift.to_radians();
+        let slip = self.wheel_slip * turn;
+        drift * drift * distance + slip * slip
+    }
+
+    /// Corrects the estimated position with a GNSS fix of the true position, weighted by the
+    /// variance of the estimate (scalar Kalman update). Updates `variance` and returns the new estimate.
+    pub fn fuse_gnss_fix<R: Rng>(
+        &self,
+        estimated_position: Pos2,
+        variance: &mut f32,
+        true_position: Pos2,
+        rng: &mut R,
+    ) -> Pos2 {
+        let std_dev = self.gnss_noise.to_base_unit();
+        let fix =
+            true_position + Vec2::new(random_normal(rng, std_dev), random_normal(rng, std_dev));
+        let gnss_variance = std_dev * std_dev;
+        let gain = if *variance + gnss_variance > 0.0 {
+            *variance / (*variance + gnss_variance)
+        } else {
+            1.0
+        };
+        *variance *= 1.0 - gain;
+        estimated_position + (fix - estimated_position) * gain
+    }
+
+    /// Corrects the estimated heading with a heading fix of the true heading, weighted by the
+    /// variance of the estimate like `fuse_gnss_fix`. Updates `variance` and returns the new estimate.
+    pub fn fuse_heading_fix<R: Rng>(
+        &self,
+        estimated_heading: Angle,
+        variance: &mut f32,
+        true_heading: Angle,
+        rng: &mut R,
+    ) -> Angle {
+        let std_dev = self.heading_noise.to_radians();
+        let fix = true_heading.to_radians() + random_normal(rng, std_dev);
+        let fix_variance = std_dev * std_dev;
+        let gain = if *variance + fix_variance > 0.0 {
+            *variance / (*variance + fix_variance)
+        } else {
+            1.0
+        };
+        *variance *= 1.0 - gain;
+        let estimate = estimated_heading.to_radians();
+        let innovation = wrap_angle(fix - estimate);
+        Angle::degrees(
+            (estimate + innovation * gain)
+                .to_degrees()
+                .rem_euclid(360.0),
+        )
+    }
+
+    /// Returns true if GNSS fixes are enabled.
+    pub fn has_gnss(&self) -> bool {
+        self.gnss_interval > Duration::ZERO
+    }
+}
+
+/// Wraps an angle in radians to -PI..PI.
+fn wrap_angle(radians: f32) -> f32 {
+    (radians + PI).rem_euclid(2.0 * PI) - PI
+}
@@ -1,9 +1,15 @@
-use egui::Vec2;
+use egui::{Pos2, Vec2};
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    cfg::{TOLERANCE_ANGLE, TOLERANCE_DISTANCE},
-    movement_module::{is_movement::IsMovement, movement::MovementInputs, pose::Pose},
+    cfg::{TOLERANCE_ANGLE, TOLERANCE_ARC_HEADING, TOLERANCE_DISTANCE},
+    logger::log_error_and_panic,
+    movement_module::{
+        is_movement::IsMovement,
+        movement::{MovementInputs, MovementState, forward_strength},
+        odometry_noise::OdometryNoise,
+        pose::Pose,
+    },
     units::{
         angle::Angle, angular_velocity::AngularVelocity, duration::Duration, length::Length,
         linear_velocity::LinearVelocity,
@@ -53,6 +59,10 @@ pub struct RombaMovement {
     pub max_angular_velocity: AngularVelocity,
     pub wheel_distance: Length,
     pub wheel_radius: Length,
+    #[serde(default = "default_max_sub_step")]
+    pub max_sub_step: Duration,
+    /// Odometry noise, the agent knows its pose exactly if `None`.
+    pub noise: Option<OdometryNoise>,
 }
 impl Default for RombaMovement {
     /// Returns a default Romba movement configuration.
@@ -62,14 +72,22 @@ impl Default for RombaMovement {
             max_angular_velocity: AngularVelocity::radians_per_second(0.1),
             wheel_distance: Length::meters(0.2),
             wheel_radius: Length::meters(0.05),
+            max_sub_step: Duration::seconds(1.0),
+            noise: None,
         }
     }
 }
 
+/// Returns the longest sub step of the default Romba movement.
+fn default_max_sub_step() -> Duration {
+    RombaMovement::default().max_sub_step
+}
+
 impl IsMovement for RombaMovement {
     /// Computes the new pose based on motor inputs and simulation time.
     fn calculate_new_pose_from_inputs(
         &self,
+        _state: &mut MovementState,
         simulation_step: Duration,
         inputs: MovementInputs,
         current_pose: Pose,
@@ -121,10 +139,8 @@ impl IsMovement for RombaMovement {
                 let new_pose = Pose::new(new_position, new_orientation);
 
                 (new_pose, current_velocity, omega)
-            } // _ => {
-              //     let msg = "Invalid inputs for RombaMovement";
-              //     log_error_and_panic(msg);
-              // },
+            }
+            _ => log_error_and_panic("Invalid inputs for RombaMovement"),
         }
     }
     /// Computes motor inputs needed to move toward the target pose.
@@ -148,6 +164,14 @@ impl IsMovement for RombaMovement {
         let should_move = position_error > TOLERANCE_DISTANCE.to_base_unit();
 
         let (left, right) = match (should_move, should_turn) {
+            (true, true)
+                if desired_orientation
+                    .is_close_to(current_pose.orientation, TOLERANCE_ARC_HEADING) =>
+            {
+                // Slightly off course, steer back while driving
+                let curvature = Self::curvature_to(current_pose, target_pose.position);
+                self.arc_inputs(curvature, position_error)
+            }
             (true, true) => {
                 // Need to rotate toward the direction of the next target position
                 let desired_direction = (target_pose.position - current_pose.position).normalized();
@@ -156,13 +180,8 @@ impl IsMovement for RombaMovement {
             }
             (true, false) => {
                 // Drive straight toward the target
-                if position_error < 0.5 {
-                    let forward_strength = position_error * 3.6 / 3.0;
-                    (forward_strength, forward_strength)
-                } else {
-                    let forward_strength = (position_error * 0.3).clamp(0.0, 1.0);
-                    (forward_strength, forward_strength)
-                }
+                let forward_strength = forward_strength(position_error);
+                (forward_strength, forward_strength)
             }
             (false, true) => {
                 // We're close to the target position, now match final orientation
@@ -173,9 +192,66 @@ impl IsMovement for RombaMovement {
 
         MovementInputs::Romba(RombaMovementInputs::new(left, right))
     }
+    /// Computes motor inputs that follow the arc to the target pose, the outer wheel runs faster.
+    fn calculate_inputs_along_arc(
+        &self,
+        current_pose: &Pose,
+        target_pose: &Pose,
+    ) -> MovementInputs {
+        let position_error = current_pose.position.distance(target_pose.position);
+        let curvature = Self::arc_curvature(current_pose, target_pose);
+        match curvature {
+            Some(curvature) if position_error > TOLERANCE_DISTANCE.to_base_unit() => {
+                let (left, right) = self.arc_inputs(curvature, position_error);
+                MovementInputs::Romba(RombaMovementInputs::new(left, right))
+            }
+            _ => self.calculate_inputs_for_target(current_pose, target_pose),
+        }
+    }
+    /// Returns the longest time step integrated at once.
+    fn max_sub_step(&self) -> Duration {
+        self.max_sub_step
+    }
 }
 
 impl RombaMovement {
+    /// Returns the curvature in 1/m of the arc leaving the current pose along its orientation
+    /// and passing through the target position, if the target lies ahead and the arc ends
+    /// in the target orientation.
+    fn arc_curvature(current_pose: &Pose, target_pose: &Pose) -> Option<f32> {
+        let heading = current_pose.orientation.to_radians();
+        let offset = target_pose.position - current_pose.position;
+        // Target in the frame of the agent, x forward and y to the left
+        let x = offset.x * heading.cos() + offset.y * heading.sin();
+        let y = -offset.x * heading.sin() + offset.y * heading.cos();
+        if x <= 0.0 {
+            return None;
+        }
+        let end_orientation = Angle::radians(heading + 2.0 * y.atan2(x));
+        end_orientation
+            .is_close_to(target_pose.orientation, TOLERANCE_ARC_HEADING)
+            .then(|| 2.0 * y / offset.length_sq())
+    }
+    /// Returns the curvature in 1/m of the arc leaving the current pose along its orientation
+    /// and passing through the target position.
+    fn curvature_to(current_pose: &Pose, target: Pos2) -> f32 {
+        let heading = current_pose.orientation.to_radians();
+        let offset = target - current_pose.position;
+        let y = -offset.x * heading.sin() + offset.y * heading.cos();
+        2.0 * y / offset.length_sq()
+    }
+    /// Computes input values that drive along an arc of the given curvature, the outer wheel runs faster.
+    fn arc_inputs(&self, curvature: f32, position_error: f32) -> (f32, f32) {
+        let forward_strength = forward_strength(position_error);
+        let offset = curvature * self.wheel_distance.to_base_unit() / 2.0;
+        let (left, right) = (
+            forward_strength * (1.0 - offset),
+            forward_strength * (1.0 + offset),
+        );
+        // Keep the ratio of the wheels when scaling into the motor range
+        let scale = left.abs().max(right.abs()).max(1.0);
+        (left / scale, right / scale)
+    }
     /// Computes input values to rotate toward a desired angle.
     fn turning_inputs(current: Angle, target: Angle) -> (f32, f32) {
         let delta = (target.to_degrees() - current.to_degrees() + 180.0).rem_euclid(360.0) - 180.0;
@@ -0,0 +1,423 @@
+use egui::{Pos2, Rect, Vec2};
+use serde::{Deserialize, Serialize};
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, HashSet},
+};
+
+use crate::{
+    environment::obstacle::{NoGoZone, Obstacle, no_go_penalty},
+    path_finding_module::path_finding::PathFinding,
+    units::length::Length,
+    utilities::utils::{point_in_polygon, polygons_overlap},
+};
+
+/// Cell of the occupancy grid, the cell `(i, j)` covers `[i, i + 1) x [j, j + 1)` times the cell size.
+pub type Cell = (i32, i32);
+
+/// Number of free cells kept around the obstacles, start and end when searching.
+const SEARCH_MARGIN: i32 = 5;
+
+/// Occupancy grid planner using 8-connected A*, or Theta* for any-angle paths.
+/// Only the parameters are serialized, the grid is rebuilt with `recalculate`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct GridPlanner {
+    pub cell_size: Length,
+    /// Uses Theta* and connects cells in line of sight directly.
+    pub any_angle: bool,
+    #[serde(skip)]
+    pub blocked: HashSet<Cell>,
+    #[serde(skip)]
+    bounds: Option<Rect>,
+    #[serde(skip)]
+    obstacles: Vec<Obstacle>,
+    #[serde(skip)]
+    no_go_zones: Vec<NoGoZone>,
+}
+
+impl PathFinding for GridPlanner {
+    fn find_path(&mut self, start: Pos2, end: Pos2) -> Option<Vec<Pos2>> {
+        if self.cell_size <= Length::ZERO {
+            return None;
+        }
+        let start_cell = self.cell_of(start);
+        let end_cell = self.cell_of(end);
+        if start_cell == end_cell {
+            return Some(vec![start, end]);
+        }
+
+        let area = match self.bounds {
+            Some(bounds) => bounds.union(Rect::from_two_pos(start, end)),
+            None => Rect::from_two_pos(start, end),
+        };
+        let (min, max) = (self.cell_of(area.min), self.cell_of(area.max));
+        let search = Search {
+            planner: self,
+            escape: self.escape_cells(start, end),
+            start,
+            end,
+            start_cell,
+            end_cell,
+            min: (min.0 - SEARCH_MARGIN, min.1 - SEARCH_MARGIN),
+            max: (max.0 + SEARCH_MARGIN, max.1 + SEARCH_MARGIN),
+        };
+        let cells = search.run()?;
+
+        let path: Vec<Pos2> = cells.iter().map(|&cell| search.position(cell)).collect();
+        Some(if self.any_angle {
+            path
+        } else {
+            remove_collinear(path)
+        })
+    }
+}
+
+impl GridPlanner {
+    /// Creates a new `GridPlanner` with the given cell size, blocking cells covered by obstacles.
+    pub fn new(
+        cell_size: Length,
+        any_angle: bool,
+        points: &[Pos2],
+        obstacles: &[Obstacle],
+    ) -> Self {
+        let mut planner = Self {
+            cell_size,
+            any_angle,
+            blocked: HashSet::new(),
+            bounds: None,
+            obstacles: vec![],
+            no_go_zones: vec![],
+        };
+        planner.recalculate(points, obstacles);
+        planner
+    }
+
+    /// Rebuilds the occupancy grid from the obstacles, `points` only extend the search area.
+    pub fn recalculate(&mut self, points: &[Pos2], obstacles: &[Obstacle]) {
+        self.blocked.clear();
+        self.obstacles = obstacles.to_vec();
+        let all_points = points
+            .iter()
+            .chain(obstacles.iter().flat_map(|o| o.points.iter()));
+        self.bounds = all_points.fold(None, |bounds: Option<Rect>, p| match bounds {
+            Some(bounds) => Some(bounds.union(Rect::from_pos(*p))),
+            None => Some(Rect::from_pos(*p)),
+        });
+        if self.cell_size <= Length::ZERO {
+            return;
+        }
+
+        for obstacle in obstacles {
+            let cells = self.obstacle_cells(obstacle);
+            self.blocked.extend(cells);
+        }
+    }
+
+    /// Sets the no-go zones whose penalty is added to the cost of moves crossing them.
+    pub fn set_no_go_zones(&mut self, no_go_zones: Vec<NoGoZone>) {
+        self.no_go_zones = no_go_zones;
+    }
+
+    /// Returns the cells overlapped by the obstacle.
+    fn obstacle_cells(&self, obstacle: &Obstacle) -> Vec<Cell> {
+        let Some(first) = obstacle.points.first() else {
+            return vec![];
+        };
+        let obstacle_bounds = obstacle
+            .points
+            .iter()
+            .fold(Rect::from_pos(*first), |r, p| r.union(Rect::from_pos(*p)));
+        let (min, max) = (
+            self.cell_of(obstacle_bounds.min),
+            self.cell_of(obstacle_bounds.max),
+        );
+        let mut cells = vec![];
+        for i in min.0..=max.0 {
+            for j in min.1..=max.1 {
+                if polygons_overlap(&self.cell_corners((i, j)), &obstacle.points) {
+                    cells.push((i, j));
+                }
+            }
+        }
+        cells
+    }
+
+    /// Returns the cells of obstacles containing `start` or `end` that no other obstacle
+    /// overlaps, so that paths can leave and enter them.
+    fn escape_cells(&self, start: Pos2, end: Pos2) -> HashSet<Cell> {
+        let (escape, others): (Vec<&Obstacle>, Vec<&Obstacle>) =
+            self.obstacles.iter().partition(|obstacle| {
+                point_in_polygon(start, &obstacle.points) || point_in_polygon(end, &obstacle.points)
+            });
+        escape
+            .into_iter()
+            .flat_map(|obstacle| self.obstacle_cells(obstacle))
+            .filter(|&cell| {
+                let corners = self.cell_corners(cell);
+                !others
+                    .iter()
+                    .any(|obstacle| polygons_overlap(&corners, &obstacle.points))
+            })
+            .collect()
+    }
+
+    /// Returns the cell containing `pos`.
+    pub fn cell_of(&self, pos: Pos2) -> Cell {
+        let cell_size = self.cell_size.to_base_unit();
+        (
+            (pos.x / cell_size).floor() as i32,
+            (pos.y / cell_size).floor() as i32,
+        )
+    }
+
+    /// Returns the center of the cell.
+    pub fn cell_center(&self, cell: Cell) -> Pos2 {
+        let cell_size = self.cell_size.to_base_unit();
+        Pos2::new(
+            (cell.0 as f32 + 0.5) * cell_size,
+            (cell.1 as f32 + 0.5) * cell_size,
+        )
+    }
+
+    /// Returns the corners of the cell in counter-clockwise order.
+    pub fn cell_corners(&self, cell: Cell) -> Vec<Pos2> {
+        let cell_size = self.cell_size.to_base_unit();
+        let min = Pos2::new(cell.0 as f32 * cell_size, cell.1 as f32 * cell_size);
+        vec![
+            min,
+            min + Vec2::new(cell_size, 0.0),
+            min + Vec2::new(cell_size, cell_size),
+            min + Vec2::new(0.0, cell_size),
+        ]
+    }
+}
+
+/// Removes points that lie on the straight line between their neighbours.
+fn remove_collinear(path: Vec<Pos2>) -> Vec<Pos2> {
+    let mut result: Vec<Pos2> = vec![];
+    for p in path {
+        if result.len() >= 2 {
+            let a = result[result.len() - 2];
+            let b = result[result.len() - 1];
+            let cross = (b - a).x * (p - b).y - (b - a).y * (p - b).x;
+            if cross.abs() < 1e-6 {
+                result.pop();
+            }
+        }
+        result.push(p);
+    }
+    result
+}
+
+/// Entry of the open list, ordered so that the lowest estimated cost is popped first.
+#[derive(Debug)]
+struct OpenEntry {
+    estimate: f32,
+    order: usize,
+    cell: Cell,
+}
+
+impl PartialEq for OpenEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+impl Eq for OpenEntry {}
+impl PartialOrd for OpenEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for OpenEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .estimate
+            .total_cmp(&self.estimate)
+            .then_with(|| other.order.cmp(&self.order))
+    }
+}
+
+/// A* or Theta* search for a single path inside the cell range `min..=max`.
+struct Search<'a> {
+    planner: &'a GridPlanner,
+    /// Blocked cells that may be entered, see `GridPlanner::escape_cells`.
+    escape: HashSet<Cell>,
+    start: Pos2,
+    end: Pos2,
+    start_cell: Cell,
+    end_cell: Cell,
+    min: Cell,
+    max: Cell,
+}
+
+impl Search<'_> {
+    /// Returns the cells of the cheapest path from the start to the end cell.
+    fn run(&self) -> Option<Vec<Cell>> {
+        let mut costs: HashMap<Cell, f32> = HashMap::from([(self.start_cell, 0.0)]);
+        let mut parents: HashMap<Cell, Cell> = HashMap::new();
+        let mut closed: HashSet<Cell> = HashSet::new();
+        let mut open = BinaryHeap::from([OpenEntry {
+            estimate: self.start.distance(self.end),
+            order: 0,
+            cell: self.start_cell,
+        }]);
+        let mut order = 0;
+
+        while let Some(OpenEntry { cell, .. }) = open.pop() {
+            if cell == self.end_cell {
+                let mut cells = vec![cell];
+                let mut current = cell;
+                while let Some(&parent) = parents.get(&current) {
+                    cells.push(parent);
+                    current = parent;
+                }
+                cells.reverse();
+                return Some(cells);
+            }
+            if !closed.insert(cell) {
+                continue;
+            }
+
+            for neighbor in self.neighbors(cell) {
+                if closed.contains(&neighbor) {
+                    continue;
+                }
+                // Theta*: connect to the grandparent if it sees the neighbor,
+                // unless a no-go zone makes the direct line more expensive
+                let mut via = cell;
+                let mut cost = self.move_cost(&costs, cell, neighbor);
+                if let Some(&parent) = parents.get(&cell)
+                    && self.planner.any_angle
+                    && self.line_of_sight(self.position(parent), self.position(neighbor))
+                {
+                    let parent_cost = self.move_cost(&costs, parent, neighbor);
+                    if parent_cost <= cost {
+                        via = parent;
+                        cost = parent_cost;
+                    }
+                }
+                if costs.get(&neighbor).is_none_or(|&c| cost < c) {
+                    costs.insert(neighbor, cost);
+                    parents.insert(neighbor, via);
+                    order += 1;
+                    open.push(OpenEntry {
+                        estimate: cost + self.position(neighbor).distance(self.end),
+                        order,
+                        cell: neighbor,
+                    });
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns the cost of reaching `to` from the already reached cell `from`.
+    fn move_cost(&self, costs: &HashMap<Cell, f32>, from: Cell, to: Cell) -> f32 {
+        let (a, b) = (self.position(from), self.position(to));
+        costs[&from] + a.distance(b) + no_go_penalty(&self.planner.no_go_zones, a, b)
+    }
+
+    /// Returns the position used for the cell, the exact start and end for their cells.
+    fn position(&self, cell: Cell) -> Pos2 {
+        if cell == self.start_cell {
+            self.start
+        } else if cell == self.end_cell {
+            self.end
+        } else {
+            self.planner.cell_center(cell)
+        }
+    }
+
+    /// Returns whether the cell can be entered, start and end cells are always free.
+    fn is_free(&self, cell: Cell) -> bool {
+        cell == self.start_cell
+            || cell == self.end_cell
+            || (cell.0 >= self.min.0
+                && cell.0 <= self.max.0
+                && cell.1 >= self.min.1
+                && cell.1 <= self.max.1
+                && (!self.planner.blocked.contains(&cell) || self.escape.contains(&cell)))
+    }
+
+    /// Returns free 8-connected neighbors, diagonals only if they don't cut a blocked corner.
+    fn neighbors(&self, cell: Cell) -> Vec<Cell> {
+        let mut neighbors = vec![];
+        for di in -1..=1 {
+            for dj in -1..=1 {
+                if di == 0 && dj == 0 {
+                    continue;
+                }
+                let neighbor = (cell.0 + di, cell.1 + dj);
+                let diagonal_clear = di == 0
+                    || dj == 0
+                    || (self.is_free((cell.0 + di, cell.1)) && self.is_free((cell.0, cell.1 + dj)));
+                if diagonal_clear && self.is_free(neighbor) {
+                    neighbors.push(neighbor);
+                }
+            }
+        }
+        neighbors
+    }
+
+    /// Returns whether all cells touched by the segment from `a` to `b` are free.
+    fn line_of_sight(&self, a: Pos2, b: Pos2) -> bool {
+        let cell_size = self.planner.cell_size.to_base_unit();
+        let (mut cell, end) = (self.planner.cell_of(a), self.planner.cell_of(b));
+        let d = b - a;
+        let step = (d.x.signum() as i32, d.y.signum() as i32);
+        let t_delta = Vec2::new(
+            if d.x != 0.0 {
+                cell_size / d.x.abs()
+            } else {
+                f32::INFINITY
+            },
+            if d.y != 0.0 {
+                cell_size / d.y.abs()
+            } else {
+                f32::INFINITY
+            },
+        );
+        let boundary = |c: i32, s: i32, origin: f32, delta: f32| {
+            if delta == 0.0 {
+                f32::INFINITY
+            } else {
+                ((c + s.max(0)) as f32 * cell_size - origin) / delta
+            }
+        };
+        let mut t_max = Vec2::new(
+            boundary(cell.0, step.0, a.x, d.x),
+            boundary(cell.1, step.1, a.y, d.y),
+        );
+
+        let max_steps = (end.0 - cell.0).abs() + (end.1 - cell.1).abs() + 2;
+        for _ in 0..max_steps {
+            if !self.is_free(cell) {
+                return false;
+            }
+            if cell == end {
+                return true;
+            }
+            match t_max.x.total_cmp(&t_max.y) {
+                Ordering::Less => {
+                    cell.0 += step.0;
+                    t_max.x += t_delta.x;
+                }
+                Ordering::Greater => {
+                    cell.1 += step.1;
+                    t_max.y += t_delta.y;
+                }
+                Ordering::Equal => {
+                    // Passing exactly through a corner touches both side cells
+                    if !self.is_free((cell.0 + step.0, cell.1))
+                        || !self.is_free((cell.0, cell.1 + step.1))
+                    {
+                        return false;
+                    }
+                    cell = (cell.0 + step.0, cell.1 + step.1);
+                    t_max += t_delta;
+                }
+            }
+        }
+        false
+    }
+}
@@ -1,4 +1,9 @@
 //! Module contains path finding
 
+pub mod grid_planner;
+pub mod path_cache;
 pub mod path_finding;
+pub mod path_planner;
+pub mod path_smoothing;
+pub mod reservation_planner;
 pub mod visibility_graph;
@@ -0,0 +1,206 @@
+use egui::Pos2;
+use std::collections::HashMap;
+
+use crate::{cfg::TOLERANCE_DISTANCE, path_finding_module::path_finding::PathFinding};
+
+/// Cell of the index used to look up key points near a position.
+type IndexCell = (i32, i32);
+
+/// Cache of paths between static key points such as task poses, station poses and spawn positions.
+/// Paths are planned on first use and kept until the obstacles change.
+/// Positions within `TOLERANCE_DISTANCE` of a key point share its paths.
+/// Key points added after the permanent ones, e.g. spawn positions, can be removed again.
+#[derive(Clone, Debug, Default)]
+pub struct PathCache {
+    key_points: Vec<Pos2>,
+    n_permanent_key_points: usize,
+    index: HashMap<IndexCell, Vec<usize>>,
+    paths: HashMap<(usize, usize), Option<Vec<Pos2>>>,
+    /// Number of requests answered from the cache.
+    pub hits: u64,
+    /// Number of requests planned with the path planner.
+    pub misses: u64,
+}
+
+impl PathCache {
+    /// Adds key points, points close to an existing key point are ignored.
+    pub fn add_key_points(&mut self, points: impl IntoIterator<Item = Pos2>) {
+        for point in points {
+            if self.key_of(point).is_none() {
+                self.index
+                    .entry(Self::index_cell(point))
+                    .or_default()
+                    .push(self.key_points.len());
+                self.key_points.push(point);
+            }
+        }
+    }
+
+    /// Makes all current key points permanent.
+    pub fn make_key_points_permanent(&mut self) {
+        self.n_permanent_key_points = self.key_points.len();
+    }
+
+    /// Removes the key points added after the permanent ones and their paths.
+    pub fn remove_temporary_key_points(&mut self) {
+        let n_permanent = self.n_permanent_key_points;
+        if self.key_points.len() == n_permanent {
+            return;
+        }
+        self.key_points.truncate(n_permanent);
+        self.index.retain(|_, keys| {
+            keys.retain(|&key| key < n_permanent);
+            !keys.is_empty()
+        });
+        self.paths
+            .retain(|&(start, end), _| start < n_permanent && end < n_permanent);
+    }
+
+    /// Returns the number of key points.
+    pub fn n_key_points(&self) -> usize {
+        self.key_points.len()
+    }
+
+    /// Returns the number of cached paths, including pairs without a path.
+    pub fn n_paths(&self) -> usize {
+        self.paths.len()
+    }
+
+    /// Finds a path with `planner`, reusing the cached path if both ends are key points.
+    /// The returned path starts at `start` and ends at `end`.
+    pub fn find_path(
+        &mut self,
+        planner: &mut impl PathFinding,
+        start: Pos2,
+        end: Pos2,
+    ) -> Option<Vec<Pos2>> {
+        let (Some(start_key), Some(end_key)) = (self.key_of(start), self.key_of(end)) else {
+            return planner.find_path(start, end);
+        };
+        let path = match self.paths.get(&(start_key, end_key)) {
+            Some(path) => {
+                self.hits += 1;
+                path.clone()
+            }
+            None => {
+                self.misses += 1;
+                let path = planner.find_path(self.key_points[start_key], self.key_points[end_key]);
+                self.paths.insert((start_key, end_key), path.clone());
+                path
+            }
+        };
+        path.map(|mut path| {
+            if let Some(first) = path.first_mut() {
+                *first = start;
+            }
+            if let Some(last) = path.last_mut() {
+                *last = end;
+            }
+            path
+        })
+    }
+
+    /// Returns the index of the key point within `TOLERANCE_DISTANCE` of `pos`, if any.
+    fn key_of(&self, pos: Pos2) -> Option<usize> {
+        let cell = Self::index_cell(pos);
+        (-1..=1)
+            .flat_map(|di| (-1..=1).map(move |dj| (cell.0 + di, cell.1 + dj)))
+            .filter_map(|cell| self.index.get(&cell))
+            .flatten()
+            .copied()
+            .find(|&key| self.key_points[key].distance(pos) <= TOLERANCE_DISTANCE.to_base_unit())
+    }
+
+    /// Returns the index cell of the position, cells are as large as the tolerance.
+    fn index_cell(pos: Pos2) -> IndexCell {
+        let cell_size = TOLERANCE_DISTANCE.to_base_unit();
+        (
+            (pos.x / cell_size).floor() as i32,
+            (pos.y / cell_size).floor() as i32,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Planner returning a path over the midpoint, recording the requested ends.
+    #[derive(Default)]
+    struct MidpointPlanner {
+        requests: Vec<(Pos2, Pos2)>,
+    }
+
+    impl PathFinding for MidpointPlanner {
+        fn find_path(&mut self, start: Pos2, end: Pos2) -> Option<Vec<Pos2>> {
+            self.requests.push((start, end));
+            Some(vec![start, start.lerp(end, 0.5), end])
+        }
+    }
+
+    fn cache() -> PathCache {
+        let mut cache = PathCache::default();
+        cache.add_key_points([Pos2::new(0.0, 0.0), Pos2::new(4.0, 2.0)]);
+        cache.make_key_points_permanent();
+        cache
+    }
+
+    #[test]
+    fn positions_within_tolerance_share_the_key_point_path() {
+        let (mut cache, mut planner) = (cache(), MidpointPlanner::default());
+        let offset = TOLERANCE_DISTANCE.to_base_unit() * 0.8;
+        let (start, end) = (Pos2::new(offset, 0.0), Pos2::new(4.0, 2.0 - offset));
+
+        cache.find_path(&mut planner, Pos2::new(0.0, 0.0), Pos2::new(4.0, 2.0));
+        let path = cache.find_path(&mut planner, start, end).unwrap();
+
+        assert_eq!(planner.requests.len(), 1);
+        assert_eq!(path, vec![start, Pos2::new(2.0, 1.0), end]);
+        assert_eq!((cache.hits, cache.misses), (1, 1));
+        assert_eq!(cache.n_paths(), 1);
+    }
+
+    #[test]
+    fn misses_plan_between_the_key_points() {
+        let (mut cache, mut planner) = (cache(), MidpointPlanner::default());
+        let start = Pos2::new(0.0, TOLERANCE_DISTANCE.to_base_unit() * 0.5);
+
+        let path = cache
+            .find_path(&mut planner, start, Pos2::new(4.0, 2.0))
+            .unwrap();
+
+        assert_eq!(
+            planner.requests,
+            vec![(Pos2::new(0.0, 0.0), Pos2::new(4.0, 2.0))]
+        );
+        assert_eq!(path.first(), Some(&start));
+        assert_eq!((cache.hits, cache.misses), (0, 1));
+    }
+
+    #[test]
+    fn positions_beyond_tolerance_bypass_the_cache() {
+        let (mut cache, mut planner) = (cache(), MidpointPlanner::default());
+        let start = Pos2::new(TOLERANCE_DISTANCE.to_base_unit() * 2.0, 0.0);
+
+        cache.find_path(&mut planner, start, Pos2::new(4.0, 2.0));
+        cache.find_path(&mut planner, start, Pos2::new(4.0, 2.0));
+
+        assert_eq!(planner.requests.len(), 2);
+        assert_eq!((cache.hits, cache.misses), (0, 0));
+        assert_eq!(cache.n_paths(), 0);
+    }
+
+    #[test]
+    fn removing_temporary_key_points_drops_their_paths() {
+        let (mut cache, mut planner) = (cache(), MidpointPlanner::default());
+        let spawn = Pos2::new(-3.0, 1.0);
+        cache.add_key_points([spawn]);
+
+        cache.find_path(&mut planner, spawn, Pos2::new(4.0, 2.0));
+        cache.find_path(&mut planner, Pos2::new(0.0, 0.0), Pos2::new(4.0, 2.0));
+        cache.remove_temporary_key_points();
+
+        assert_eq!(cache.n_key_points(), 2);
+        assert_eq!(cache.n_paths(), 1);
+    }
+}
@@ -1,8 +1,51 @@
 use egui::Pos2;
 
+use crate::{
+    agent_module::agent::AgentId,
+    movement_module::pose::Pose,
+    units::{
+        angular_velocity::AngularVelocity, duration::Duration, length::Length,
+        linear_velocity::LinearVelocity,
+    },
+};
+
+/// Describes an agent that needs a path at a given time, used by planners that consider time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathRequest {
+    pub agent_id: AgentId,
+    pub start: Pose,
+    pub end: Pos2,
+    /// Time at which the agent departs, measured from the start of the episode.
+    pub departure: Duration,
+    pub velocity: LinearVelocity,
+    pub angular_velocity: AngularVelocity,
+    pub footprint_radius: Length,
+}
+
+/// Straight motion an agent performs after its planned path, e.g. between crops or along a row.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FollowUpMotion {
+    /// Point the agent drives to, the point it stands at for work in place.
+    pub to: Pos2,
+    pub velocity: LinearVelocity,
+    /// Time the agent stays at `to` after arriving.
+    pub hold: Duration,
+}
+
 /// Trait defining pathfinding functionality.
 pub trait PathFinding {
     /// Finds a path from `start` to `end`.
     /// If no path returns None.
     fn find_path(&mut self, start: Pos2, end: Pos2) -> Option<Vec<Pos2>>;
+
+    /// Finds a path for the request, each point paired with the time to wait there before moving on.
+    /// If no path returns None. By default never waits.
+    fn find_timed_path(&mut self, request: &PathRequest) -> Option<Vec<(Pos2, Duration)>> {
+        self.find_path(request.start.position, request.end)
+            .map(|path| path.into_iter().map(|p| (p, Duration::ZERO)).collect())
+    }
+
+    /// Plans the motions the agent performs after the end of the path found last for it.
+    /// By default nothing is planned.
+    fn follow_up(&mut self, _agent_id: AgentId, _motions: &[FollowUpMotion]) {}
 }
@@ -0,0 +1,133 @@
+use egui::Pos2;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    agent_module::agent::AgentId,
+    environment::obstacle::{NoGoZone, Obstacle},
+    path_finding_module::{
+        grid_planner::GridPlanner,
+        path_finding::{FollowUpMotion, PathFinding, PathRequest},
+        reservation_planner::ReservationPlanner,
+        visibility_graph::VisibilityGraph,
+    },
+    units::{duration::Duration, length::Length},
+};
+
+/// Selects the path planner used by the task manager.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PathPlannerKind {
+    /// Shortest paths on the visibility graph, other agents are ignored.
+    #[default]
+    VisibilityGraph,
+    /// Visibility graph paths that wait for space-time segments reserved by other agents.
+    Reservation,
+    /// A* on an occupancy grid, Theta* if `any_angle` is set.
+    Grid { cell_size: Length, any_angle: bool },
+}
+
+/// Represents a configurable path planner.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum PathPlanner {
+    VisibilityGraph(VisibilityGraph),
+    Reservation(ReservationPlanner),
+    Grid(GridPlanner),
+}
+impl PathFinding for PathPlanner {
+    /// Delegates path finding to the underlying planner.
+    fn find_path(&mut self, start: Pos2, end: Pos2) -> Option<Vec<Pos2>> {
+        match self {
+            PathPlanner::VisibilityGraph(graph) => graph.find_path(start, end),
+            PathPlanner::Reservation(planner) => planner.find_path(start, end),
+            PathPlanner::Grid(planner) => planner.find_path(start, end),
+        }
+    }
+    /// Delegates timed path finding to the underlying planner.
+    fn find_timed_path(&mut self, request: &PathRequest) -> Option<Vec<(Pos2, Duration)>> {
+        match self {
+            PathPlanner::VisibilityGraph(graph) => graph.find_timed_path(request),
+            PathPlanner::Reservation(planner) => planner.find_timed_path(request),
+            PathPlanner::Grid(planner) => planner.find_timed_path(request),
+        }
+    }
+    /// Delegates planning of follow-up motions to the underlying planner.
+    fn follow_up(&mut self, agent_id: AgentId, motions: &[FollowUpMotion]) {
+        match self {
+            PathPlanner::VisibilityGraph(graph) => graph.follow_up(agent_id, motions),
+            PathPlanner::Reservation(planner) => planner.follow_up(agent_id, motions),
+            PathPlanner::Grid(planner) => planner.follow_up(agent_id, motions),
+        }
+    }
+}
+impl PathPlanner {
+    /// Creates a planner of the given kind from points and obstacles.
+    pub fn new(kind: PathPlannerKind, points: &[Pos2], obstacles: Vec<Obstacle>) -> Self {
+        match kind {
+            PathPlannerKind::VisibilityGraph => {
+                PathPlanner::VisibilityGraph(VisibilityGraph::new(points, obstacles))
+            }
+            PathPlannerKind::Reservation => {
+                PathPlanner::Reservation(ReservationPlanner::new(points, obstacles))
+            }
+            PathPlannerKind::Grid {
+                cell_size,
+                any_angle,
+            } => PathPlanner::Grid(GridPlanner::new(cell_size, any_angle, points, &obstacles)),
+        }
+    }
+    /// Returns the kind of the planner.
+    pub fn kind(&self) -> PathPlannerKind {
+        match self {
+            PathPlanner::VisibilityGraph(_) => PathPlannerKind::VisibilityGraph,
+            PathPlanner::Reservation(_) => PathPlannerKind::Reservation,
+            PathPlanner::Grid(planner) => PathPlannerKind::Grid {
+                cell_size: planner.cell_size,
+                any_angle: planner.any_angle,
+            },
+        }
+    }
+    /// Rebuilds the planner's graph with new points and obstacles.
+    pub fn recalculate(&mut self, points: &[Pos2], obstacles: &[Obstacle]) {
+        match self {
+            PathPlanner::VisibilityGraph(graph) => graph.recalculate(points, obstacles),
+            PathPlanner::Reservation(planner) => planner.recalculate(points, obstacles),
+            PathPlanner::Grid(planner) => planner.recalculate(points, obstacles),
+        }
+    }
+    /// Sets the no-go zones, paths cross them only if going around is more expensive.
+    pub fn set_no_go_zones(&mut self, no_go_zones: Vec<NoGoZone>) {
+        match self {
+            PathPlanner::VisibilityGraph(graph) => graph.set_no_go_zones(no_go_zones),
+            PathPlanner::Reservation(planner) => planner.set_no_go_zones(no_go_zones),
+            PathPlanner::Grid(planner) => planner.set_no_go_zones(no_go_zones),
+        }
+    }
+    /// Returns whether paths depend only on the obstacles and not on paths planned before.
+    pub fn is_static(&self) -> bool {
+        match self {
+            PathPlanner::VisibilityGraph(_) | PathPlanner::Grid(_) => true,
+            PathPlanner::Reservation(_) => false,
+        }
+    }
+    /// Forgets everything planned for the agents so far.
+    pub fn reset(&mut self) {
+        match self {
+            PathPlanner::VisibilityGraph(_) | PathPlanner::Grid(_) => {}
+            PathPlanner::Reservation(planner) => {
+                planner.clear_reservations();
+                planner.n_fallbacks = 0;
+            }
+        }
+    }
+    /// Returns how many paths ignore other agents because planning around them was given up.
+    pub fn n_fallbacks(&self) -> u32 {
+        match self {
+            PathPlanner::VisibilityGraph(_) | PathPlanner::Grid(_) => 0,
+            PathPlanner::Reservation(planner) => planner.n_fallbacks,
+        }
+    }
+}
+impl Default for PathPlanner {
+    fn default() -> Self {
+        PathPlanner::VisibilityGraph(VisibilityGraph::default())
+    }
+}
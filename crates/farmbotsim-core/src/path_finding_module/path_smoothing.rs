@@ -0,0 +1,132 @@
+use egui::{Pos2, Vec2};
+
+use crate::{
+    cfg::{ARC_SAMPLE_ANGLE, MIN_ARC_RADIUS},
+    environment::obstacle::Obstacle,
+    movement_module::pose::{Pose, path_to_poses},
+    units::{angle::Angle, angular_velocity::AngularVelocity, linear_velocity::LinearVelocity},
+    utilities::utils::segments_intersect,
+};
+
+/// Circular arc replacing a path corner, tangent to both adjacent segments.
+#[derive(Debug, Clone, Copy)]
+struct Fillet {
+    start: Pos2,
+    end: Pos2,
+    center: Pos2,
+    radius: f32,
+    /// Signed turning angle in radians, positive for left turns.
+    turn: f32,
+}
+
+impl Fillet {
+    /// Returns the points along the arc from its start to its end.
+    fn sample(&self) -> Vec<Pos2> {
+        let n_samples = (self.turn.abs() / ARC_SAMPLE_ANGLE.to_radians())
+            .ceil()
+            .max(1.0) as u32;
+        let start_angle = (self.start - self.center).angle();
+        (0..=n_samples)
+            .map(|i| {
+                let angle = start_angle + self.turn * i as f32 / n_samples as f32;
+                self.center + Vec2::angled(angle) * self.radius
+            })
+            .collect()
+    }
+
+    /// Returns whether the arc crosses an edge of any obstacle.
+    fn collides(&self, obstacles: &[Obstacle]) -> bool {
+        self.sample().windows(2).any(|chord| {
+            obstacles.iter().any(|obstacle| {
+                let points = &obstacle.points;
+                (0..points.len()).any(|i| {
+                    let (b1, b2) = (points[i], points[(i + 1) % points.len()]);
+                    segments_intersect(chord[0], chord[1], b1, b2)
+                })
+            })
+        })
+    }
+}
+
+/// Converts a piecewise-linear path into poses whose corners are driven as circular arcs.
+/// Each corner gets the largest arc fitting between its neighbours that doesn't cross
+/// the obstacles, corners without such an arc are kept and turned in place.
+/// Arcs are at least as wide as the agent can drive at `velocity` without exceeding
+/// `max_angular_velocity`. Every arc is given by its start pose and end pose,
+/// both oriented along the path.
+pub fn smooth_path(
+    path: &[Pos2],
+    obstacles: &[Obstacle],
+    velocity: LinearVelocity,
+    max_angular_velocity: AngularVelocity,
+) -> Vec<Pose> {
+    if path.len() < 3 {
+        return path_to_poses(path.to_vec());
+    }
+    // Infinite if the agent can't turn while driving, which leaves every corner to turn in place
+    let min_radius = MIN_ARC_RADIUS
+        .to_base_unit()
+        .max(velocity.to_base_unit() / max_angular_velocity.to_base_unit());
+    let last = path.len() - 1;
+    let direction = |i: usize| Angle::radians((path[i + 1] - path[i]).angle());
+
+    let mut poses = vec![Pose::new(path[0], direction(0))];
+    for i in 1..last {
+        match fillet(path, i, obstacles, min_radius) {
+            Some(fillet) => {
+                poses.push(Pose::new(fillet.start, direction(i - 1)));
+                poses.push(Pose::new(fillet.end, direction(i)));
+            }
+            None => poses.push(Pose::new(path[i], direction(i))),
+        }
+    }
+    poses.push(Pose::new(path[last], direction(last - 1)));
+
+    // Arcs may end exactly where the next one or the path starts or ends
+    poses.dedup_by(|b, a| a.position.distance(b.position) < 1e-4);
+    poses
+}
+
+/// Returns the largest collision-free arc for the corner at `path[i]` with a radius of at least
+/// `min_radius`. Arcs may use half of the adjacent segments, or all of the first and last segment.
+fn fillet(path: &[Pos2], i: usize, obstacles: &[Obstacle], min_radius: f32) -> Option<Fillet> {
+    let (incoming, outgoing) = (path[i] - path[i - 1], path[i + 1] - path[i]);
+    let (length_in, length_out) = (incoming.length(), outgoing.length());
+    if length_in <= f32::EPSILON || length_out <= f32::EPSILON {
+        return None;
+    }
+    let (incoming, outgoing) = (incoming / length_in, outgoing / length_out);
+    let turn = (incoming.x * outgoing.y - incoming.y * outgoing.x).atan2(incoming.dot(outgoing));
+    let half_tan = (turn.abs() / 2.0).tan();
+    if half_tan <= f32::EPSILON || !half_tan.is_finite() {
+        return None;
+    }
+
+    let allowed_in = if i == 1 { length_in } else { length_in / 2.0 };
+    let allowed_out = if i + 2 == path.len() {
+        length_out
+    } else {
+        length_out / 2.0
+    };
+    let mut radius = allowed_in.min(allowed_out) / half_tan;
+    // Smaller arcs stay closer to the corner, which is collision-free
+    while radius >= min_radius {
+        let tangent_length = radius * half_tan;
+        let start = path[i] - incoming * tangent_length;
+        // The center lies to the left of the path for left turns
+        let left = Vec2::new(-incoming.y, incoming.x);
+        let normal = if turn > 0.0 { left } else { -left };
+        let fillet = Fillet {
+            start,
+            end: path[i] + outgoing * tangent_length,
+            center: start + normal * radius,
+            radius,
+            turn,
+        };
+        if !fillet.collides(obstacles) {
+            return Some(fillet);
+        }
+        radius /= 2.0;
+    }
+    None
+}
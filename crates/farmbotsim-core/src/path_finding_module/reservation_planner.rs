@@ -0,0 +1,531 @@
+use egui::{Pos2, Vec2};
+use petgraph::graph::{NodeIndex, UnGraph};
+use serde::{Deserialize, Serialize};
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashSet},
+    f32::consts::TAU,
+};
+
+use crate::{
+    agent_module::agent::AgentId,
+    cfg::{RESERVATION_MAX_WAIT, RESERVATION_TIME_MARGIN, RESERVATION_WAIT_STEP},
+    environment::obstacle::{NoGoZone, Obstacle, no_go_penalty},
+    path_finding_module::{
+        path_finding::{FollowUpMotion, PathFinding, PathRequest},
+        visibility_graph::VisibilityGraph,
+    },
+    units::{duration::Duration, length::Length},
+};
+
+/// Upper bound on searched states per request, after which the unconstrained path is used.
+const MAX_EXPANSIONS: usize = 20_000;
+
+/// Space-time segment an agent occupies while moving from `from` to `to`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Reservation {
+    pub agent_id: AgentId,
+    pub from: Pos2,
+    pub to: Pos2,
+    /// Time the agent leaves `from`, measured from the start of the episode.
+    pub start: Duration,
+    /// Time the agent reaches `to`, `None` if it stays there until it gets a new path
+    /// or follow-up motions.
+    pub end: Option<Duration>,
+    pub footprint_radius: Length,
+}
+
+/// Prioritized multi-agent planner, every planned path reserves its space-time segments
+/// and later paths wait at graph nodes until reserved segments are free (cooperative A*).
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ReservationPlanner {
+    visibility_graph: VisibilityGraph,
+    pub reservations: Vec<Reservation>,
+    /// Number of paths planned without regard to other agents, because the goal stays occupied
+    /// or no free path was found within `MAX_EXPANSIONS` searched states and `RESERVATION_MAX_WAIT`.
+    pub n_fallbacks: u32,
+}
+
+impl PathFinding for ReservationPlanner {
+    fn find_path(&mut self, start: Pos2, end: Pos2) -> Option<Vec<Pos2>> {
+        self.visibility_graph.find_path(start, end)
+    }
+
+    fn find_timed_path(&mut self, request: &PathRequest) -> Option<Vec<(Pos2, Duration)>> {
+        let departure = request.departure.to_base_unit();
+        let margin = RESERVATION_TIME_MARGIN.to_base_unit();
+        self.reservations.retain(|r| {
+            r.agent_id != request.agent_id
+                && r.end
+                    .is_none_or(|end| end.to_base_unit() + margin >= departure)
+        });
+
+        // Agents already too close to the start can't be avoided by waiting
+        let radius = request.footprint_radius.to_base_unit();
+        let start_hold = Motion::hold(request.start.position, departure, departure);
+        let blocking: Vec<(Motion, f32)> = self
+            .reservations
+            .iter()
+            .flat_map(|r| {
+                let clearance = radius + r.footprint_radius.to_base_unit();
+                Motion::padded(r, margin)
+                    .into_iter()
+                    .map(move |motion| (motion, clearance))
+            })
+            .filter(|(motion, clearance)| {
+                start_hold
+                    .min_distance(motion)
+                    .is_none_or(|distance| distance >= *clearance)
+            })
+            .collect();
+
+        // Waiting doesn't help if the goal stays occupied
+        let goal_hold = Motion::hold(request.end, departure, f32::INFINITY);
+        let goal_blocked = blocking.iter().any(|(motion, clearance)| {
+            motion.end == f32::INFINITY
+                && goal_hold
+                    .min_distance(motion)
+                    .is_some_and(|distance| distance < *clearance)
+        });
+
+        let no_go_zones = self.visibility_graph.no_go_zones().to_vec();
+        let search = Search {
+            request,
+            departure,
+            blocking: &blocking,
+            no_go_zones: &no_go_zones,
+        };
+        let waypoints = (!goal_blocked)
+            .then(|| {
+                self.visibility_graph.with_endpoints(
+                    request.start.position,
+                    request.end,
+                    |graph, start, end| search.run(graph, start, end),
+                )
+            })
+            .flatten()
+            .or_else(|| {
+                let path = self
+                    .visibility_graph
+                    .find_path(request.start.position, request.end)?;
+                self.n_fallbacks += 1;
+                Some(search.unconstrained(&path))
+            })?;
+
+        self.reserve(request, &waypoints);
+        Some(
+            waypoints
+                .iter()
+                .map(|w| (w.position, Duration::seconds(w.wait)))
+                .collect(),
+        )
+    }
+
+    fn follow_up(&mut self, agent_id: AgentId, motions: &[FollowUpMotion]) {
+        let Some(index) = self
+            .reservations
+            .iter()
+            .position(|r| r.agent_id == agent_id && r.end.is_none())
+        else {
+            return;
+        };
+        let hold = self.reservations.remove(index);
+        let reservation = |from: Pos2, to: Pos2, start: f32, end: Option<f32>| Reservation {
+            agent_id,
+            from,
+            to,
+            start: Duration::seconds(start),
+            end: end.map(Duration::seconds),
+            footprint_radius: hold.footprint_radius,
+        };
+        // Turns are only covered by the time margin around the reservations
+        let (mut position, mut time) = (hold.to, hold.start.to_base_unit());
+        for motion in motions {
+            let distance = position.distance(motion.to);
+            let velocity = motion.velocity.to_base_unit();
+            if distance > 0.0 && velocity > 0.0 {
+                let arrival = time + distance / velocity;
+                self.reservations
+                    .push(reservation(position, motion.to, time, Some(arrival)));
+                (position, time) = (motion.to, arrival);
+            }
+            let hold = motion.hold.to_base_unit();
+            if hold > 0.0 {
+                self.reservations
+                    .push(reservation(position, position, time, Some(time + hold)));
+                time += hold;
+            }
+        }
+        self.reservations
+            .push(reservation(position, position, time, None));
+    }
+}
+
+impl ReservationPlanner {
+    /// Creates a new `ReservationPlanner` from given points and obstacles without reservations.
+    pub fn new(points: &[Pos2], obstacles: Vec<Obstacle>) -> Self {
+        Self {
+            visibility_graph: VisibilityGraph::new(points, obstacles),
+            reservations: vec![],
+            n_fallbacks: 0,
+        }
+    }
+
+    /// Recalculates the underlying visibility graph, keeping reservations.
+    pub fn recalculate(&mut self, points: &[Pos2], obstacles: &[Obstacle]) {
+        self.visibility_graph.recalculate(points, obstacles);
+    }
+
+    /// Sets the no-go zones, crossing them counts as the time needed for the penalty distance.
+    pub fn set_no_go_zones(&mut self, no_go_zones: Vec<NoGoZone>) {
+        self.visibility_graph.set_no_go_zones(no_go_zones);
+    }
+
+    /// Removes all reservations.
+    pub fn clear_reservations(&mut self) {
+        self.reservations.clear();
+    }
+
+    /// Replaces reservations of the requesting agent with the segments of the planned waypoints.
+    fn reserve(&mut self, request: &PathRequest, waypoints: &[Waypoint]) {
+        let reservation = |from: Pos2, to: Pos2, start: f32, end: Option<f32>| Reservation {
+            agent_id: request.agent_id,
+            from,
+            to,
+            start: Duration::seconds(start),
+            end: end.map(Duration::seconds),
+            footprint_radius: request.footprint_radius,
+        };
+        let mut reservations = vec![];
+        for pair in waypoints.windows(2) {
+            let (a, b) = (&pair[0], &pair[1]);
+            reservations.push(reservation(
+                a.position,
+                a.position,
+                a.arrival,
+                Some(b.leave),
+            ));
+            reservations.push(reservation(
+                a.position,
+                b.position,
+                b.leave,
+                Some(b.arrival),
+            ));
+        }
+        if let Some(last) = waypoints.last() {
+            reservations.push(reservation(
+                last.position,
+                last.position,
+                last.arrival,
+                None,
+            ));
+        }
+        self.reservations.extend(reservations);
+    }
+}
+
+/// Point of a planned path with times in seconds from the start of the episode.
+#[derive(Debug, Clone)]
+struct Waypoint {
+    position: Pos2,
+    /// Time the agent leaves the previous waypoint, after waiting and turning.
+    leave: f32,
+    arrival: f32,
+    /// Time spent waiting at this waypoint before turning towards the next one.
+    wait: f32,
+}
+
+/// Straight motion with constant velocity, times in seconds.
+#[derive(Debug, Clone)]
+struct Motion {
+    from: Pos2,
+    to: Pos2,
+    start: f32,
+    end: f32,
+}
+
+impl Motion {
+    /// Creates a motion that stays at `position` from `start` until `end`.
+    fn hold(position: Pos2, start: f32, end: f32) -> Self {
+        Self {
+            from: position,
+            to: position,
+            start,
+            end,
+        }
+    }
+
+    /// Splits a reservation into motions, holding at both ends for `margin` seconds.
+    fn padded(reservation: &Reservation, margin: f32) -> Vec<Self> {
+        let start = reservation.start.to_base_unit();
+        match reservation.end {
+            Some(end) => {
+                let end = end.to_base_unit();
+                vec![
+                    Self::hold(reservation.from, start - margin, start),
+                    Self {
+                        from: reservation.from,
+                        to: reservation.to,
+                        start,
+                        end,
+                    },
+                    Self::hold(reservation.to, end, end + margin),
+                ]
+            }
+            None => vec![Self::hold(reservation.to, start - margin, f32::INFINITY)],
+        }
+    }
+
+    fn velocity(&self) -> Vec2 {
+        if self.end - self.start > f32::EPSILON {
+            (self.to - self.from) / (self.end - self.start)
+        } else {
+            Vec2::ZERO
+        }
+    }
+
+    fn position_at(&self, time: f32) -> Pos2 {
+        self.from + self.velocity() * (time - self.start)
+    }
+
+    /// Returns the smallest distance between both motions while they overlap in time, if they do.
+    fn min_distance(&self, other: &Motion) -> Option<f32> {
+        let t0 = self.start.max(other.start);
+        let t1 = self.end.min(other.end);
+        if t0 > t1 {
+            return None;
+        }
+        let offset = self.position_at(t0) - other.position_at(t0);
+        let relative_velocity = self.velocity() - other.velocity();
+        let t = if relative_velocity.length_sq() > 0.0 {
+            (-offset.dot(relative_velocity) / relative_velocity.length_sq()).clamp(0.0, t1 - t0)
+        } else {
+            0.0
+        };
+        Some((offset + relative_velocity * t).length())
+    }
+}
+
+/// State of the space-time search, times in seconds.
+#[derive(Debug, Clone)]
+struct State {
+    node: NodeIndex,
+    time: f32,
+    /// Time the agent left the previous node, equals `time` for waits.
+    leave: f32,
+    heading: Vec2,
+    waited: f32,
+    /// Time equivalent of the no-go zone penalties along the way, in seconds.
+    penalty: f32,
+    parent: Option<usize>,
+}
+
+/// Entry of the open list, ordered so that the lowest estimated cost is popped first.
+#[derive(Debug)]
+struct OpenEntry {
+    estimate: f32,
+    index: usize,
+}
+
+impl PartialEq for OpenEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+impl Eq for OpenEntry {}
+impl PartialOrd for OpenEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for OpenEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .estimate
+            .total_cmp(&self.estimate)
+            .then_with(|| other.index.cmp(&self.index))
+    }
+}
+
+/// A* over graph nodes and time for a single request against reserved motions.
+struct Search<'a> {
+    request: &'a PathRequest,
+    departure: f32,
+    blocking: &'a [(Motion, f32)],
+    no_go_zones: &'a [NoGoZone],
+}
+
+impl Search<'_> {
+    /// Returns the earliest arriving waypoints that keep clear of all blocking motions.
+    fn run(
+        &self,
+        graph: &UnGraph<Pos2, ()>,
+        start: NodeIndex,
+        end: NodeIndex,
+    ) -> Option<Vec<Waypoint>> {
+        let velocity = self.request.velocity.to_base_unit();
+        if velocity <= 0.0 {
+            return None;
+        }
+        let wait_step = RESERVATION_WAIT_STEP.to_base_unit();
+        let max_wait = RESERVATION_MAX_WAIT.to_base_unit();
+        let goal = graph[end];
+
+        let mut states = vec![State {
+            node: start,
+            time: self.departure,
+            leave: self.departure,
+            heading: self.request.start.orientation.to_vec2(),
+            waited: 0.0,
+            penalty: 0.0,
+            parent: None,
+        }];
+        let mut open = BinaryHeap::from([OpenEntry {
+            estimate: self.departure + graph[start].distance(goal) / velocity,
+            index: 0,
+        }]);
+        let mut closed = HashSet::new();
+
+        while let Some(OpenEntry { index, .. }) = open.pop() {
+            let state = states[index].clone();
+            if state.node == end {
+                return Some(Self::waypoints(graph, &states, index));
+            }
+            let time_step = ((state.time - self.departure) / wait_step).round() as i64;
+            if !closed.insert((state.node, time_step)) {
+                continue;
+            }
+            if closed.len() > MAX_EXPANSIONS {
+                return None;
+            }
+            let position = graph[state.node];
+
+            let mut successors = vec![];
+            if state.waited + wait_step <= max_wait {
+                let time = state.time + wait_step;
+                if self.is_free(&Motion::hold(position, state.time, time)) {
+                    successors.push(State {
+                        time,
+                        leave: time,
+                        waited: state.waited + wait_step,
+                        parent: Some(index),
+                        ..state.clone()
+                    });
+                }
+            }
+            for neighbor in graph.neighbors(state.node) {
+                let target = graph[neighbor];
+                let direction = (target - position).normalized();
+                let leave = state.time + self.turn_duration(state.heading, direction);
+                let time = leave + position.distance(target) / velocity;
+                let motion = Motion {
+                    from: position,
+                    to: target,
+                    start: leave,
+                    end: time,
+                };
+                if self.is_free(&Motion::hold(position, state.time, leave)) && self.is_free(&motion)
+                {
+                    successors.push(State {
+                        node: neighbor,
+                        time,
+                        leave,
+                        heading: direction,
+                        waited: state.waited,
+                        penalty: state.penalty
+                            + no_go_penalty(self.no_go_zones, position, target) / velocity,
+                        parent: Some(index),
+                    });
+                }
+            }
+
+            for successor in successors {
+                let estimate = successor.time
+                    + successor.penalty
+                    + graph[successor.node].distance(goal) / velocity;
+                open.push(OpenEntry {
+                    estimate,
+                    index: states.len(),
+                });
+                states.push(successor);
+            }
+        }
+        None
+    }
+
+    /// Returns waypoints along the path without any waiting.
+    fn unconstrained(&self, path: &[Pos2]) -> Vec<Waypoint> {
+        let velocity = self.request.velocity.to_base_unit().max(f32::EPSILON);
+        let mut heading = self.request.start.orientation.to_vec2();
+        let mut waypoints: Vec<Waypoint> = vec![];
+        for &position in path {
+            let waypoint = match waypoints.last() {
+                Some(previous) => {
+                    let direction = (position - previous.position).normalized();
+                    let leave = previous.arrival + self.turn_duration(heading, direction);
+                    heading = direction;
+                    Waypoint {
+                        position,
+                        leave,
+                        arrival: leave + previous.position.distance(position) / velocity,
+                        wait: 0.0,
+                    }
+                }
+                None => Waypoint {
+                    position,
+                    leave: self.departure,
+                    arrival: self.departure,
+                    wait: 0.0,
+                },
+            };
+            waypoints.push(waypoint);
+        }
+        waypoints
+    }
+
+    /// Returns whether the motion keeps clear of all blocking motions.
+    fn is_free(&self, motion: &Motion) -> bool {
+        self.blocking.iter().all(|(other, clearance)| {
+            motion
+                .min_distance(other)
+                .is_none_or(|distance| distance >= *clearance)
+        })
+    }
+
+    /// Returns the time in seconds needed to turn from `heading` to `direction`.
+    fn turn_duration(&self, heading: Vec2, direction: Vec2) -> f32 {
+        let angular_velocity = self.request.angular_velocity.to_base_unit().abs();
+        if angular_velocity <= 0.0 || direction == Vec2::ZERO {
+            return 0.0;
+        }
+        let angle = (direction.angle() - heading.angle()).rem_euclid(TAU);
+        angle.min(TAU - angle) / angular_velocity
+    }
+
+    /// Collects the waypoints leading to the state at `index`, merging waits into the waypoint.
+    fn waypoints(graph: &UnGraph<Pos2, ()>, states: &[State], index: usize) -> Vec<Waypoint> {
+        let mut chain = vec![];
+        let mut current = Some(index);
+        while let Some(i) = current {
+            chain.push(&states[i]);
+            current = states[i].parent;
+        }
+        chain.reverse();
+
+        let mut waypoints: Vec<Waypoint> = vec![];
+        for state in chain {
+            match waypoints.last_mut() {
+                Some(last) if graph[state.node] == last.position && state.leave == state.time => {
+                    last.wait = state.time - last.arrival;
+                }
+                _ => waypoints.push(Waypoint {
+                    position: graph[state.node],
+                    leave: state.leave,
+                    arrival: state.time,
+                    wait: 0.0,
+                }),
+            }
+        }
+        waypoints
+    }
+}
@@ -4,18 +4,79 @@ use petgraph::{
     graph::{NodeIndex, UnGraph},
     visit::EdgeRef,
 };
+use serde::{Deserialize, Serialize};
 
-use crate::{environment::obstacle::Obstacle, path_finding_module::path_finding::PathFinding};
+use crate::{
+    environment::obstacle::{NoGoZone, Obstacle, no_go_penalty},
+    path_finding_module::path_finding::PathFinding,
+    utilities::utils::point_in_polygon,
+};
 
 /// A graph-based pathfinding structure using a visibility graph approach.
-#[derive(Clone, Debug)]
+/// Nothing is serialized, the graph is rebuilt with `recalculate`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct VisibilityGraph {
+    #[serde(skip)]
     pub graph: UnGraph<Pos2, ()>,
+    #[serde(skip)]
     obstacles: Vec<Obstacle>,
+    #[serde(skip)]
+    no_go_zones: Vec<NoGoZone>,
 }
 
 impl PathFinding for VisibilityGraph {
     fn find_path(&mut self, start: Pos2, end: Pos2) -> Option<Vec<Pos2>> {
+        let no_go_zones = self.no_go_zones.clone();
+        self.with_endpoints(start, end, |graph, start_node, end_node| {
+            // Run A* algorithm
+            astar(
+                graph,
+                start_node,
+                |n| n == end_node,
+                |e| {
+                    // Safely handle Option from edge_endpoints
+                    graph
+                        .edge_endpoints(e.id())
+                        .map_or(f32::INFINITY, |(a, b)| {
+                            graph[a].distance(graph[b])
+                                + no_go_penalty(&no_go_zones, graph[a], graph[b])
+                        })
+                },
+                |n| graph[n].distance(end),
+            )
+            .map(|(_, path)| path.into_iter().map(|n| graph[n]).collect())
+        })
+    }
+}
+
+impl VisibilityGraph {
+    /// Creates a new `VisibilityGraph` from given points and obstacles.
+    pub fn new(points: &[Pos2], obstacles: Vec<Obstacle>) -> Self {
+        Self {
+            graph: Self::build_graph(points, &obstacles),
+            obstacles,
+            no_go_zones: vec![],
+        }
+    }
+
+    /// Sets the no-go zones whose penalty is added to the length of edges crossing them.
+    pub fn set_no_go_zones(&mut self, no_go_zones: Vec<NoGoZone>) {
+        self.no_go_zones = no_go_zones;
+    }
+
+    /// Returns the no-go zones considered when finding paths.
+    pub fn no_go_zones(&self) -> &[NoGoZone] {
+        &self.no_go_zones
+    }
+
+    /// Adds `start` and `end` as nodes if not present, runs `f` with their indices
+    /// and removes the added nodes again.
+    pub fn with_endpoints<R>(
+        &mut self,
+        start: Pos2,
+        end: Pos2,
+        f: impl FnOnce(&UnGraph<Pos2, ()>, NodeIndex, NodeIndex) -> R,
+    ) -> R {
         let mut added_nodes = Vec::new();
 
         let start_node = match self.find_existing_node(start) {
@@ -36,22 +97,7 @@ impl PathFinding for VisibilityGraph {
             }
         };
 
-        // Run A* algorithm
-        let result = astar(
-            &self.graph,
-            start_node,
-            |n| n == end_node,
-            |e| {
-                // Safely handle Option from edge_endpoints
-                self.graph
-                    .edge_endpoints(e.id())
-                    .map_or(f32::INFINITY, |(a, b)| {
-                        self.graph[a].distance(self.graph[b])
-                    })
-            },
-            |n| self.graph[n].distance(end),
-        )
-        .map(|(_, path)| path.into_iter().map(|n| self.graph[n]).collect());
+        let result = f(&self.graph, start_node, end_node);
 
         // Cleanup any temporary nodes added
         for node in added_nodes.iter().rev() {
@@ -59,16 +105,6 @@ impl PathFinding for VisibilityGraph {
         }
         result
     }
-}
-
-impl VisibilityGraph {
-    /// Creates a new `VisibilityGraph` from given points and obstacles.
-    pub fn new(points: &[Pos2], obstacles: Vec<Obstacle>) -> Self {
-        Self {
-            graph: Self::build_graph(points, &obstacles),
-            obstacles,
-        }
-    }
 
     /// Recalculates the graph with new points and obstacles, rebuilding the visibility edges.
     pub fn recalculate(&mut self, points: &[Pos2], obstacles: &[Obstacle]) {
@@ -151,8 +187,14 @@ impl VisibilityGraph {
     }
 
     /// Adds a new node with position `pos` and connects it to all visible existing nodes.
+    /// Obstacles containing `pos` are ignored so that it can leave them.
     fn add_node_with_connections(&mut self, pos: Pos2) -> NodeIndex {
         let new_node = self.graph.add_node(pos);
+        let obstacles: Vec<&Obstacle> = self
+            .obstacles
+            .iter()
+            .filter(|obstacle| !point_in_polygon(pos, &obstacle.points))
+            .collect();
 
         // Collect node indices and sort to ensure deterministic connection order
         let mut existing_nodes: Vec<_> = self.graph.node_indices().collect();
@@ -162,8 +204,7 @@ impl VisibilityGraph {
             if existing_node != new_node {
                 let existing_pos = self.graph[existing_node];
                 let edge_line = (pos, existing_pos);
-                let intersects_obstacle = self
-                    .obstacles
+                let intersects_obstacle = obstacles
                     .iter()
                     .any(|obstacle| Self::lines_intersect(edge_line, obstacle));
                 if !intersects_obstacle {
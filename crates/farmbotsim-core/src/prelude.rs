@@ -2,13 +2,15 @@
 // Configuration
 // ===========================
 pub use crate::cfg::{
-    AGENT_CONFIGS_PATH, BATTERIES_PATH, DEFAULT_AGENT_CONFIG_PATH, DEFAULT_FIELD_CONFIG_PATH,
-    DEFAULT_LINE_FARM_ENTITY_PLAN_PATH, DEFAULT_POINT_FARM_ENTITY_PLAN_PATH,
-    DEFAULT_ROMBA_MOVEMENT_CONFIG_PATH, DEFAULT_SCENE_CONFIG_PATH,
-    DEFAULT_TASK_MANAGER_CONFIG_PATH, FARM_ENTITY_PLANS_PATH, FIELD_CONFIGS_PATH,
-    MAX_VELOCITY_BETWEEN_POINTS, MOVEMENT_CONFIGS_PATH, PERFORMANCE_MATRIX_PATH,
-    POWER_CONSUMPTION_TRAVEL, POWER_CONSUMPTION_WAIT, RNG_SEED, SCENE_CONFIGS_PATH,
-    TASK_MANAGER_CONFIGS_PATH, TOLERANCE_ANGLE, TOLERANCE_DISTANCE,
+    AGENT_CONFIGS_PATH, ARC_SAMPLE_ANGLE, BATTERIES_PATH, DEFAULT_AGENT_CONFIG_PATH,
+    DEFAULT_FIELD_CONFIG_PATH, DEFAULT_GRID_CELL_SIZE, DEFAULT_LINE_FARM_ENTITY_PLAN_PATH,
+    DEFAULT_POINT_FARM_ENTITY_PLAN_PATH, DEFAULT_ROMBA_MOVEMENT_CONFIG_PATH,
+    DEFAULT_SCENE_CONFIG_PATH, DEFAULT_TASK_MANAGER_CONFIG_PATH, EVENT_LOGS_PATH,
+    FARM_ENTITY_PLANS_PATH, FIELD_CONFIGS_PATH, GRAPH_POINT_CLEARANCE, GRAVITY, IRRADIANCE_PATH,
+    MAX_VELOCITY_BETWEEN_POINTS, MIN_ARC_RADIUS, MOVEMENT_CONFIGS_PATH, NEAR_MISS_DISTANCE,
+    PERFORMANCE_MATRIX_PATH, RESERVATION_MAX_WAIT, RESERVATION_TIME_MARGIN, RESERVATION_WAIT_STEP,
+    RNG_SEED, SCENE_CONFIGS_PATH, SNAPSHOTS_PATH, TASK_MANAGER_CONFIGS_PATH, TOLERANCE_ANGLE,
+    TOLERANCE_ARC_HEADING, TOLERANCE_DISTANCE, YIELD_DISTANCE,
 };
 
 // ===========================
@@ -18,6 +20,8 @@ pub use crate::agent_module::{
     agent::{Agent, AgentId},
     agent_config::AgentConfig,
     agent_state::AgentState,
+    collision::{CollisionAvoidance, Contact, ContactKind},
+    power_model::PowerModel,
     work_schedule::WorkSchedule,
 };
 
@@ -25,7 +29,12 @@ pub use crate::agent_module::{
 // Battery Module
 // ===========================
 pub use crate::battery_module::{
-    battery::Battery, battery_config::BatteryConfig, battery_error::BatteryError,
+    battery::Battery,
+    battery_ageing::{AgeingModel, BatteryHealth},
+    battery_config::BatteryConfig,
+    battery_error::BatteryError,
+    battery_model::{BatteryModel, CURVE_DATE_FORMAT, CcCvModel, CurveMorphModel, DatedCurve},
+    charging_curve::{ChargingCurve, DAYS_IN_YEAR},
     is_battery::IsBattery,
 };
 
@@ -33,13 +42,23 @@ pub use crate::battery_module::{
 // Environment Module
 // ===========================
 pub use crate::environment::{
-    datetime::{DATE_FORMAT, DATETIME_FORMAT, DateTimeConfig, DateTimeManager, TIME_FORMAT},
+    datetime::{
+        DATE_FORMAT, DATETIME_FORMAT, DateTimeConfig, DateTimeManager, TIME_FORMAT, leap_year_day,
+    },
     field_config::{FieldConfig, LineFieldConfig, PointFieldConfig, VariantFieldConfig},
-    obstacle::Obstacle,
+    obstacle::{NoGoZone, Obstacle},
     scene_config::SceneConfig,
 };
 
-pub use crate::environment::env_module::{env::Env, env_config::EnvConfig};
+pub use crate::environment::env_module::{
+    env::Env,
+    env_config::{EnvConfig, FleetGroup},
+    env_snapshot::{ENV_SNAPSHOT_VERSION, EnvSnapshot},
+    snapshot_error::SnapshotError,
+    validation::{
+        ValidationIssue, validate, validate_capabilities, validate_field, validate_scene,
+    },
+};
 
 pub use crate::environment::farm_entity_module::{
     crop::Crop, farm_entity::FarmEntity, farm_entity_action::FarmEntityAction,
@@ -51,13 +70,32 @@ pub use crate::environment::spawn_area_module::{
     spawn_area::SpawnArea, spawn_area_config::SpawnAreaConfig,
 };
 
-pub use crate::environment::station_module::{station::Station, station_config::StationConfig};
+pub use crate::environment::station_module::{
+    power_budget::{PowerBudget, PowerRequest, PowerSharePolicy, PowerSource},
+    solar::SolarModel,
+    station::Station,
+    station_config::StationConfig,
+};
+
+// ===========================
+// Error
+// ===========================
+pub use crate::error::FarmbotSimError;
+
+// ===========================
+// Event Module
+// ===========================
+pub use crate::event_module::{
+    event::{Event, EventKind},
+    event_log::EventLog,
+};
 
 // ===========================
 // Movement Module
 // ===========================
 pub use crate::movement_module::{
-    is_movement::IsMovement, movement::Movement, pose::Pose, pose::path_to_poses,
+    ackermann_movement::AckermannMovement, is_movement::IsMovement, movement::Movement,
+    movement::MovementState, odometry_noise::OdometryNoise, pose::Pose, pose::path_to_poses,
     romba_movement::RombaMovement,
 };
 
@@ -65,7 +103,20 @@ pub use crate::movement_module::{
 // Pathfinding Module
 // ===========================
 pub use crate::path_finding_module::{
-    path_finding::PathFinding, visibility_graph::VisibilityGraph,
+    grid_planner::{Cell, GridPlanner},
+    path_cache::PathCache,
+    path_finding::{PathFinding, PathRequest},
+    path_planner::{PathPlanner, PathPlannerKind},
+    path_smoothing::smooth_path,
+    reservation_planner::{Reservation, ReservationPlanner},
+    visibility_graph::VisibilityGraph,
+};
+
+// ===========================
+// Simulation Module
+// ===========================
+pub use crate::simulation_module::{
+    simulation::Simulation, termination_condition::TerminationCondition,
 };
 
 // ===========================
@@ -80,8 +131,9 @@ pub use crate::task_module::{
 // Units
 // ===========================
 pub use crate::units::{
-    angle::Angle, angular_velocity::AngularVelocity, duration::Duration, energy::Energy,
-    length::Length, linear_velocity::LinearVelocity, power::Power, voltage::Voltage,
+    angle::Angle, angular_velocity::AngularVelocity, current::Current, duration::Duration,
+    energy::Energy, length::Length, linear_velocity::LinearVelocity, mass::Mass, power::Power,
+    voltage::Voltage,
 };
 
 // ===========================
@@ -89,7 +141,10 @@ pub use crate::units::{
 // ===========================
 pub use crate::utilities::{
     pos2::ExtendedPos2,
-    utils::{generate_colors, load_json_or_panic},
+    utils::{
+        generate_colors, load_json, load_json_or_panic, point_in_polygon, polygons_overlap,
+        segments_intersect,
+    },
     vec2::Vec2Rotate,
 };
 
@@ -0,0 +1,4 @@
+//! Headless simulation runner and its termination conditions.
+
+pub mod simulation;
+pub mod termination_condition;
@@ -0,0 +1,100 @@
+use crate::{
+    environment::env_module::{env::Env, env_config::EnvConfig},
+    error::FarmbotSimError,
+    simulation_module::termination_condition::TerminationCondition,
+    statistics::EnvEpisodeStats,
+};
+
+/// Headless simulation runner that owns an [`Env`] and runs it until a [`TerminationCondition`] is met.
+#[derive(Debug, Clone)]
+pub struct Simulation {
+    /// Environment being simulated.
+    pub env: Env,
+    /// Condition that ends an episode.
+    pub termination_condition: TerminationCondition,
+    /// Seed of the first episode, the seed of the env when the simulation was created.
+    pub first_seed: u64,
+    /// Number of episodes started so far.
+    pub n_started_episodes: u32,
+}
+
+impl Simulation {
+    /// Creates a new `Simulation` from an existing env.
+    pub fn new(env: Env, termination_condition: TerminationCondition) -> Self {
+        Self {
+            first_seed: env.seed,
+            env,
+            termination_condition,
+            n_started_episodes: 0,
+        }
+    }
+
+    /// Creates a new `Simulation` by building an env from `EnvConfig`.
+    /// Panics if any JSON file can't be parsed or is not present.
+    pub fn from_config(config: EnvConfig, termination_condition: TerminationCondition) -> Self {
+        Self::new(Env::from_config(config), termination_condition)
+    }
+
+    /// Creates a new `Simulation` by building an env from `EnvConfig`.
+    /// Returns an error if any JSON file can't be parsed or is not present.
+    pub fn try_from_config(
+        config: EnvConfig,
+        termination_condition: TerminationCondition,
+    ) -> Result<Self, FarmbotSimError> {
+        Ok(Self::new(
+            Env::try_from_config(config)?,
+            termination_condition,
+        ))
+    }
+
+    /// Performs one simulation tick: assigns tasks and advances the env by one step,
+    /// or up to the next event if the env is in fast-forward mode.
+    pub fn step(&mut self) {
+        self.env.task_manager.assign_tasks(
+            &mut self.env.agents,
+            &mut self.env.stations,
+            self.env.duration,
+        );
+        if self.env.fast_forward {
+            let max_steps = self.termination_condition.max_skippable_steps(&self.env);
+            self.env.step_to_next_event(max_steps);
+        } else {
+            self.env.step();
+        }
+    }
+
+    /// Returns true if the termination condition is met for the current env state.
+    pub fn is_finished(&self) -> bool {
+        self.termination_condition.is_met(&self.env)
+    }
+
+    /// Steps the env from its current state until the termination condition is met
+    /// and returns the episode statistics.
+    pub fn run_to_termination(&mut self) -> EnvEpisodeStats {
+        while !self.is_finished() {
+            self.step();
+        }
+        self.env.get_env_episode_stats()
+    }
+
+    /// Resets the env for the next episode.
+    /// The first episode uses the seed the env was created with, each later one the next seed.
+    pub fn start_episode(&mut self) {
+        let seed = self
+            .first_seed
+            .wrapping_add(u64::from(self.n_started_episodes));
+        self.env.reset_with_seed(seed);
+        self.n_started_episodes += 1;
+    }
+
+    /// Resets the env and runs a whole episode.
+    pub fn run_episode(&mut self) -> EnvEpisodeStats {
+        self.start_episode();
+        self.run_to_termination()
+    }
+
+    /// Runs `n_episodes` episodes and returns statistics of each.
+    pub fn run_episodes(&mut self, n_episodes: u32) -> Vec<EnvEpisodeStats> {
+        (0..n_episodes).map(|_| self.run_episode()).collect()
+    }
+}
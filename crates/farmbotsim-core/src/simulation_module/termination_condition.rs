@@ -0,0 +1,95 @@
+use std::sync::Arc;
+
+use crate::{
+    agent_module::agent_state::AgentState, environment::env_module::env::Env,
+    units::duration::Duration,
+};
+
+/// Defines conditions under which a simulation episode terminates.
+#[derive(Clone)]
+pub enum TerminationCondition {
+    /// Terminates when every work action of every farm entity plan is completed.
+    /// Never met if any farm entity plan has a cycle.
+    AllTasksCompleted,
+    /// Terminates after a specified number of tasks are completed.
+    NumberCompletedTasks(u32),
+    /// Terminates after a specified duration in simulation time.
+    EnvDuration(Duration),
+    /// Terminates when every agent is discharged.
+    AllAgentsDischarged,
+    /// Terminates when any of the contained conditions is met.
+    Any(Vec<TerminationCondition>),
+    /// Terminates when all of the contained conditions are met.
+    All(Vec<TerminationCondition>),
+    /// Terminates when the closure returns `true` for the current env.
+    Custom(Arc<dyn Fn(&Env) -> bool + Send + Sync>),
+}
+
+impl TerminationCondition {
+    /// Creates a custom condition from a closure.
+    pub fn custom<F>(f: F) -> Self
+    where
+        F: Fn(&Env) -> bool + Send + Sync + 'static,
+    {
+        Self::Custom(Arc::new(f))
+    }
+
+    /// Returns true if the condition is met for the given env.
+    pub fn is_met(&self, env: &Env) -> bool {
+        match self {
+            Self::AllTasksCompleted => {
+                env.task_manager
+                    .number_of_work_actions()
+                    .is_some_and(|n_actions| {
+                        env.task_manager.completed_tasks.len() as u32 >= n_actions
+                    })
+            }
+            Self::NumberCompletedTasks(n_tasks) => {
+                env.task_manager.completed_tasks.len() as u32 >= *n_tasks
+            }
+            Self::EnvDuration(duration) => env.duration >= *duration,
+            Self::AllAgentsDischarged => env
+                .agents
+                .iter()
+                .all(|agent| agent.state == AgentState::Discharged),
+            Self::Any(conditions) => conditions.iter().any(|c| c.is_met(env)),
+            Self::All(conditions) => conditions.iter().all(|c| c.is_met(env)),
+            Self::Custom(f) => f(env),
+        }
+    }
+
+    /// Returns how many steps can be skipped at once without stepping past the point where the
+    /// condition is met. Conditions that depend on task or agent state only change on events,
+    /// which are never skipped.
+    pub fn max_skippable_steps(&self, env: &Env) -> u32 {
+        match self {
+            Self::AllTasksCompleted | Self::NumberCompletedTasks(_) | Self::AllAgentsDischarged => {
+                u32::MAX
+            }
+            Self::EnvDuration(duration) => {
+                let remaining_steps = (*duration - env.duration) / env.simulation_step;
+                (remaining_steps.ceil().max(0.0) as u32).saturating_sub(1)
+            }
+            Self::Any(conditions) | Self::All(conditions) => conditions
+                .iter()
+                .map(|c| c.max_skippable_steps(env))
+                .min()
+                .unwrap_or(u32::MAX),
+            Self::Custom(_) => 1,
+        }
+    }
+}
+
+impl std::fmt::Debug for TerminationCondition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::AllTasksCompleted => write!(f, "AllTasksCompleted"),
+            Self::NumberCompletedTasks(n) => write!(f, "NumberCompletedTasks({n})"),
+            Self::EnvDuration(d) => write!(f, "EnvDuration({d})"),
+            Self::AllAgentsDischarged => write!(f, "AllAgentsDischarged"),
+            Self::Any(conditions) => f.debug_tuple("Any").field(conditions).finish(),
+            Self::All(conditions) => f.debug_tuple("All").field(conditions).finish(),
+            Self::Custom(_) => write!(f, "Custom(..)"),
+        }
+    }
+}
@@ -8,7 +8,7 @@ use crate::{
     movement_module::pose::Pose,
     task_module::{
         strategies::{ChargingStrategy, ChooseStationStrategy},
-        task::Task,
+        task::{Intent, Task},
     },
     units::{duration::Duration, energy::Energy, length::Length},
 };
@@ -16,19 +16,22 @@ use crate::{
 // ---------- Single timestep ----------
 
 /// Represents the state of an agent at a single timestep in the environment.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AgentTimestep {
     pub duration: Duration,
     pub state: AgentState,
     pub pose: Pose,
+    pub localization_error: Length, // distance between true and estimated position
     pub battery_energy: Energy,
     pub task: Option<Task>,
+    #[serde(default)]
+    pub yielding: bool,
 }
 
 // ---------- Single Episode ----------
 
 /// Contains aggregated statistics for a single agent over one episode.
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
 pub struct AgentEpisodeStats {
     pub work_time: Duration,
     pub travel_time: Duration,
@@ -40,9 +43,33 @@ pub struct AgentEpisodeStats {
     pub energy_charged: Energy,
     pub energy_discharged: Energy,
     pub distance_travelled: Length,
+
+    #[serde(default)]
+    pub yield_time: Duration,
+    #[serde(default)]
+    pub n_collisions: f32, // a count, fractional when averaged over episodes
+    #[serde(default)]
+    pub n_near_misses: f32,
+    #[serde(default)]
+    pub conflict_wait_time: Duration,
+    #[serde(default)]
+    pub mean_localization_error: Length, // weighted by time
+    #[serde(default)]
+    pub max_localization_error: Length,
+    #[serde(default = "full_health")]
+    pub state_of_health: f32, // in percent at the end of the episode
+    #[serde(default)]
+    pub equivalent_full_cycles: f32, // battery throughput since new, at the end of the episode
+}
+
+/// Returns the state of health of a new battery.
+fn full_health() -> f32 {
+    100.0
 }
+
 impl AgentEpisodeStats {
     /// Creates an `AgentEpisodeStats` from a slice of `AgentTimestep`s.
+    /// Contact counts and battery health are not part of timesteps and start as for a new agent.
     pub fn from_timesteps(timesteps: &[AgentTimestep]) -> Self {
         use crate::agent_module::agent_state::AgentState::*;
 
@@ -52,10 +79,14 @@ impl AgentEpisodeStats {
         let mut charging_time = Duration::ZERO;
         let mut queue_time = Duration::ZERO;
         let mut discharged_time = Duration::ZERO;
+        let mut yield_time = Duration::ZERO;
+        let mut conflict_wait_time = Duration::ZERO;
 
         let mut energy_charged = Energy::ZERO;
         let mut energy_discharged = Energy::ZERO;
         let mut distance_travelled = Length::ZERO;
+        let mut localization_error_integral = 0.0; // in m*s
+        let mut max_localization_error = Length::ZERO;
 
         let mut prev_pose: Option<Pose> = None;
         let mut prev_battery: Option<Energy> = None;
@@ -67,12 +98,14 @@ impl AgentEpisodeStats {
                 Travel => travel_time = travel_time + step.duration,
                 Charging => charging_time = charging_time + step.duration,
                 Wait => {
-                    // Check if waiting in a queue
+                    // Check if waiting in a queue or for another agent
                     if let Some(task) = &step.task {
-                        if *task.get_intent() == crate::task_module::task::Intent::Queue {
-                            queue_time = queue_time + step.duration;
-                        } else {
-                            idle_time = idle_time + step.duration;
+                        match task.get_intent() {
+                            Intent::Queue => queue_time = queue_time + step.duration,
+                            Intent::Yield => {
+                                conflict_wait_time = conflict_wait_time + step.duration
+                            }
+                            _ => idle_time = idle_time + step.duration,
                         }
                     } else {
                         idle_time = idle_time + step.duration;
@@ -80,6 +113,13 @@ impl AgentEpisodeStats {
                 }
                 Discharged => discharged_time = discharged_time + step.duration,
             }
+            if step.yielding {
+                yield_time = yield_time + step.duration;
+            }
+
+            localization_error_integral +=
+                step.localization_error.to_base_unit() * step.duration.to_base_unit();
+            max_localization_error = max_localization_error.max(step.localization_error);
 
             // Compute energy delta
             if let Some(prev) = prev_battery {
@@ -100,6 +140,13 @@ impl AgentEpisodeStats {
             prev_pose = Some(step.pose.clone());
         }
 
+        let total_time: f32 = timesteps.iter().map(|s| s.duration.to_base_unit()).sum();
+        let mean_localization_error = if total_time > 0.0 {
+            Length::meters(localization_error_integral / total_time)
+        } else {
+            Length::ZERO
+        };
+
         Self {
             work_time,
             travel_time,
@@ -110,22 +157,102 @@ impl AgentEpisodeStats {
             energy_charged,
             energy_discharged,
             distance_travelled,
+            yield_time,
+            n_collisions: 0.0,
+            n_near_misses: 0.0,
+            conflict_wait_time,
+            mean_localization_error,
+            max_localization_error,
+            state_of_health: full_health(),
+            equivalent_full_cycles: 0.0,
         }
     }
+
+    /// Returns the time covered by the stats, i.e. the sum of the time spent in each state.
+    pub fn duration(&self) -> Duration {
+        self.work_time
+            + self.travel_time
+            + self.idle_time
+            + self.charging_time
+            + self.queue_time
+            + self.conflict_wait_time
+            + self.discharged_time
+    }
+
+    /// Adds up the stats, e.g. of all agents of a group.
+    /// The mean localization error is averaged weighted by time, the maximum is the largest one.
+    /// The state of health is the lowest one.
+    pub fn total<'a>(stats: impl IntoIterator<Item = &'a AgentEpisodeStats>) -> Self {
+        let empty = AgentEpisodeStats {
+            state_of_health: full_health(),
+            ..Default::default()
+        };
+        stats.into_iter().fold(empty, |total, s| AgentEpisodeStats {
+            mean_localization_error: weighted_mean_error(&total, s),
+            max_localization_error: total.max_localization_error.max(s.max_localization_error),
+            work_time: total.work_time + s.work_time,
+            travel_time: total.travel_time + s.travel_time,
+            idle_time: total.idle_time + s.idle_time,
+            charging_time: total.charging_time + s.charging_time,
+            queue_time: total.queue_time + s.queue_time,
+            discharged_time: total.discharged_time + s.discharged_time,
+            energy_charged: total.energy_charged + s.energy_charged,
+            energy_discharged: total.energy_discharged + s.energy_discharged,
+            distance_travelled: total.distance_travelled + s.distance_travelled,
+            yield_time: total.yield_time + s.yield_time,
+            n_collisions: total.n_collisions + s.n_collisions,
+            n_near_misses: total.n_near_misses + s.n_near_misses,
+            conflict_wait_time: total.conflict_wait_time + s.conflict_wait_time,
+            state_of_health: total.state_of_health.min(s.state_of_health),
+            equivalent_full_cycles: total.equivalent_full_cycles + s.equivalent_full_cycles,
+        })
+    }
+}
+
+/// Returns the mean localization error of two stats weighted by their duration.
+fn weighted_mean_error(a: &AgentEpisodeStats, b: &AgentEpisodeStats) -> Length {
+    let (a_time, b_time) = (a.duration().to_base_unit(), b.duration().to_base_unit());
+    if a_time + b_time <= 0.0 {
+        return Length::ZERO;
+    }
+    Length::meters(
+        (a.mean_localization_error.to_base_unit() * a_time
+            + b.mean_localization_error.to_base_unit() * b_time)
+            / (a_time + b_time),
+    )
 }
 
 /// Contains statistics for an environment episode, including all agents.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct EnvEpisodeStats {
+    #[serde(default)]
+    pub seed: u64,
     pub n_completed_tasks: u32,
     pub env_duration: Duration,
     pub agents: HashMap<AgentId, AgentEpisodeStats>,
+    #[serde(default)]
+    pub groups: Vec<GroupEpisodeStats>,
+    #[serde(default)]
+    pub n_blocked_tasks: u32, // work tasks left that no agent is capable of
+    #[serde(default)]
+    pub blocked_capabilities: Vec<Vec<String>>, // distinct capabilities required by blocked tasks
+    #[serde(default)]
+    pub n_planning_fallbacks: u32, // paths planned without regard to other agents
+}
+
+/// Contains statistics for a group of agents sharing an agent config.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GroupEpisodeStats {
+    pub agent_config_path: String,
+    pub agent_ids: Vec<AgentId>,
+    /// Stats of all agents in the group added up.
+    pub combined: AgentEpisodeStats,
 }
 
 // ---------- Aggregated Types ----------
 
 /// Represents a min/average/max summary of a set of values.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct StatSummary<T> {
     pub min: T,
     pub avg: T,
@@ -189,6 +316,23 @@ pub struct AgentResultSummary {
     pub energy_charged: StatSummary<Energy>,
     pub energy_discharged: StatSummary<Energy>,
     pub distance_travelled: StatSummary<Length>,
+
+    #[serde(default)]
+    pub yield_time: StatSummary<Duration>,
+    #[serde(default)]
+    pub n_collisions: StatSummary<f32>,
+    #[serde(default)]
+    pub n_near_misses: StatSummary<f32>,
+    #[serde(default)]
+    pub conflict_wait_time: StatSummary<Duration>,
+    #[serde(default)]
+    pub mean_localization_error: StatSummary<Length>,
+    #[serde(default)]
+    pub max_localization_error: StatSummary<Length>,
+    #[serde(default)]
+    pub state_of_health: StatSummary<f32>,
+    #[serde(default)]
+    pub equivalent_full_cycles: StatSummary<f32>,
 }
 impl AgentResultSummary {
     pub fn from_episodes(stats: &[AgentEpisodeStats]) -> Self {
@@ -202,6 +346,14 @@ impl AgentResultSummary {
             energy_charged: summarize(stats.iter().map(|s| s.energy_charged)),
             energy_discharged: summarize(stats.iter().map(|s| s.energy_discharged)),
             distance_travelled: summarize(stats.iter().map(|s| s.distance_travelled)),
+            yield_time: summarize(stats.iter().map(|s| s.yield_time)),
+            n_collisions: summarize(stats.iter().map(|s| s.n_collisions)),
+            n_near_misses: summarize(stats.iter().map(|s| s.n_near_misses)),
+            conflict_wait_time: summarize(stats.iter().map(|s| s.conflict_wait_time)),
+            mean_localization_error: summarize(stats.iter().map(|s| s.mean_localization_error)),
+            max_localization_error: summarize(stats.iter().map(|s| s.max_localization_error)),
+            state_of_health: summarize(stats.iter().map(|s| s.state_of_health)),
+            equivalent_full_cycles: summarize(stats.iter().map(|s| s.equivalent_full_cycles)),
         }
     }
 }
@@ -211,19 +363,31 @@ impl AgentResultSummary {
 pub struct EnvResult {
     pub n_episodes: u32,
     pub env_config: EnvConfig,
+    #[serde(default)]
+    pub seeds: Vec<u64>, // episode seeds, in order
     pub n_completed_tasks: StatSummary<f32>,
     pub env_duration: StatSummary<Duration>,
     pub agents: HashMap<AgentId, AgentResultSummary>,
     pub combined_agents: AgentEpisodeStats,
+    #[serde(default)]
+    pub combined_groups: Vec<GroupEpisodeStats>,
+    #[serde(default)]
+    pub n_blocked_tasks: StatSummary<f32>,
+    #[serde(default)]
+    pub n_planning_fallbacks: StatSummary<f32>,
 }
 impl EnvResult {
     /// Aggregates statistics across multiple env episodes.
     pub fn from_episodes(env_config: EnvConfig, episodes: Vec<EnvEpisodeStats>) -> Self {
         let n_episodes = episodes.len() as u32;
+        let seeds = episodes.iter().map(|e| e.seed).collect();
 
         // Compute min/avg/max for top-level env stats
         let n_completed_tasks = summarize(episodes.iter().map(|e| e.n_completed_tasks as f32));
         let env_duration = summarize(episodes.iter().map(|e| e.env_duration));
+        let n_blocked_tasks = summarize(episodes.iter().map(|e| e.n_blocked_tasks as f32));
+        let n_planning_fallbacks =
+            summarize(episodes.iter().map(|e| e.n_planning_fallbacks as f32));
 
         // Aggregate agent stats
         let mut agents_map: HashMap<AgentId, Vec<AgentEpisodeStats>> = HashMap::new();
@@ -248,48 +412,37 @@ impl EnvResult {
         for stats in agents_map.clone().values() {
             all_agent_stats.extend_from_slice(stats);
         }
-        // Compute summed totals
-        let mut total_work_time = Duration::ZERO;
-        let mut total_travel_time = Duration::ZERO;
-        let mut total_idle_time = Duration::ZERO;
-        let mut total_charging_time = Duration::ZERO;
-        let mut total_queue_time = Duration::ZERO;
-        let mut total_discharged_time = Duration::ZERO;
-        let mut total_energy_charged = Energy::ZERO;
-        let mut total_energy_discharged = Energy::ZERO;
-        let mut total_distance_travelled = Length::ZERO;
-
-        for stats in &all_agent_stats {
-            total_work_time = total_work_time + stats.work_time;
-            total_travel_time = total_travel_time + stats.travel_time;
-            total_idle_time = total_idle_time + stats.idle_time;
-            total_charging_time = total_charging_time + stats.charging_time;
-            total_queue_time = total_queue_time + stats.queue_time;
-            total_discharged_time = total_discharged_time + stats.discharged_time;
-            total_energy_charged = total_energy_charged + stats.energy_charged;
-            total_energy_discharged = total_energy_discharged + stats.energy_discharged;
-            total_distance_travelled = total_distance_travelled + stats.distance_travelled;
-        }
-
-        let combined_agents = AgentEpisodeStats {
-            work_time: total_work_time,
-            travel_time: total_travel_time,
-            idle_time: total_idle_time,
-            charging_time: total_charging_time,
-            queue_time: total_queue_time,
-            discharged_time: total_discharged_time,
-            energy_charged: total_energy_charged,
-            energy_discharged: total_energy_discharged,
-            distance_travelled: total_distance_travelled,
-        };
+        let combined_agents = AgentEpisodeStats::total(&all_agent_stats);
+
+        // Groups are the same in every episode, their stats add up like `combined_agents`
+        let combined_groups = episodes
+            .first()
+            .map(|first| {
+                (0..first.groups.len())
+                    .map(|i| GroupEpisodeStats {
+                        combined: AgentEpisodeStats::total(
+                            episodes
+                                .iter()
+                                .filter_map(|e| e.groups.get(i))
+                                .map(|group| &group.combined),
+                        ),
+                        ..first.groups[i].clone()
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
 
         Self {
             n_episodes,
             env_config,
+            seeds,
             n_completed_tasks,
             env_duration,
             agents,
             combined_agents,
+            combined_groups,
+            n_blocked_tasks,
+            n_planning_fallbacks,
         }
     }
 }
@@ -344,12 +497,12 @@ pub struct AnalyzeEnvResult {
 }
 impl AnalyzeEnvResult {
     /// Aggregates statistics across multiple env episodes.
-    pub fn from_episodes(
-        n_agents: u32,
-        combination: Combination,
-        episodes: Vec<EnvEpisodeStats>,
-    ) -> Self {
+    /// Per-agent averages are taken over the agents of the episodes.
+    pub fn from_episodes(combination: Combination, episodes: Vec<EnvEpisodeStats>) -> Self {
         let n_episodes = episodes.len() as u32;
+        let n_agents = episodes
+            .first()
+            .map_or(0, |episode| episode.agents.len() as u32);
 
         // Compute min/avg/max for top-level env stats
         let vec_n_completed_tasks: Vec<f32> = episodes
@@ -395,6 +548,10 @@ impl AnalyzeEnvResult {
         let mut total_energy_charged = Energy::ZERO;
         let mut total_energy_discharged = Energy::ZERO;
         let mut total_distance_travelled = Length::ZERO;
+        let mut total_yield_time = Duration::ZERO;
+        let mut total_collisions = 0.0;
+        let mut total_near_misses = 0.0;
+        let mut total_conflict_wait_time = Duration::ZERO;
 
         for stats in &all_agent_stats {
             total_work_time = total_work_time + stats.work_time;
@@ -406,6 +563,10 @@ impl AnalyzeEnvResult {
             total_energy_charged = total_energy_charged + stats.energy_charged;
             total_energy_discharged = total_energy_discharged + stats.energy_discharged;
             total_distance_travelled = total_distance_travelled + stats.distance_travelled;
+            total_yield_time = total_yield_time + stats.yield_time;
+            total_collisions += stats.n_collisions;
+            total_near_misses += stats.n_near_misses;
+            total_conflict_wait_time = total_conflict_wait_time + stats.conflict_wait_time;
         }
         // let vec_travel: Vec<f32> = all_agent_stats
         //     .iter()
@@ -423,6 +584,14 @@ impl AnalyzeEnvResult {
         let avg_energy_charged = total_energy_charged / n_episodes as f32;
         let avg_energy_discharged = total_energy_discharged / n_episodes as f32;
         let avg_distance_travelled = total_distance_travelled / n_episodes as f32;
+        let avg_yield_time = total_yield_time / n_episodes as f32;
+        let avg_collisions = total_collisions / n_episodes as f32;
+        let avg_near_misses = total_near_misses / n_episodes as f32;
+        let avg_conflict_wait_time = total_conflict_wait_time / n_episodes as f32;
+        // Localization errors and the state of health are not summed,
+        // both rows show the one over all agents
+        let combined = AgentEpisodeStats::total(&all_agent_stats);
+        let avg_full_cycles = combined.equivalent_full_cycles / n_episodes as f32;
 
         let agent_totaled_stats = AgentEpisodeStats {
             work_time: avg_work_time,
@@ -434,6 +603,14 @@ impl AnalyzeEnvResult {
             energy_charged: avg_energy_charged,
             energy_discharged: avg_energy_discharged,
             distance_travelled: avg_distance_travelled,
+            yield_time: avg_yield_time,
+            n_collisions: avg_collisions,
+            n_near_misses: avg_near_misses,
+            conflict_wait_time: avg_conflict_wait_time,
+            mean_localization_error: combined.mean_localization_error,
+            max_localization_error: combined.max_localization_error,
+            state_of_health: combined.state_of_health,
+            equivalent_full_cycles: avg_full_cycles,
         };
 
         let agent_averaged_stats = AgentEpisodeStats {
@@ -446,6 +623,14 @@ impl AnalyzeEnvResult {
             energy_charged: avg_energy_charged / n_agents as f32,
             energy_discharged: avg_energy_discharged / n_agents as f32,
             distance_travelled: avg_distance_travelled / n_agents as f32,
+            yield_time: avg_yield_time / n_agents as f32,
+            n_collisions: avg_collisions / n_agents as f32,
+            n_near_misses: avg_near_misses / n_agents as f32,
+            conflict_wait_time: avg_conflict_wait_time / n_agents as f32,
+            mean_localization_error: combined.mean_localization_error,
+            max_localization_error: combined.max_localization_error,
+            state_of_health: combined.state_of_health,
+            equivalent_full_cycles: avg_full_cycles / n_agents as f32,
         };
 
         Self {
@@ -91,6 +91,13 @@ impl ChargingStrategy {
             ChargingStrategy::ThresholdWithLimit(60.0, 45.0),
         ]
     }
+    /// Returns SoC values (0.0 - 100.0) below which the strategy may send an agent charging.
+    pub fn soc_thresholds(&self) -> Vec<f32> {
+        match self {
+            Self::CriticalOnly(critical) => vec![*critical],
+            Self::ThresholdWithLimit(threshold, critical) => vec![*threshold, *critical],
+        }
+    }
 }
 impl std::fmt::Display for ChargingStrategy {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
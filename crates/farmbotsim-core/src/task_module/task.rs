@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 
 use crate::{
@@ -6,7 +7,7 @@ use crate::{
 };
 
 /// Represents the intention of a task.
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub enum Intent {
     /// Performing work-related tasks
     Work,
@@ -16,10 +17,12 @@ pub enum Intent {
     Queue,
     /// Idle
     Idle,
+    /// Waiting for another agent to pass
+    Yield,
 }
 
 /// Represents different types of tasks an agent can perform, including stationary/moving work, travel, and waiting.
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub enum Task {
     /// A work stationary task at a specific pose, with duration and associated metadata.
     Stationary {
@@ -32,6 +35,7 @@ pub enum Task {
         line_id: u32,
         power: Power,
         info: String,
+        required_capabilities: Vec<String>,
     },
     /// A work moving task along a path at a specified velocity, with associated metadata.
     Moving {
@@ -43,12 +47,15 @@ pub enum Task {
         farm_entity_id: u32,
         power: Power,
         info: String,
+        required_capabilities: Vec<String>,
     },
     /// A travel task representing movement along a path.
     Travel {
         path: VecDeque<Pose>,
         velocity: LinearVelocity,
         intent: Intent,
+        /// Whether consecutive poses are connected by arcs, see `smooth_path`.
+        smoothed: bool,
     },
     /// A waiting task for a specified duration with an intent.
     WaitDuration { duration: Duration, intent: Intent },
@@ -63,6 +70,16 @@ impl Task {
             path: VecDeque::from(path),
             velocity,
             intent,
+            smoothed: false,
+        }
+    }
+    /// Creates a travel task along a smoothed path, driving arcs between consecutive poses.
+    pub fn smoothed_travel(path: Vec<Pose>, velocity: LinearVelocity, intent: Intent) -> Self {
+        Task::Travel {
+            path: VecDeque::from(path),
+            velocity,
+            intent,
+            smoothed: true,
         }
     }
     /// Creates a wait task for a fixed duration with the specified intent.
@@ -92,6 +109,20 @@ impl Task {
             _ => None,
         }
     }
+    /// Returns the capabilities an agent needs for the task, none for non-work tasks.
+    pub fn get_required_capabilities(&self) -> &[String] {
+        match self {
+            Task::Stationary {
+                required_capabilities,
+                ..
+            } => required_capabilities,
+            Task::Moving {
+                required_capabilities,
+                ..
+            } => required_capabilities,
+            _ => &[],
+        }
+    }
     /// Returns the path of poses associated with the task if any.
     pub fn get_path(&self) -> Option<VecDeque<Pose>> {
         match self {
@@ -146,6 +177,10 @@ impl Task {
     pub fn is_travel(&self) -> bool {
         matches!(self, Task::Travel { .. })
     }
+    /// Returns true if the task is a travel task along a smoothed path.
+    pub fn is_smoothed(&self) -> bool {
+        matches!(self, Task::Travel { smoothed: true, .. })
+    }
     /// Returns true if the task is a waiting task (either fixed duration or infinite).
     pub fn is_wait(&self) -> bool {
         matches!(self, Task::WaitDuration { .. } | Task::WaitInfinite { .. })
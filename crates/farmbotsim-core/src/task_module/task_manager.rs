@@ -1,4 +1,5 @@
 use egui::Pos2;
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet, VecDeque};
 
 use super::task::{Intent, Task};
@@ -15,54 +16,117 @@ use crate::{
             farm_stages::FarmStages,
         },
         field_config::FieldConfig,
+        obstacle::Obstacle,
         station_module::station::{Station, StationId, StationPosType},
     },
+    error::FarmbotSimError,
+    event_module::event::EventKind,
     movement_module::pose::{Pose, path_to_poses},
-    path_finding_module::{path_finding::PathFinding, visibility_graph::VisibilityGraph},
+    path_finding_module::{
+        path_cache::PathCache,
+        path_finding::{FollowUpMotion, PathFinding, PathRequest},
+        path_planner::{PathPlanner, PathPlannerKind},
+        path_smoothing::smooth_path,
+    },
     task_module::{
         strategies::{ChargingStrategy, ChooseStationStrategy},
         task_manager_config::TaskManagerConfig,
     },
-    units::{duration::Duration, length::Length},
+    units::{angle::Angle, duration::Duration, length::Length, linear_velocity::LinearVelocity},
 };
 
 /// Manages task assignment, tracking, and execution for farm entities.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TaskManager {
     id_counter: u32,
     field_config: FieldConfig,
 
     pub farm_entities: HashMap<u32, FarmEntity>,
+    n_work_actions: Option<u32>,
     pub waiting: HashMap<u32, Duration>, // stores and decremend all waiting actions
 
     pub work_list: VecDeque<Task>,
     pub assigned_tasks: Vec<Task>,
     pub completed_tasks: Vec<Task>,
-    visibility_graph: VisibilityGraph,
+    path_planner: PathPlanner,
+    planning_radius: Length, // obstacles are inflated by it for path planning
+    #[serde(skip)]
+    planning_obstacles: Vec<Obstacle>,
+    path_smoothing: bool,
+    #[serde(skip)]
+    path_cache: PathCache,
+    planning_time: Duration, // departure time of planned paths, set by `assign_tasks`
 
     pub charging_strategy: ChargingStrategy,
     pub choose_station_strategy: ChooseStationStrategy,
+
+    pub pending_events: Vec<EventKind>, // for storing so env can collect
 }
 
 impl TaskManager {
     /// Creates a new `TaskManager` instance from given configurations and initializes state.
-    pub fn from_config(task_manager_config: TaskManagerConfig, field_config: FieldConfig) -> Self {
-        let farm_entities = field_config.get_farm_entities();
+    /// Panics if the farm entities can't be built from the field configuration.
+    pub fn from_config(
+        task_manager_config: TaskManagerConfig,
+        field_config: FieldConfig,
+        path_planner: PathPlannerKind,
+        planning_radius: Length,
+        path_smoothing: bool,
+    ) -> Self {
+        Self::try_from_config(
+            task_manager_config,
+            field_config,
+            path_planner,
+            planning_radius,
+            path_smoothing,
+        )
+        .unwrap_or_else(|e| e.log_and_panic())
+    }
+
+    /// Creates a new `TaskManager` instance from given configurations and initializes state.
+    /// Returns an error if the farm entities can't be built from the field configuration.
+    pub fn try_from_config(
+        task_manager_config: TaskManagerConfig,
+        field_config: FieldConfig,
+        path_planner: PathPlannerKind,
+        planning_radius: Length,
+        path_smoothing: bool,
+    ) -> Result<Self, FarmbotSimError> {
+        let farm_entities = field_config.try_get_farm_entities()?;
         let (id_counter, work_list) = Self::get_initial_work_list(&farm_entities);
-        let obstacles = field_config.get_obstacles();
-        let visibility_graph = VisibilityGraph::new(&field_config.get_graph_points(), obstacles);
-        Self {
+        let n_work_actions = Self::count_work_actions(&farm_entities);
+        let planning_obstacles = field_config.get_inflated_obstacles(planning_radius);
+        let mut path_planner = PathPlanner::new(
+            path_planner,
+            &field_config.get_inflated_graph_points(planning_radius),
+            planning_obstacles.clone(),
+        );
+        path_planner.set_no_go_zones(field_config.no_go_zones.clone());
+        let pending_events = work_list
+            .iter()
+            .filter_map(EventKind::task_created)
+            .collect();
+        let mut task_manager = Self {
             id_counter,
             field_config,
             farm_entities,
+            n_work_actions,
             waiting: HashMap::new(),
             work_list,
             assigned_tasks: vec![],
             completed_tasks: vec![],
-            visibility_graph,
+            path_planner,
+            planning_radius,
+            planning_obstacles,
+            path_smoothing,
+            path_cache: PathCache::default(),
+            planning_time: Duration::ZERO,
             charging_strategy: task_manager_config.charging_strategy,
             choose_station_strategy: task_manager_config.choose_station_strategy,
-        }
+            pending_events,
+        };
+        task_manager.rebuild_path_cache();
+        Ok(task_manager)
     }
 
     /// Converts the `TaskManager` back into a `TaskManagerConfig`.
@@ -82,6 +146,122 @@ impl TaskManager {
         self.work_list = work_list;
         self.assigned_tasks.clear();
         self.completed_tasks.clear();
+        self.path_planner.reset();
+        self.planning_time = Duration::ZERO;
+        self.pending_events = self
+            .work_list
+            .iter()
+            .filter_map(EventKind::task_created)
+            .collect();
+    }
+
+    /// Rebuilds the path planner's graph from the field configuration, e.g. after deserialization.
+    pub fn rebuild_path_planner(&mut self) {
+        self.planning_obstacles = self
+            .field_config
+            .get_inflated_obstacles(self.planning_radius);
+        self.path_planner.recalculate(
+            &self
+                .field_config
+                .get_inflated_graph_points(self.planning_radius),
+            &self.planning_obstacles,
+        );
+        self.path_planner
+            .set_no_go_zones(self.field_config.no_go_zones.clone());
+        self.rebuild_path_cache();
+    }
+
+    /// Replaces the path cache with an empty one keyed by the start and end positions of the work.
+    fn rebuild_path_cache(&mut self) {
+        let mut farm_entities: Vec<_> = self.farm_entities.values().collect();
+        farm_entities.sort_by_key(|farm_entity| farm_entity.get_id());
+        let key_points = farm_entities
+            .into_iter()
+            .flat_map(|farm_entity| match farm_entity {
+                FarmEntity::Crop(crop) => vec![crop.position],
+                FarmEntity::Row(row) => row
+                    .path
+                    .first()
+                    .into_iter()
+                    .chain(row.path.last())
+                    .copied()
+                    .collect(),
+            });
+        self.path_cache = PathCache::default();
+        self.path_cache.add_key_points(key_points);
+        self.path_cache.make_key_points_permanent();
+    }
+
+    /// Adds the station, slot and queue positions as permanent key points of the path cache
+    /// and replaces the spawn positions of the previous episode with those of the agents.
+    pub fn add_path_cache_key_points(&mut self, agents: &[Agent], stations: &[Station]) {
+        self.path_cache.remove_temporary_key_points();
+        let station_positions = stations.iter().flat_map(|station| {
+            let slots =
+                (0..station.n_slots as usize).filter_map(|slot| station.get_pose_for_slot(slot));
+            let queue = (0..agents.len()).map(|i| station.get_waiting_pose(i));
+            slots
+                .chain(queue)
+                .map(|pose| pose.position)
+                .chain([station.pose.position])
+        });
+        self.path_cache.add_key_points(station_positions);
+        self.path_cache.make_key_points_permanent();
+        self.path_cache
+            .add_key_points(agents.iter().map(|agent| agent.spawn_position));
+    }
+
+    /// Returns the cache of paths between key points.
+    pub fn path_cache(&self) -> &PathCache {
+        &self.path_cache
+    }
+
+    /// Returns whether travel paths are smoothed into arcs.
+    pub fn path_smoothing(&self) -> bool {
+        self.path_smoothing
+    }
+
+    /// Returns the radius by which obstacles are inflated for path planning.
+    pub fn planning_radius(&self) -> Length {
+        self.planning_radius
+    }
+
+    /// Returns the obstacles used for path planning, inflated by the planning radius.
+    pub fn planning_obstacles(&self) -> &[Obstacle] {
+        &self.planning_obstacles
+    }
+
+    /// Returns how many travel paths of the episode were planned without regard to other agents,
+    /// because the path planner gave up planning around them.
+    pub fn n_planning_fallbacks(&self) -> u32 {
+        self.path_planner.n_fallbacks()
+    }
+
+    /// Returns the kind of path planner used for travel tasks.
+    pub fn path_planner_kind(&self) -> PathPlannerKind {
+        self.path_planner.kind()
+    }
+
+    /// Returns the total number of work actions across all farm entity plans,
+    /// or `None` if any plan contains a cycle.
+    pub fn number_of_work_actions(&self) -> Option<u32> {
+        self.n_work_actions
+    }
+
+    /// Counts work actions (excluding waits) across all farm entities, `None` if any plan cycles.
+    fn count_work_actions(farm_entities: &HashMap<u32, FarmEntity>) -> Option<u32> {
+        let mut n_actions = 0;
+        for entity in farm_entities.values() {
+            if entity.cycle().is_some() {
+                return None;
+            }
+            n_actions += entity
+                .stages()
+                .iter()
+                .filter(|stage| !matches!(stage, FarmEntityActionInstance::Wait { .. }))
+                .count() as u32;
+        }
+        Some(n_actions)
     }
 
     /// Generates the initial task list and ID counter from the provided farm entities.
@@ -114,6 +294,8 @@ impl TaskManager {
                     let next_task = next_action_instance.to_task(self.id_counter);
                     if let Some(next_task) = next_task {
                         self.id_counter += 1;
+                        self.pending_events
+                            .extend(EventKind::task_created(&next_task));
                         self.work_list.push_back(next_task);
                     } else if let FarmEntityActionInstance::Wait { id, duration, .. } =
                         next_action_instance
@@ -133,19 +315,16 @@ impl TaskManager {
 
         let mut finished_ids = Vec::new();
 
-        // Collect and sort keys for deterministic iteration
-        let mut keys: Vec<_> = self.waiting.keys().cloned().collect();
-        keys.sort(); // requires that your ID type implements Ord
-
-        for id in keys {
-            if let Some(duration) = self.waiting.get_mut(&id) {
-                *duration = *duration - duration_;
-                if duration.value <= 0.0 {
-                    finished_ids.push(id);
-                }
+        for (id, duration) in self.waiting.iter_mut() {
+            *duration = *duration - duration_;
+            if duration.value <= 0.0 {
+                finished_ids.push(*id);
             }
         }
 
+        // Sort finished ids for deterministic task creation
+        finished_ids.sort();
+
         for id in finished_ids {
             self.waiting.remove(&id);
             self.add_new_task_for_id(id);
@@ -161,6 +340,8 @@ impl TaskManager {
                 let next_task = next_action_instance.to_task(self.id_counter);
                 if let Some(next_task) = next_task {
                     self.id_counter += 1;
+                    self.pending_events
+                        .extend(EventKind::task_created(&next_task));
                     self.work_list.push_back(next_task);
                 } else if let FarmEntityActionInstance::Wait { id, duration, .. } =
                     next_action_instance
@@ -172,7 +353,14 @@ impl TaskManager {
     }
 
     /// (main) Assigns tasks to agents and manages their states, including handling discharged, charging, and idle agents.
-    pub fn assign_tasks(&mut self, agents: &mut Vec<Agent>, stations: &mut [Station]) {
+    /// `time` is the elapsed episode time, paths are planned to depart then.
+    pub fn assign_tasks(
+        &mut self,
+        agents: &mut Vec<Agent>,
+        stations: &mut [Station],
+        time: Duration,
+    ) {
+        self.planning_time = time;
         let mut agent_ids_updated = HashSet::new();
         let mut station_ids_updated = HashSet::new();
         for agent in &mut *agents {
@@ -187,11 +375,13 @@ impl TaskManager {
                 if let Some(task) = agent.current_task.take() {
                     if task.get_intent() == &Intent::Work {
                         // Return work task
+                        self.record_task_returned(&task, agent.id);
                         self.work_list.push_front(task);
                     }
                     for ws_task in &agent.work_schedule.tasks {
                         if *ws_task.get_intent() == Intent::Work {
                             // Return work task
+                            self.record_task_returned(ws_task, agent.id);
                             self.work_list.push_front(ws_task.clone());
                         }
                     }
@@ -333,21 +523,18 @@ impl TaskManager {
                         }
 
                         // Deterministic pathfinding + scheduling
-                        if let Some(path) = self
-                            .visibility_graph
-                            .find_path(agent.pose.position, pose.position)
-                        {
-                            let mut path = path_to_poses(path);
-                            if let Some(last) = path.last_mut() {
-                                last.orientation = pose.orientation;
-                            }
-
-                            let travel_task =
-                                Task::travel(path, agent.movement.max_velocity(), intent.clone());
+                        if let Some(travel_tasks) = self.plan_travel(
+                            agent,
+                            pose.position,
+                            Some(pose.orientation),
+                            intent.clone(),
+                        ) {
                             let wait_task = Task::wait_infinite(intent);
 
                             agent.work_schedule.clear();
-                            agent.work_schedule.push_back(travel_task);
+                            for travel_task in travel_tasks {
+                                agent.work_schedule.push_back(travel_task);
+                            }
                             agent.work_schedule.push_back(wait_task);
                             agent.current_task = agent.work_schedule.pop_front();
 
@@ -374,6 +561,7 @@ impl TaskManager {
         }
         //self.work_list.extend(tasks_to_return.clone());
         for task in tasks_to_return.clone().into_iter().rev() {
+            self.record_task_returned(&task, agent.id);
             self.work_list.push_front(task);
         }
         self.assigned_tasks.retain(|task| {
@@ -431,21 +619,15 @@ impl TaskManager {
         let station_index = self.choose_station_index(agent, stations);
         let station = &mut stations[station_index];
         let (pose, pos_type) = station.request_charge(agent.id);
-        let path = self
-            .visibility_graph
-            .find_path(agent.pose.position, pose.position);
-        if let Some(path) = path {
-            let mut path = path_to_poses(path);
-            if let Some(last) = path.last_mut() {
-                last.orientation = pose.orientation;
-            }
-            let intent = match pos_type {
-                StationPosType::ChargingSlot => Intent::Charge,
-                StationPosType::QueueSlot => Intent::Queue,
-            };
-            let task = Task::wait_infinite(intent.clone());
-            let travel_task = Task::travel(path, agent.movement.max_velocity(), intent);
-            tasks.push(travel_task);
+        let intent = match pos_type {
+            StationPosType::ChargingSlot => Intent::Charge,
+            StationPosType::QueueSlot => Intent::Queue,
+        };
+        let travel_tasks =
+            self.plan_travel(agent, pose.position, Some(pose.orientation), intent.clone());
+        if let Some(travel_tasks) = travel_tasks {
+            let task = Task::wait_infinite(intent);
+            tasks.extend(travel_tasks);
             tasks.push(task);
         } else {
             station.release_agent(agent.id); // path to station was not found
@@ -455,13 +637,21 @@ impl TaskManager {
     }
 
     /// Retrieves and organizes work tasks for the agent based on proximity and task grouping.
+    /// Only tasks the agent is capable of are considered.
     pub fn get_work_tasks(&mut self, agent: &Agent) -> Vec<Task> {
         let mut tasks: Vec<Task> = vec![];
 
-        if let Some(task) = self.work_list.pop_front() {
+        let index = self
+            .work_list
+            .iter()
+            .position(|task| agent.is_capable_of(task));
+        if let Some(index) = index
+            && let Some(task) = self.work_list.remove(index)
+        {
             let mut related_tasks: Vec<_> = self
                 .work_list
                 .iter()
+                .filter(|other| agent.is_capable_of(other))
                 .filter_map(|other| match other {
                     Task::Stationary {
                         field_id, line_id, ..
@@ -499,7 +689,7 @@ impl TaskManager {
                 })
                 .collect();
             related_tasks.push(task.clone());
-            let reference_pos = agent.pose.position;
+            let reference_pos = agent.estimated_pose.position;
             related_tasks.sort_by(|a, b| {
                 let a_pos = match a {
                     Task::Stationary { pose, .. } => pose.position,
@@ -521,44 +711,46 @@ impl TaskManager {
             });
             let target_pose = related_tasks[0].get_first_pose();
             if let Some(target_pose) = target_pose {
-                let path = self
-                    .visibility_graph
-                    .find_path(agent.pose.position, target_pose.position);
-                if let Some(path) = path {
+                let travel_tasks =
+                    self.plan_travel(agent, target_pose.position, None, Intent::Work);
+                if let Some(travel_tasks) = travel_tasks {
+                    let n_travel_tasks = travel_tasks.len();
                     for (i, task) in related_tasks.iter().enumerate() {
-                        let (velocity, path_) = match i {
-                            0 => (agent.movement.max_velocity(), path.clone()),
+                        match i {
+                            0 => tasks.extend(travel_tasks.clone()), // Travel to first task
                             _ => {
-                                if let Some(pose) = task.get_first_pose() {
-                                    (
-                                        MAX_VELOCITY_BETWEEN_POINTS
-                                            .min(agent.movement.max_velocity()),
-                                        vec![pose.position],
-                                    )
-                                } else {
-                                    (
-                                        MAX_VELOCITY_BETWEEN_POINTS
-                                            .min(agent.movement.max_velocity()),
-                                        vec![],
-                                    )
-                                }
+                                let path_ = match task.get_first_pose() {
+                                    Some(pose) => vec![pose.position],
+                                    None => vec![],
+                                };
+                                let velocity =
+                                    MAX_VELOCITY_BETWEEN_POINTS.min(agent.movement.max_velocity());
+                                let path_ = path_to_poses(path_);
+                                tasks.push(Task::travel(path_, velocity, Intent::Work)); // Travel to task
                             }
-                        };
-                        let path_ = path_to_poses(path_);
-                        tasks.push(Task::travel(path_, velocity, Intent::Work)); // Travel to task
+                        }
                         tasks.push(task.clone()); // Task
                     }
                     for task_ in tasks.clone() {
                         if task_.is_work() {
+                            if let Some(task_id) = task_.get_id() {
+                                self.pending_events.push(EventKind::TaskAssigned {
+                                    task_id: *task_id,
+                                    agent_id: agent.id,
+                                });
+                            }
                             self.assigned_tasks.push(task_);
                         }
                     }
 
+                    self.path_planner
+                        .follow_up(agent.id, &Self::follow_up_motions(&tasks[n_travel_tasks..]));
+
                     // Remove related tasks from work_list
                     self.work_list
                         .retain(|task| !tasks.clone().iter().any(|related| task == related));
                 } else {
-                    self.work_list.push_front(task); // Add task back if path to it is None
+                    self.work_list.insert(index, task); // Add task back if path to it is None
                 }
             }
         }
@@ -566,27 +758,143 @@ impl TaskManager {
         tasks
     }
 
+    /// Returns true if the work list holds a task the agent is capable of.
+    pub fn has_work_for(&self, agent: &Agent) -> bool {
+        self.work_list.iter().any(|task| agent.is_capable_of(task))
+    }
+
+    /// Returns the tasks of the work list that none of the agents is capable of.
+    pub fn blocked_tasks<'a>(&'a self, agents: &'a [Agent]) -> impl Iterator<Item = &'a Task> {
+        self.work_list
+            .iter()
+            .filter(|task| !agents.iter().any(|agent| agent.is_capable_of(task)))
+    }
+
     /// Generates idle tasks for the agent, typically involving traveling to its spawn position.
     pub fn get_idle_tasks(&mut self, agent: &Agent) -> Vec<Task> {
-        if Length::meters(agent.pose.position.distance(agent.spawn_position)) <= TOLERANCE_DISTANCE
+        if Length::meters(agent.estimated_pose.position.distance(agent.spawn_position))
+            <= TOLERANCE_DISTANCE
         {
             return vec![];
         }
         let mut tasks: Vec<Task> = vec![];
 
-        let path = self
-            .visibility_graph
-            .find_path(agent.pose.position, agent.spawn_position);
-        if let Some(path) = path {
-            let path = path_to_poses(path);
-            let travel_task = Task::travel(path, agent.movement.max_velocity(), Intent::Idle);
+        let travel_tasks = self.plan_travel(agent, agent.spawn_position, None, Intent::Idle);
+        if let Some(travel_tasks) = travel_tasks {
             let wait_task = Task::wait_infinite(Intent::Idle);
-            tasks.extend([travel_task, wait_task]);
+            tasks.extend(travel_tasks);
+            tasks.push(wait_task);
         }
 
         tasks
     }
 
+    /// Plans the agent's travel to `target` with the path planner and returns the travel tasks,
+    /// split by yield waits where the planner lets other agents pass first.
+    /// The last pose gets `orientation` if given. Returns `None` if no path was found.
+    fn plan_travel(
+        &mut self,
+        agent: &Agent,
+        target: Pos2,
+        orientation: Option<Angle>,
+        intent: Intent,
+    ) -> Option<Vec<Task>> {
+        let velocity = agent.movement.max_velocity();
+        let request = PathRequest {
+            agent_id: agent.id,
+            start: agent.estimated_pose.clone(),
+            end: target,
+            departure: self.planning_time,
+            velocity,
+            angular_velocity: agent.movement.max_angular_velocity(),
+            footprint_radius: agent.footprint_radius,
+        };
+        let timed_path = if self.path_planner.is_static() {
+            self.path_cache
+                .find_path(&mut self.path_planner, request.start.position, target)?
+                .into_iter()
+                .map(|position| (position, Duration::ZERO))
+                .collect()
+        } else {
+            self.path_planner.find_timed_path(&request)?
+        };
+
+        let mut tasks = vec![];
+        let mut segment = vec![];
+        for (position, wait) in timed_path {
+            segment.push(position);
+            if wait > Duration::ZERO {
+                if segment.len() > 1 {
+                    let segment = std::mem::take(&mut segment);
+                    let task = self.travel_task(agent, segment, intent.clone(), None);
+                    tasks.push(task);
+                }
+                tasks.push(Task::wait_duration(wait, Intent::Yield));
+                segment = vec![position];
+            }
+        }
+        tasks.push(self.travel_task(agent, segment, intent, orientation));
+        Some(tasks)
+    }
+
+    /// Creates a travel task for the agent along `path` at its maximum velocity,
+    /// smoothed to its turning limits if enabled. The last pose gets `orientation` if given.
+    fn travel_task(
+        &self,
+        agent: &Agent,
+        path: Vec<Pos2>,
+        intent: Intent,
+        orientation: Option<Angle>,
+    ) -> Task {
+        let velocity = agent.movement.max_velocity();
+        let mut poses = if self.path_smoothing {
+            smooth_path(
+                &path,
+                &self.planning_obstacles,
+                velocity,
+                agent.movement.max_angular_velocity(),
+            )
+        } else {
+            path_to_poses(path)
+        };
+        if let (Some(last), Some(orientation)) = (poses.last_mut(), orientation) {
+            last.orientation = orientation;
+        }
+        if self.path_smoothing {
+            Task::smoothed_travel(poses, velocity, intent)
+        } else {
+            Task::travel(poses, velocity, intent)
+        }
+    }
+
+    /// Returns the motions performed by the tasks, for planners that reserve them.
+    fn follow_up_motions(tasks: &[Task]) -> Vec<FollowUpMotion> {
+        let mut motions = vec![];
+        for task in tasks {
+            match task {
+                Task::Stationary { pose, duration, .. } => motions.push(FollowUpMotion {
+                    to: pose.position,
+                    velocity: LinearVelocity::ZERO,
+                    hold: *duration,
+                }),
+                Task::Moving { path, velocity, .. } | Task::Travel { path, velocity, .. } => {
+                    motions.extend(path.iter().map(|pose| FollowUpMotion {
+                        to: pose.position,
+                        velocity: *velocity,
+                        hold: Duration::ZERO,
+                    }))
+                }
+                Task::WaitDuration { duration, .. } => {
+                    if let Some(last) = motions.last_mut() {
+                        last.hold = last.hold + *duration;
+                    }
+                }
+                Task::WaitInfinite { .. } => {}
+            }
+        }
+        motions
+    }
+
     /// Adds a list of tasks to the agent's work schedule.
     fn assign_tasks_to_agent(&mut self, agent: &mut Agent, tasks: Vec<Task>) {
         if tasks.is_empty() {
@@ -604,6 +912,10 @@ impl TaskManager {
             self.assigned_tasks.retain(|task| {
                 if let Some(id) = task.get_id() {
                     if agent.completed_task_ids.contains(id) {
+                        self.pending_events.push(EventKind::TaskCompleted {
+                            task_id: *id,
+                            agent_id: agent.id,
+                        });
                         self.completed_tasks.push(task.clone());
                         completed_task = Some(task.clone());
                         false // Remove task from assigned_tasks
@@ -622,6 +934,16 @@ impl TaskManager {
         }
     }
 
+    /// Records that a work task was taken from the agent and returned to the work list.
+    fn record_task_returned(&mut self, task: &Task, agent_id: AgentId) {
+        if let Some(task_id) = task.get_id() {
+            self.pending_events.push(EventKind::TaskReturned {
+                task_id: *task_id,
+                agent_id,
+            });
+        }
+    }
+
     /// Applies the charging strategy to assign charging-related tasks to agents based on battery levels and station availability.
     fn charging_strategy(
         &mut self,
@@ -704,7 +1026,10 @@ impl TaskManager {
                     .iter()
                     .enumerate()
                     .min_by_key(|(_, station)| {
-                        let dist = manhattan_distance(agent.pose.position, station.pose.position);
+                        let dist = manhattan_distance(
+                            agent.estimated_pose.position,
+                            station.pose.position,
+                        );
                         let queue_penalty =
                             (station.n_occupied_slots() as f32 + station.queue.len() as f32) * 50.0;
                         // Interpolate distance vs queue
@@ -719,8 +1044,12 @@ impl TaskManager {
                     .iter()
                     .enumerate()
                     .filter_map(|(idx, station)| {
-                        self.visibility_graph
-                            .find_path(agent.pose.position, station.pose.position)
+                        self.path_cache
+                            .find_path(
+                                &mut self.path_planner,
+                                agent.estimated_pose.position,
+                                station.pose.position,
+                            )
                             .map(|path| {
                                 let dist: f32 = path.windows(2).map(|w| w[0].distance(w[1])).sum();
                                 let queue_penalty = (station.n_occupied_slots() as f32
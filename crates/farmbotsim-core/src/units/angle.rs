@@ -2,7 +2,7 @@ use std::str::FromStr;
 
 use egui::Vec2;
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, enum_iterator::Sequence)]
 pub enum AngleUnit {
     Degrees,
     Radians,
@@ -0,0 +1,60 @@
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, enum_iterator::Sequence)]
+pub enum CurrentUnit {
+    Milliamperes,
+    Amperes,
+}
+
+impl CurrentUnit {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CurrentUnit::Milliamperes => "mA",
+            CurrentUnit::Amperes => "A",
+        }
+    }
+}
+impl FromStr for CurrentUnit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "ma" | "milliampere" | "milliamperes" => Ok(CurrentUnit::Milliamperes),
+            "a" | "ampere" | "amperes" => Ok(CurrentUnit::Amperes),
+            _ => Err(format!("Unknown CurrentUnit: {s}")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Current {
+    pub value: f32,
+    pub unit: CurrentUnit,
+}
+
+impl Current {
+    pub const ZERO: Current = Current::amperes(0.0);
+
+    pub const fn new(value: f32, unit: CurrentUnit) -> Self {
+        Self { value, unit }
+    }
+    pub const fn milliamperes(value: f32) -> Self {
+        Self::new(value, CurrentUnit::Milliamperes)
+    }
+    pub const fn amperes(value: f32) -> Self {
+        Self::new(value, CurrentUnit::Amperes)
+    }
+
+    pub fn to_base_unit(self) -> f32 {
+        match self.unit {
+            CurrentUnit::Milliamperes => self.value / 1000.0,
+            CurrentUnit::Amperes => self.value,
+        }
+    }
+    pub fn from_base_unit(value: f32, unit: CurrentUnit) -> Self {
+        match unit {
+            CurrentUnit::Milliamperes => Self::new(value * 1000.0, unit),
+            CurrentUnit::Amperes => Self::new(value, unit),
+        }
+    }
+}
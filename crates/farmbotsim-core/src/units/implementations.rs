@@ -4,10 +4,12 @@ use std::fmt;
 use crate::units::{
     angle::{Angle, AngleUnit},
     angular_velocity::{AngularVelocity, AngularVelocityUnit},
+    current::{Current, CurrentUnit},
     duration::{Duration, DurationUnit},
     energy::{Energy, EnergyUnit},
     length::{Length, LengthUnit},
     linear_velocity::{LinearVelocity, LinearVelocityUnit},
+    mass::{Mass, MassUnit},
     power::{Power, PowerUnit},
     voltage::{Voltage, VoltageUnit},
 };
@@ -32,13 +34,36 @@ macro_rules! impl_ordering {
 
 impl_ordering!(Angle);
 impl_ordering!(AngularVelocity);
+impl_ordering!(Current);
 impl_ordering!(Duration);
 impl_ordering!(Energy);
 impl_ordering!(Length);
 impl_ordering!(LinearVelocity);
+impl_ordering!(Mass);
 impl_ordering!(Power);
 impl_ordering!(Voltage);
 
+macro_rules! impl_default {
+    ($type:ty) => {
+        impl Default for $type {
+            fn default() -> Self {
+                Self::ZERO
+            }
+        }
+    };
+}
+
+impl_default!(Angle);
+impl_default!(AngularVelocity);
+impl_default!(Current);
+impl_default!(Duration);
+impl_default!(Energy);
+impl_default!(Length);
+impl_default!(LinearVelocity);
+impl_default!(Mass);
+impl_default!(Power);
+impl_default!(Voltage);
+
 macro_rules! impl_ser_deser {
     ($type:ident, $unit_enum:ident) => {
         impl Serialize for $type {
@@ -79,10 +104,12 @@ macro_rules! impl_ser_deser {
 
 impl_ser_deser!(Angle, AngleUnit);
 impl_ser_deser!(AngularVelocity, AngularVelocityUnit);
+impl_ser_deser!(Current, CurrentUnit);
 impl_ser_deser!(Duration, DurationUnit);
 impl_ser_deser!(Energy, EnergyUnit);
 impl_ser_deser!(Length, LengthUnit);
 impl_ser_deser!(LinearVelocity, LinearVelocityUnit);
+impl_ser_deser!(Mass, MassUnit);
 impl_ser_deser!(Power, PowerUnit);
 impl_ser_deser!(Voltage, VoltageUnit);
 
@@ -103,9 +130,11 @@ macro_rules! impl_display {
 
 impl_display!(Angle, AngleUnit);
 impl_display!(AngularVelocity, AngularVelocityUnit);
+impl_display!(Current, CurrentUnit);
 impl_display!(Duration, DurationUnit);
 impl_display!(Energy, EnergyUnit);
 impl_display!(Length, LengthUnit);
 impl_display!(LinearVelocity, LinearVelocityUnit);
+impl_display!(Mass, MassUnit);
 impl_display!(Power, PowerUnit);
 impl_display!(Voltage, VoltageUnit);
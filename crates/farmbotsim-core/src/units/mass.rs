@@ -0,0 +1,60 @@
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, enum_iterator::Sequence)]
+pub enum MassUnit {
+    Grams,
+    Kilograms,
+}
+
+impl MassUnit {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MassUnit::Grams => "g",
+            MassUnit::Kilograms => "kg",
+        }
+    }
+}
+impl FromStr for MassUnit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "g" | "gram" | "grams" => Ok(MassUnit::Grams),
+            "kg" | "kilogram" | "kilograms" => Ok(MassUnit::Kilograms),
+            _ => Err(format!("Unknown MassUnit: {s}")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Mass {
+    pub value: f32,
+    pub unit: MassUnit,
+}
+
+impl Mass {
+    pub const ZERO: Mass = Mass::kilograms(0.0);
+
+    pub const fn new(value: f32, unit: MassUnit) -> Self {
+        Self { value, unit }
+    }
+    pub const fn grams(value: f32) -> Self {
+        Self::new(value, MassUnit::Grams)
+    }
+    pub const fn kilograms(value: f32) -> Self {
+        Self::new(value, MassUnit::Kilograms)
+    }
+
+    pub fn to_base_unit(self) -> f32 {
+        match self.unit {
+            MassUnit::Grams => self.value / 1000.0,
+            MassUnit::Kilograms => self.value,
+        }
+    }
+    pub fn from_base_unit(value: f32, unit: MassUnit) -> Self {
+        match unit {
+            MassUnit::Grams => Self::new(value * 1000.0, unit),
+            MassUnit::Kilograms => Self::new(value, unit),
+        }
+    }
+}
@@ -2,11 +2,13 @@
 
 pub mod angle; // base unit: RADIANS
 pub mod angular_velocity; // base unit: RADIANS/SECOND
+pub mod current; // base unit: AMPERE
 pub mod duration; // base unit: SECOND
 pub mod energy; // base unit: JOULE
 pub mod implementations;
 pub mod length; // base unit: METER
 pub mod linear_velocity; // base unit: METER/SECOND
+pub mod mass; // base unit: KILOGRAM
 pub mod operations;
 pub mod power; // base unit: WATT
 pub mod voltage; // base unit: VOLT
@@ -4,10 +4,12 @@ use std::ops::{Add, Div, Mul, Neg, Sub};
 use super::{
     angle::Angle,
     angular_velocity::AngularVelocity,
+    current::Current,
     duration::Duration,
     energy::{Energy, EnergyUnit},
     length::Length,
     linear_velocity::LinearVelocity,
+    mass::Mass,
     power::Power,
     voltage::Voltage,
 };
@@ -62,10 +64,12 @@ macro_rules! impl_same_type_ops {
 
 impl_same_type_ops!(Angle);
 impl_same_type_ops!(AngularVelocity);
+impl_same_type_ops!(Current);
 impl_same_type_ops!(Duration);
 impl_same_type_ops!(Energy);
 impl_same_type_ops!(Length);
 impl_same_type_ops!(LinearVelocity);
+impl_same_type_ops!(Mass);
 impl_same_type_ops!(Power);
 impl_same_type_ops!(Voltage);
 
@@ -145,6 +149,14 @@ impl Mul<Power> for Duration {
     }
 }
 
+// Current * Voltage = Power (W)
+impl Mul<Voltage> for Current {
+    type Output = Power;
+    fn mul(self, voltage: Voltage) -> Power {
+        Power::watts(self.to_base_unit() * voltage.to_base_unit())
+    }
+}
+
 // Duration * AngularVelocity = Angle (rad)
 impl Mul<Duration> for AngularVelocity {
     type Output = Angle;
@@ -1,15 +1,10 @@
 use egui::{Pos2, Rect};
-use rand::{rngs::StdRng, Rng, SeedableRng};
+use rand::Rng;
 
-use crate::{
-    cfg::RNG_SEED,
-    units::{angle::Angle, length::Length},
-};
+use crate::units::{angle::Angle, length::Length};
 
 /// Get random `Pos2` inside rect with angle.
-pub fn random_pos2_in_rect(rect: Rect, angle: Angle) -> Pos2 {
-    let mut rng = StdRng::seed_from_u64(RNG_SEED);
-
+pub fn random_pos2_in_rect<R: Rng>(rect: Rect, angle: Angle, rng: &mut R) -> Pos2 {
     // Generate random x and y coordinates within the bounds of the rectangle
     let x = rng.random_range(rect.min.x..rect.max.x);
     let y = rng.random_range(rect.min.y..rect.max.y);
@@ -1,7 +1,8 @@
 use egui::Pos2;
-use std::{any, fs, path::Path};
+use rand::Rng;
+use std::{f32::consts::PI, fs, path::Path};
 
-use crate::logger::log_error_and_panic;
+use crate::error::FarmbotSimError;
 
 /// Converts hsv to rgb
 fn hsv_to_rgb(h: f32, s: f32, v: f32) -> (u8, u8, u8) {
@@ -38,27 +39,24 @@ pub fn generate_colors(n: usize, hue_offset: f32) -> Vec<egui::Color32> {
     colors
 }
 
-/// Loads json or panics on failure.
-pub fn load_json_or_panic<T, P>(path: P) -> T
+/// Loads json, returning an error with the file path on failure.
+pub fn load_json<T, P>(path: P) -> Result<T, FarmbotSimError>
 where
     T: serde::de::DeserializeOwned,
     P: AsRef<Path>,
 {
     let path_ref = path.as_ref();
+    let data = fs::read_to_string(path_ref).map_err(|e| FarmbotSimError::io(path_ref, e))?;
+    serde_json::from_str(&data).map_err(|e| FarmbotSimError::parse::<T, _>(path_ref, e))
+}
 
-    // Read the file contents, or panic with logging if it fails
-    let data = fs::read_to_string(path_ref).unwrap_or_else(|e| {
-        let type_name = any::type_name::<T>();
-        let msg = format!("Failed to read file {path_ref:?} for {type_name}: {e}");
-        log_error_and_panic(&msg);
-    });
-
-    // Parse JSON, or panic with logging if it fails
-    serde_json::from_str(&data).unwrap_or_else(|e| {
-        let type_name = any::type_name::<T>();
-        let msg = format!("Failed to parse JSON from {path_ref:?} into {type_name}: {e}");
-        log_error_and_panic(&msg);
-    })
+/// Loads json or panics on failure.
+pub fn load_json_or_panic<T, P>(path: P) -> T
+where
+    T: serde::de::DeserializeOwned,
+    P: AsRef<Path>,
+{
+    load_json(path).unwrap_or_else(|e| e.log_and_panic())
 }
 
 /// Generates a set of `n` positions along a straight line with specified spacing and angle.
@@ -90,6 +88,75 @@ pub fn line_positions(n: usize, spacing: f32, angle: f32) -> Vec<Pos2> {
     positions
 }
 
+/// Checks if two line segments cross each other. Touching segments don't count.
+pub fn segments_intersect(a1: Pos2, a2: Pos2, b1: Pos2, b2: Pos2) -> bool {
+    let ccw = |a: Pos2, b: Pos2, c: Pos2| (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x);
+    let d1 = ccw(a1, a2, b1);
+    let d2 = ccw(a1, a2, b2);
+    let d3 = ccw(b1, b2, a1);
+    let d4 = ccw(b1, b2, a2);
+    ((d1 * d2) < 0.0) && ((d3 * d4) < 0.0)
+}
+
+/// Checks if a point lies inside a polygon using ray casting.
+pub fn point_in_polygon(point: Pos2, polygon: &[Pos2]) -> bool {
+    let mut inside = false;
+    let mut j = polygon.len().wrapping_sub(1);
+    for i in 0..polygon.len() {
+        let (pi, pj) = (polygon[i], polygon[j]);
+        if (pi.y > point.y) != (pj.y > point.y)
+            && point.x < (pj.x - pi.x) * (point.y - pi.y) / (pj.y - pi.y) + pi.x
+        {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
+
+/// Checks if two polygons overlap, i.e. their edges cross or one lies inside the other.
+pub fn polygons_overlap(a: &[Pos2], b: &[Pos2]) -> bool {
+    let edges = |polygon: &[Pos2]| {
+        (0..polygon.len())
+            .map(|i| (polygon[i], polygon[(i + 1) % polygon.len()]))
+            .collect::<Vec<_>>()
+    };
+    let edges_b = edges(b);
+    edges(a).iter().any(|(a1, a2)| {
+        edges_b
+            .iter()
+            .any(|(b1, b2)| segments_intersect(*a1, *a2, *b1, *b2))
+    }) || a.first().is_some_and(|p| point_in_polygon(*p, b))
+        || b.first().is_some_and(|p| point_in_polygon(*p, a))
+}
+
+/// Returns the length of the part of the segment from `a` to `b` that lies inside the polygon.
+pub fn segment_length_in_polygon(a: Pos2, b: Pos2, polygon: &[Pos2]) -> f32 {
+    let d = b - a;
+    let mut ts = vec![0.0, 1.0];
+    for i in 0..polygon.len() {
+        let (p1, p2) = (polygon[i], polygon[(i + 1) % polygon.len()]);
+        let e = p2 - p1;
+        let denominator = d.x * e.y - d.y * e.x;
+        if denominator.abs() <= f32::EPSILON {
+            continue;
+        }
+        let w = p1 - a;
+        let t = (w.x * e.y - w.y * e.x) / denominator;
+        let u = (w.x * d.y - w.y * d.x) / denominator;
+        if (0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u) {
+            ts.push(t);
+        }
+    }
+    ts.sort_by(f32::total_cmp);
+    let inside: f32 = ts
+        .windows(2)
+        .filter(|t| point_in_polygon(a + d * (t[0] + t[1]) / 2.0, polygon))
+        .map(|t| t[1] - t[0])
+        .sum();
+    inside * d.length()
+}
+
 /// Performs linear interpolation between two points.
 pub fn linear_interpolate(x0: f32, y0: f32, x1: f32, y1: f32, x: f32) -> f32 {
     if x1 == x0 {
@@ -97,3 +164,10 @@ pub fn linear_interpolate(x0: f32, y0: f32, x1: f32, y1: f32, x: f32) -> f32 {
     }
     y0 + (x - x0) * (y1 - y0) / (x1 - x0)
 }
+
+/// Samples a normally distributed value with zero mean (Box-Muller transform).
+pub fn random_normal<R: Rng>(rng: &mut R, std_dev: f32) -> f32 {
+    let u1 = 1.0 - rng.random::<f32>(); // in (0, 1] so the logarithm stays finite
+    let u2 = rng.random::<f32>();
+    std_dev * (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos()
+}
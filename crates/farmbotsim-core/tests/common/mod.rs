@@ -0,0 +1,24 @@
+use farmbotsim_core::prelude::*;
+
+/// Makes the config paths in `cfg` resolve by running from the repository root.
+pub fn use_repo_root() {
+    let root = concat!(env!("CARGO_MANIFEST_DIR"), "/../..");
+    std::env::set_current_dir(root).expect("Repository root not found");
+}
+
+/// Returns a simulation of the default scene with `n_agents` agents that runs for `hours`.
+pub fn default_simulation(n_agents: u32, fast_forward: bool, hours: f32) -> Simulation {
+    use_repo_root();
+    let env_config = EnvConfig {
+        fleet: vec![FleetGroup::new(
+            DEFAULT_AGENT_CONFIG_PATH.to_string(),
+            n_agents,
+        )],
+        fast_forward,
+        ..Default::default()
+    };
+    Simulation::from_config(
+        env_config,
+        TerminationCondition::EnvDuration(Duration::hours(hours)),
+    )
+}
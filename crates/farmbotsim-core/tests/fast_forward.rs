@@ -0,0 +1,30 @@
+mod common;
+
+use common::default_simulation;
+
+#[test]
+fn fast_forward_matches_stepping() {
+    let stepped = default_simulation(2, false, 12.0).run_episode();
+    let skipped = default_simulation(2, true, 12.0).run_episode();
+
+    assert!(stepped.n_completed_tasks > 0);
+    assert_eq!(stepped.n_completed_tasks, skipped.n_completed_tasks);
+    assert_eq!(stepped.agents.len(), skipped.agents.len());
+    for (id, stepped_agent) in &stepped.agents {
+        let skipped_agent = &skipped.agents[id];
+        // Skipped steps are summed up in one go, so only f32 rounding may differ
+        for (a, b) in [
+            (
+                stepped_agent.energy_discharged,
+                skipped_agent.energy_discharged,
+            ),
+            (stepped_agent.energy_charged, skipped_agent.energy_charged),
+        ] {
+            let (a, b) = (a.to_base_unit(), b.to_base_unit());
+            assert!(
+                (a - b).abs() <= 1e-4 * a.abs().max(1.0),
+                "agent {id}: {a} J stepped, {b} J fast-forwarded"
+            );
+        }
+    }
+}
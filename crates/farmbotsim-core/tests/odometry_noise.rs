@@ -0,0 +1,31 @@
+mod common;
+
+use common::default_simulation;
+use farmbotsim_core::prelude::*;
+
+#[test]
+fn odometry_noise_keeps_most_of_the_throughput() {
+    let exact = default_simulation(2, false, 12.0).run_episode();
+
+    let mut simulation = default_simulation(2, false, 12.0);
+    simulation.start_episode();
+    for agent in &mut simulation.env.agents {
+        if let Movement::RombaMovement(romba) = &mut agent.movement {
+            romba.noise = Some(OdometryNoise::default());
+        }
+    }
+    let noisy = simulation.run_to_termination();
+
+    assert!(
+        noisy
+            .agents
+            .values()
+            .all(|agent| agent.max_localization_error > Length::ZERO)
+    );
+    assert!(
+        noisy.n_completed_tasks as f32 >= 0.85 * exact.n_completed_tasks as f32,
+        "{} tasks with noise, {} without",
+        noisy.n_completed_tasks,
+        exact.n_completed_tasks
+    );
+}
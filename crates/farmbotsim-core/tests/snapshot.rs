@@ -0,0 +1,54 @@
+mod common;
+
+use common::default_simulation;
+use farmbotsim_core::prelude::*;
+
+#[test]
+fn restored_snapshot_continues_like_the_original() {
+    let mut original = default_simulation(2, false, 8.0);
+    original.start_episode();
+    while original.env.duration < Duration::hours(3.0) {
+        original.step();
+    }
+
+    let path =
+        std::env::temp_dir().join(format!("farmbotsim_snapshot_{}.json", std::process::id()));
+    original
+        .env
+        .to_snapshot(true)
+        .save_json_file(&path)
+        .expect("Snapshot could not be saved");
+    let snapshot = EnvSnapshot::from_json_file(&path).expect("Snapshot could not be loaded");
+    std::fs::remove_file(&path).ok();
+    let mut restored = Simulation::new(
+        Env::from_snapshot(snapshot),
+        original.termination_condition.clone(),
+    );
+
+    let original_stats = original.run_to_termination();
+    let restored_stats = restored.run_to_termination();
+
+    assert!(original_stats.n_completed_tasks > 0);
+    assert_eq!(original.env.step_count, restored.env.step_count);
+    assert_eq!(
+        original_stats.n_completed_tasks,
+        restored_stats.n_completed_tasks
+    );
+    for (original_agent, restored_agent) in original.env.agents.iter().zip(&restored.env.agents) {
+        assert_eq!(original_agent.state, restored_agent.state);
+        assert_eq!(
+            original_agent.completed_task_ids,
+            restored_agent.completed_task_ids
+        );
+        // Units keep the precision of their JSON format, so energies may differ slightly
+        let (a, b) = (
+            original_agent.battery.energy.to_base_unit(),
+            restored_agent.battery.energy.to_base_unit(),
+        );
+        assert!(
+            (a - b).abs() <= 1e-3 * a.abs().max(1.0),
+            "agent {:?}: {a} J original, {b} J restored",
+            original_agent.id
+        );
+    }
+}